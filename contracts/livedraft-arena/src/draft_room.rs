@@ -0,0 +1,3323 @@
+use linera_sdk::{
+    base::{ChainId, Owner, TimeDelta, Timestamp},
+    views::{linera_views, MapView, RegisterView, RootView, View, ViewStorageContext},
+    ContractRuntime,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::{ContractParameters, LiveDraftArena, Message, RoomStatus};
+
+/// Minimum number of players required before a draft can start
+pub const MIN_PLAYERS_TO_START: u8 = 2;
+
+/// Largest room capacity the Lobby creates or a creator can set
+pub const MAX_ROOM_CAPACITY: u8 = 8;
+
+/// Maximum length of a pool item name
+pub const MAX_ITEM_NAME_LEN: usize = 64;
+
+/// Maximum number of distinct items in a custom pool
+pub const MAX_POOL_ITEMS: usize = 100;
+
+/// Upper bound on `max_players * max_rounds`, keeping per-room pick storage bounded
+pub const MAX_TOTAL_PICKS: u32 = 256;
+
+/// Rounds every room drafts
+pub const DEFAULT_MAX_ROUNDS: u8 = 3;
+
+/// How many times each player may extend their own turn with `RequestMoreTime`
+pub const MAX_TIME_REQUESTS: u8 = 2;
+
+/// Seconds each `RequestMoreTime` adds to the turn deadline
+pub const TIME_REQUEST_SECS: u64 = 30;
+
+/// Most suggestions kept per player; the oldest are dropped first
+pub const MAX_SUGGESTIONS: usize = 10;
+
+/// Status of a draft room
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DraftStatus {
+    Waiting,
+    /// Players take turns removing items from the pool before picking starts
+    Banning,
+    Drafting,
+    Finished,
+    /// The two captains take turns drafting the other players into their squads
+    ///
+    /// Declared last so the earlier variants keep their checkpointed discriminants.
+    CaptainPicking,
+}
+
+/// An item that can be drafted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftItem {
+    pub id: u8,
+    pub name: String,
+    pub power: u32,
+    /// Copies remaining in the pool; the item leaves the pool when this reaches zero
+    #[serde(default = "default_quantity")]
+    pub quantity: u8,
+    /// Free-form categories such as "aggro" or "control"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Budget spent by picking this item in salary-cap rooms
+    #[serde(default)]
+    pub cost: u32,
+    /// Relative chance of being drawn when the pool is sampled from a larger set
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_quantity() -> u8 {
+    1
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl DraftItem {
+    /// Create a single-copy item
+    pub fn new(id: u8, name: &str, power: u32) -> Self {
+        DraftItem {
+            id,
+            name: name.to_string(),
+            power,
+            quantity: default_quantity(),
+            tags: Vec::new(),
+            cost: 0,
+            weight: default_weight(),
+        }
+    }
+}
+
+/// Check that a single pool item is acceptable
+///
+/// Shared by every pool-setup path and by the service, so items are judged
+/// the same way wherever they are submitted.
+pub fn validate_pool_item(item: &DraftItem) -> Result<(), DraftRoomError> {
+    if item.id == 0 {
+        return Err(DraftRoomError::InvalidPool("item ids must be non-zero".to_string()));
+    }
+    validate_item_name(item.id, &item.name)?;
+    if item.power == 0 {
+        return Err(DraftRoomError::InvalidPool(format!("item {} must have non-zero power", item.id)));
+    }
+    if item.quantity == 0 {
+        return Err(DraftRoomError::InvalidPool(format!("item {} must have at least one copy", item.id)));
+    }
+    Ok(())
+}
+
+/// Check that an item name is safe to display and export
+///
+/// Names must be 1 to `MAX_ITEM_NAME_LEN` characters, not all whitespace, and
+/// free of control characters such as newlines, which would break CSV rows
+/// and multi-line-unaware UIs.
+pub fn validate_item_name(id: u8, name: &str) -> Result<(), DraftRoomError> {
+    if name.trim().is_empty() || name.chars().count() > MAX_ITEM_NAME_LEN {
+        return Err(DraftRoomError::InvalidPool(format!(
+            "item {} name must be 1 to {} characters",
+            id, MAX_ITEM_NAME_LEN
+        )));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(DraftRoomError::InvalidPool(format!(
+            "item {} name must not contain control characters",
+            id
+        )));
+    }
+    Ok(())
+}
+
+/// Check a whole custom pool, returning every problem found
+///
+/// Runs `validate_pool_item` on each item and also checks pool size and id
+/// uniqueness. An empty result means the pool is acceptable.
+pub fn validate_pool(items: &[DraftItem]) -> Vec<DraftRoomError> {
+    let mut errors = Vec::new();
+
+    if items.is_empty() {
+        errors.push(DraftRoomError::InvalidPool("pool must contain at least one item".to_string()));
+    }
+    if items.len() > MAX_POOL_ITEMS {
+        errors.push(DraftRoomError::InvalidPool(format!(
+            "pool has {} items, at most {} allowed",
+            items.len(),
+            MAX_POOL_ITEMS
+        )));
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if let Err(error) = validate_pool_item(item) {
+            errors.push(error);
+        }
+        if items[..index].iter().any(|earlier| earlier.id == item.id) {
+            errors.push(DraftRoomError::InvalidPool(format!("item id {} is used more than once", item.id)));
+        }
+    }
+
+    errors
+}
+
+/// Check that a draft of `max_players * max_rounds` picks can be run from `pool`
+///
+/// The draft must stay under `MAX_TOTAL_PICKS` and the pool must hold at least
+/// one copy per pick plus one per ban, since the ban phase takes
+/// `ban_rounds * max_players` items before picking starts. Shared by
+/// `CreateRoom` and `StartDraft`, so a room is judged the same way when it is
+/// opened and when its draft begins.
+pub fn validate_draft_config(
+    max_players: u8,
+    max_rounds: u8,
+    ban_rounds: u8,
+    pool: &[DraftItem],
+) -> Result<(), DraftRoomError> {
+    let picks = u32::from(max_players) * u32::from(max_rounds);
+    if picks > MAX_TOTAL_PICKS {
+        return Err(DraftRoomError::DraftTooLarge(picks));
+    }
+
+    let bans = u32::from(max_players) * u32::from(ban_rounds);
+    let items: u32 = pool.iter().map(|item| u32::from(item.quantity)).sum();
+    if items < picks + bans {
+        return Err(DraftRoomError::PoolTooSmall { picks, bans, items });
+    }
+    Ok(())
+}
+
+/// Check a room's captains: none, or two different owners
+///
+/// Shared by `CreateRoom` and share codes; `StartDraft` also checks both
+/// captains have joined.
+pub fn validate_captains(captains: &[Owner]) -> Result<(), DraftRoomError> {
+    match captains {
+        [] => Ok(()),
+        [first, second] if first != second => Ok(()),
+        [_, _] => Err(DraftRoomError::InvalidCaptains("the two captains must be different players".to_string())),
+        _ => Err(DraftRoomError::InvalidCaptains(format!("expected 2 captains, got {}", captains.len()))),
+    }
+}
+
+/// Index into the turn order of the player picking at `current_turn` of `round`
+///
+/// Snake draft: odd rounds go forward, even rounds go backward. Shared with
+/// the service so clients see exactly the order the contract enforces.
+/// `player_count` must be non-zero.
+pub fn snake_player_index(round: u8, current_turn: u8, player_count: u8) -> u8 {
+    if round % 2 == 1 {
+        // Forward direction
+        current_turn % player_count
+    } else {
+        // Backward direction
+        (player_count - 1) - (current_turn % player_count)
+    }
+}
+
+/// Choose the item an automatic pick takes from the pool
+///
+/// Highest power wins; equal power is broken by the lowest id, so the choice
+/// never depends on pool order and replays identically on every validator.
+pub fn highest_power_item(pool: &[DraftItem]) -> Option<&DraftItem> {
+    pool.iter().min_by_key(|item| (Reverse(item.power), item.id))
+}
+
+/// Draw `size` distinct items from `candidates`, weighted by `weight`
+///
+/// Deterministic for a given seed, so anyone holding the room's recorded seed
+/// can reproduce the pool. Items with zero weight are never drawn. The result
+/// keeps the candidates' order.
+pub fn sample_pool(candidates: &[DraftItem], size: usize, seed: u64) -> Vec<DraftItem> {
+    let mut remaining: Vec<usize> = (0..candidates.len()).filter(|&index| candidates[index].weight > 0).collect();
+    let mut drawn = Vec::new();
+    let mut state = seed;
+
+    while drawn.len() < size && !remaining.is_empty() {
+        let total: u64 = remaining.iter().map(|&index| u64::from(candidates[index].weight)).sum();
+        let mut target = splitmix64(&mut state) % total;
+        let position = remaining
+            .iter()
+            .position(|&index| {
+                let weight = u64::from(candidates[index].weight);
+                if target < weight {
+                    true
+                } else {
+                    target -= weight;
+                    false
+                }
+            })
+            .expect("Target is below the total weight");
+        drawn.push(remaining.remove(position));
+    }
+
+    drawn.sort_unstable();
+    drawn.into_iter().map(|index| candidates[index].clone()).collect()
+}
+
+/// Seed for shuffling the seat order at `StartDraft`
+///
+/// The first eight bytes of SHA-256 over the room's chain id followed by the
+/// block time in big-endian microseconds. Every validator computes the same
+/// seed, and anyone can recompute it from the block that started the draft
+/// to check the order.
+pub fn seat_order_seed(chain_id: &ChainId, time: Timestamp) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(chain_id.to_string().as_bytes());
+    hasher.update(time.micros().to_be_bytes());
+    let digest = hasher.finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digests are 32 bytes"))
+}
+
+/// Fisher-Yates shuffle of `players` driven by SplitMix64 from `seed`
+///
+/// The same players and seed always give the same order.
+pub fn shuffle_players(players: &[Owner], seed: u64) -> Vec<Owner> {
+    let mut shuffled = players.to_vec();
+    let mut state = seed;
+    for index in (1..shuffled.len()).rev() {
+        let swap = (splitmix64(&mut state) % (index as u64 + 1)) as usize;
+        shuffled.swap(index, swap);
+    }
+    shuffled
+}
+
+/// Commitment to a pool seed: the lowercase hex SHA-256 of its big-endian bytes
+///
+/// A creator publishes this at `CreateRoom` and reveals the seed before the
+/// draft starts, so nobody can pick a seed after seeing who joined.
+pub fn seed_commitment(seed: u64) -> String {
+    Sha256::digest(seed.to_be_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hex SHA-256 of a room password, as kept in `RoomOptions::password_hash`
+pub fn password_hash(password: &str) -> String {
+    Sha256::digest(password.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Next value of the SplitMix64 generator
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Reject a final-round pick below the room's power threshold
+///
+/// The rule only applies while some item in `pool` still meets the threshold.
+/// Shared with the service so pick validation matches the contract.
+pub fn check_power_threshold(
+    pool: &[DraftItem],
+    item: &DraftItem,
+    round: u8,
+    max_rounds: u8,
+    threshold: Option<u32>,
+) -> Result<(), DraftRoomError> {
+    let Some(threshold) = threshold else {
+        return Ok(());
+    };
+
+    if round == max_rounds && item.power < threshold && pool.iter().any(|candidate| candidate.power >= threshold) {
+        return Err(DraftRoomError::BelowPowerThreshold(threshold));
+    }
+
+    Ok(())
+}
+
+/// The item a handicapped first pick may not take: the pool's highest-power item
+///
+/// Ties go to the lowest item ID, as in `highest_power_item`, so exactly one card is restricted.
+pub fn handicapped_item(pool: &[DraftItem]) -> Option<u8> {
+    highest_power_item(pool).map(|item| item.id)
+}
+
+/// Enforce the first-pick handicap, if the room has it
+///
+/// Only the first pick of the draft (round 1, turn 0) is restricted. Shared
+/// with the service so pick validation matches the contract.
+pub fn check_first_pick_handicap(
+    pool: &[DraftItem],
+    item: &DraftItem,
+    round: u8,
+    current_turn: u8,
+    enabled: bool,
+) -> Result<(), DraftRoomError> {
+    if enabled && round == 1 && current_turn == 0 && handicapped_item(pool) == Some(item.id) {
+        return Err(DraftRoomError::HandicapRestricted);
+    }
+    Ok(())
+}
+
+/// Budget left after paying `cost`, or `None` when the room has no salary cap
+pub fn budget_after_pick(remaining: Option<u32>, cost: u32) -> Result<Option<u32>, DraftRoomError> {
+    match remaining {
+        Some(remaining) if cost > remaining => Err(DraftRoomError::OverBudget { cost, remaining }),
+        Some(remaining) => Ok(Some(remaining - cost)),
+        None => Ok(None),
+    }
+}
+
+/// A player's final score, raw and weighted by the round each pick was made in
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Standing {
+    pub player: Owner,
+    pub raw_power: u64,
+    pub weighted_power: u64,
+}
+
+/// Rank players by round-weighted power, highest first
+///
+/// `round_weights[0]` applies to round 1; rounds without a weight count once,
+/// so empty weights rank by raw power. Ties keep turn order.
+pub fn compute_standings(players: &[Owner], pick_history: &[PickRecord], round_weights: &[u32]) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            player: *player,
+            raw_power: 0,
+            weighted_power: 0,
+        })
+        .collect();
+
+    for record in pick_history {
+        let weight = record
+            .round
+            .checked_sub(1)
+            .and_then(|index| round_weights.get(index as usize))
+            .copied()
+            .unwrap_or(1);
+
+        if let Some(standing) = standings.iter_mut().find(|standing| standing.player == record.player) {
+            standing.raw_power += u64::from(record.item.power);
+            standing.weighted_power += u64::from(record.item.power) * u64::from(weight);
+        }
+    }
+
+    standings.sort_by_key(|standing| Reverse(standing.weighted_power));
+    standings
+}
+
+/// The player with the highest total power, ties going to the lowest `Owner`
+pub fn draft_winner(totals: &[(Owner, u64)]) -> Option<Owner> {
+    totals
+        .iter()
+        .max_by(|(a, a_total), (b, b_total)| a_total.cmp(b_total).then(b.cmp(a)))
+        .map(|(player, _)| *player)
+}
+
+/// A turn's clock: when it began and when it runs out
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TurnClock {
+    pub started_at: Option<Timestamp>,
+    pub deadline: Option<Timestamp>,
+}
+
+/// A single pick, recorded in draft order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PickRecord {
+    pub player: Owner,
+    pub item: DraftItem,
+    pub round: u8,
+}
+
+/// The plain DraftRoom fields, persisted together between blocks
+///
+/// The layout is not versioned or migrated: a room stored with a different
+/// shape fails to decode when its storage loads. New persisted state belongs
+/// in its own view on `DraftRoomStorage`, which older rooms load as empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftRoomCheckpoint {
+    pub players: Vec<Owner>,
+    pub max_players: u8,
+    pub current_turn: u8,
+    pub round: u8,
+    pub max_rounds: u8,
+    pub pool: Vec<DraftItem>,
+    pub staged_pool: Vec<DraftItem>,
+    pub pick_history: Vec<PickRecord>,
+    pub status: DraftStatus,
+    pub creator: Option<Owner>,
+    pub turn_order: Option<Vec<Owner>>,
+    pub reserved: Vec<Owner>,
+    pub standings: Vec<Standing>,
+    pub winner: Option<Owner>,
+    pub spectators: Vec<Owner>,
+    pub turn_deadline: Option<Timestamp>,
+    pub turn_started_at: Option<Timestamp>,
+    pub time_requests_used: BTreeMap<Owner, u8>,
+    pub bans: Vec<PickRecord>,
+    pub pool_seed: Option<u64>,
+    pub seed_commitment: Option<String>,
+    pub last_picker: Option<Owner>,
+    pub conceded_by: Option<Owner>,
+    pub version: u64,
+}
+
+/// On-chain storage for a DraftRoom
+#[derive(RootView)]
+#[view(context = "ViewStorageContext")]
+pub struct DraftRoomStorage {
+    /// Latest checkpoint; `None` until the room is first stored
+    pub checkpoint: RegisterView<Option<DraftRoomCheckpoint>>,
+    pub picks: MapView<Owner, Vec<DraftItem>>,
+    /// Each player's preferred auto-pick order, as item ids
+    pub wishlists: MapView<Owner, Vec<u8>>,
+    /// Each player's remaining budget in salary-cap rooms, set by `StartDraft`
+    pub budgets: MapView<Owner, u32>,
+    /// Advisory item ids suggested to each player by others in the room, oldest first
+    pub suggestions: MapView<Owner, Vec<u8>>,
+    /// Players who have confirmed with `SetReady` that they are present; `StartDraft` needs all of them
+    pub ready: MapView<Owner, bool>,
+    /// Players each captain drafted into their squad, in pick order, in captains rooms
+    pub squads: MapView<Owner, Vec<Owner>>,
+    /// Turn clock in effect when the latest pick was made, put back by `UndoPick`
+    pub undo_clock: RegisterView<TurnClock>,
+}
+
+/// The DraftRoom application state
+pub struct DraftRoom {
+    pub players: Vec<Owner>,
+    pub max_players: u8,
+    pub current_turn: u8,
+    pub round: u8,
+    pub max_rounds: u8,
+    pub pool: Vec<DraftItem>,
+    /// Custom pool from `CreateRoom` or prepared by the creator while waiting; used by `StartDraft` if non-empty
+    pub staged_pool: Vec<DraftItem>,
+    pub storage: DraftRoomStorage,
+    pub pick_history: Vec<PickRecord>,
+    pub status: DraftStatus,
+    pub creator: Option<Owner>,
+    /// Explicit pick order chosen by the creator; applied to `players` by `StartDraft`
+    pub turn_order: Option<Vec<Owner>>,
+    /// Players seated by the creator via `InviteAndReserve` rather than `JoinRoom`
+    pub reserved: Vec<Owner>,
+    /// When set, other players' picks are only revealed once their round is complete
+    pub reveal_after_round: bool,
+    /// Minimum power for final-round picks, if the room enforces one
+    pub final_round_min_power: Option<u32>,
+    /// Score multiplier per round used for the final standings
+    pub round_weights: Vec<u32>,
+    /// Seconds each player has per pick, if the room has a turn clock
+    pub turn_duration_secs: Option<u64>,
+    /// Starting budget per player, if the room is in salary-cap mode
+    pub budget: Option<u32>,
+    /// Owners watching the room without a player seat
+    pub spectators: Vec<Owner>,
+    /// Most spectators the room accepts
+    pub max_spectators: u8,
+    /// Rehearsal mode: picks are validated and advance the turn without being kept
+    pub preview: bool,
+    /// When the current turn's clock runs out, if the room has a turn duration
+    pub turn_deadline: Option<Timestamp>,
+    /// When the current turn began; `None` unless drafting
+    pub turn_started_at: Option<Timestamp>,
+    /// How many `RequestMoreTime` extensions each player has used
+    pub time_requests_used: BTreeMap<Owner, u8>,
+    /// Rounds of bans before picking starts
+    pub ban_rounds: u8,
+    /// Items removed during the ban phase, with who banned them and in which ban round
+    pub bans: Vec<PickRecord>,
+    /// Number of items `StartDraft` samples from the candidate pool, if the room samples
+    pub pool_size: Option<u8>,
+    /// Seed used to sample the pool; recorded at `StartDraft` when not configured
+    pub pool_seed: Option<u64>,
+    /// Published hash of the pool seed; the seed must be revealed to match it before `StartDraft`
+    pub seed_commitment: Option<String>,
+    /// The Lobby chain that opened this room, told about capacity changes
+    pub lobby: Option<ChainId>,
+    /// When set, the first pick of the draft cannot take the single highest-power item
+    pub handicap_first_pick: bool,
+    /// Hex SHA-256 of the password `JoinRoom` must present; anyone may join when unset
+    pub password_hash: Option<String>,
+    /// Seconds into a turn before `PickItem` is accepted, if the room throttles picks
+    pub min_pick_interval_secs: Option<u64>,
+    /// The two captains who draft squads before the item draft; empty for a plain draft
+    pub captains: Vec<Owner>,
+    /// Seconds after finalizing before the Lobby may delist the room, if it auto-closes
+    pub auto_close_after_secs: Option<u64>,
+    /// Final standings, computed by `FinalizeDraft`
+    pub standings: Vec<Standing>,
+    /// Highest total picked power, recorded by `FinalizeDraft`
+    pub winner: Option<Owner>,
+    /// Who made the most recent `PickItem`, while it can still be undone
+    pub last_picker: Option<Owner>,
+    /// The player who ended the draft early with `Concede`, handing the win to their opponent
+    pub conceded_by: Option<Owner>,
+    /// Incremented after every accepted operation, so clients can tell which state they are seeing
+    pub version: u64,
+    runtime: ContractRuntime<LiveDraftArena>,
+}
+
+/// Operations for the DraftRoom
+#[derive(Debug, Deserialize, Serialize)]
+pub enum DraftRoomOperation {
+    JoinRoom { password: Option<String> },
+    LeaveRoom,
+    KickPlayer { player: Owner },
+    /// `shuffle` randomizes the seat order unless the creator set one with `SetTurnOrder`
+    StartDraft { shuffle: bool },
+    PickItem { item_id: u8 },
+    UndoPick,
+    FinalizeDraft,
+    UpdatePoolItem {
+        id: u8,
+        name: Option<String>,
+        power: Option<u32>,
+    },
+    AddPoolItem { item: DraftItem },
+    RemovePoolItem { id: u8 },
+    SetTurnOrder { order: Vec<Owner> },
+    SetWishlist { item_ids: Vec<u8> },
+    ForceAutoPick,
+    ForcePick,
+    InviteAndReserve { owners: Vec<Owner> },
+    Spectate,
+    RequestMoreTime,
+    BanItem { item_id: u8 },
+    SetMaxPlayers { max_players: u8 },
+    RevealSeed { seed: u64 },
+    SuggestToPlayer { player: Owner, item_id: u8 },
+    SetReady { ready: bool },
+    DraftCaptainPick { player: Owner },
+    Concede,
+}
+
+/// Messages for DraftRoom
+#[derive(Debug, Deserialize, Serialize)]
+pub enum DraftRoomMessage {
+    // No cross-chain messages needed yet
+}
+
+/// Errors for DraftRoom operations
+#[derive(Debug, Error)]
+pub enum DraftRoomError {
+    #[error("Room is not in waiting status")]
+    NotWaiting,
+    #[error("Room is full")]
+    RoomFull,
+    #[error("Player already joined")]
+    AlreadyJoined,
+    #[error("Only creator can start draft")]
+    NotCreator,
+    #[error("Room is not in drafting status")]
+    NotDrafting,
+    #[error("Not your turn")]
+    NotYourTurn,
+    #[error("Item not found in pool")]
+    ItemNotFound,
+    #[error("Authentication required")]
+    AuthenticationRequired,
+    #[error("Draft not finished")]
+    DraftNotFinished,
+    #[error("State invariant violated: {0}")]
+    InvariantViolation(String),
+    #[error("Invalid pool: {0}")]
+    InvalidPool(String),
+    #[error("Invalid turn order: {0}")]
+    InvalidTurnOrder(String),
+    #[error("Draft of {0} picks exceeds the limit of {MAX_TOTAL_PICKS}")]
+    DraftTooLarge(u32),
+    #[error("Draft of {picks} picks and {bans} bans needs at least {} items but the pool has {items}", .picks + .bans)]
+    PoolTooSmall { picks: u32, bans: u32, items: u32 },
+    #[error("Player has not joined this room")]
+    NotAPlayer,
+    #[error("Final round picks need at least {0} power while such an item remains")]
+    BelowPowerThreshold(u32),
+    #[error("Item costs {cost} but only {remaining} budget remains")]
+    OverBudget { cost: u32, remaining: u32 },
+    #[error("Room already has the maximum of {0} spectators")]
+    SpectatorLimitReached(u8),
+    #[error("Room has no turn clock")]
+    NoTurnClock,
+    #[error("All {MAX_TIME_REQUESTS} time extensions already used")]
+    NoTimeRequestsLeft,
+    #[error("Room is not in banning status")]
+    NotBanning,
+    #[error("Capacity must be between {MIN_PLAYERS_TO_START} and {MAX_ROOM_CAPACITY} and fit the {0} seated players")]
+    InvalidCapacity(u8),
+    #[error("Room has no seed commitment")]
+    NoSeedCommitment,
+    #[error("Seed does not match the room's commitment")]
+    SeedMismatch,
+    #[error("Pool seed must be revealed before the draft starts")]
+    SeedNotRevealed,
+    #[error("Signer is not in this room")]
+    NotInRoom,
+    #[error("The current turn has not expired yet")]
+    TurnNotExpired,
+    #[error("The first pick cannot take the highest-power item")]
+    HandicapRestricted,
+    #[error("Spectators must leave before joining, starting or picking")]
+    Spectating,
+    #[error("The creator cannot be kicked")]
+    CannotKickCreator,
+    #[error("Wrong room password")]
+    WrongPassword,
+    #[error("No pick of yours to undo")]
+    NothingToUndo,
+    #[error("Picks must wait until the turn has run its minimum interval")]
+    PickingTooFast,
+    #[error("At least {MIN_PLAYERS_TO_START} players must join before the draft starts")]
+    NotEnoughPlayers,
+    #[error("The creator must join the room before starting the draft")]
+    CreatorNotJoined,
+    #[error("Every player must be ready before the draft starts")]
+    PlayersNotReady,
+    #[error("Invalid captains: {0}")]
+    InvalidCaptains(String),
+    #[error("Room is not in captain picking status")]
+    NotCaptainPicking,
+    #[error("Player is a captain or already in a squad")]
+    AlreadyInSquad,
+    #[error("Only a two-player draft can be conceded")]
+    ConcedeNeedsTwoPlayers,
+}
+
+impl DraftRoom {
+    pub(crate) async fn load(runtime: ContractRuntime<LiveDraftArena>) -> Self {
+        let (max_players, options, creator, lobby) = match runtime.application_parameters() {
+            ContractParameters::DraftRoom { max_players, options, creator, lobby } => {
+                (max_players, options, Some(creator), lobby)
+            }
+            ContractParameters::Lobby => (0, Default::default(), None, None),
+        };
+
+        let storage = DraftRoomStorage::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load draft room state");
+
+        let mut room = DraftRoom {
+            players: Vec::new(),
+            max_players,
+            current_turn: 0,
+            round: 1,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            pool: Vec::new(),
+            staged_pool: options.custom_pool,
+            storage,
+            pick_history: Vec::new(),
+            status: DraftStatus::Waiting,
+            creator,
+            turn_order: None,
+            reserved: Vec::new(),
+            reveal_after_round: options.reveal_after_round,
+            final_round_min_power: options.final_round_min_power,
+            round_weights: options.round_weights,
+            turn_duration_secs: options.turn_duration_secs,
+            budget: options.budget,
+            spectators: Vec::new(),
+            max_spectators: options.max_spectators,
+            preview: options.preview,
+            turn_deadline: None,
+            turn_started_at: None,
+            time_requests_used: BTreeMap::new(),
+            ban_rounds: options.ban_rounds,
+            bans: Vec::new(),
+            pool_size: options.pool_size,
+            pool_seed: options.pool_seed,
+            seed_commitment: options.seed_commitment,
+            lobby,
+            handicap_first_pick: options.handicap_first_pick,
+            password_hash: options.password_hash,
+            min_pick_interval_secs: options.min_pick_interval_secs,
+            captains: options.captains,
+            auto_close_after_secs: options.auto_close_after_secs,
+            standings: Vec::new(),
+            winner: None,
+            last_picker: None,
+            conceded_by: None,
+            version: 0,
+            runtime,
+        };
+
+        // Resume from the last stored checkpoint, if any
+        if let Some(checkpoint) = room.storage.checkpoint.get().clone() {
+            room.players = checkpoint.players;
+            room.max_players = checkpoint.max_players;
+            room.current_turn = checkpoint.current_turn;
+            room.round = checkpoint.round;
+            room.max_rounds = checkpoint.max_rounds;
+            room.pool = checkpoint.pool;
+            room.staged_pool = checkpoint.staged_pool;
+            room.pick_history = checkpoint.pick_history;
+            room.status = checkpoint.status;
+            room.creator = checkpoint.creator;
+            room.turn_order = checkpoint.turn_order;
+            room.reserved = checkpoint.reserved;
+            room.standings = checkpoint.standings;
+            room.winner = checkpoint.winner;
+            room.spectators = checkpoint.spectators;
+            room.turn_deadline = checkpoint.turn_deadline;
+            room.turn_started_at = checkpoint.turn_started_at;
+            room.time_requests_used = checkpoint.time_requests_used;
+            room.bans = checkpoint.bans;
+            room.pool_seed = checkpoint.pool_seed;
+            room.seed_commitment = checkpoint.seed_commitment;
+            room.last_picker = checkpoint.last_picker;
+            room.conceded_by = checkpoint.conceded_by;
+            room.version = checkpoint.version;
+        }
+
+        room
+    }
+
+    /// Persist the room so the next block resumes from exactly this state
+    pub(crate) async fn store(mut self) {
+        self.storage.checkpoint.set(Some(DraftRoomCheckpoint {
+            players: self.players,
+            max_players: self.max_players,
+            current_turn: self.current_turn,
+            round: self.round,
+            max_rounds: self.max_rounds,
+            pool: self.pool,
+            staged_pool: self.staged_pool,
+            pick_history: self.pick_history,
+            status: self.status,
+            creator: self.creator,
+            turn_order: self.turn_order,
+            reserved: self.reserved,
+            standings: self.standings,
+            winner: self.winner,
+            spectators: self.spectators,
+            turn_deadline: self.turn_deadline,
+            turn_started_at: self.turn_started_at,
+            time_requests_used: self.time_requests_used,
+            bans: self.bans,
+            pool_seed: self.pool_seed,
+            seed_commitment: self.seed_commitment,
+            last_picker: self.last_picker,
+            conceded_by: self.conceded_by,
+            version: self.version,
+        }));
+        self.storage.save().await.expect("Failed to save draft room state");
+    }
+
+    /// Initialize hardcoded Wave-5 pool
+    pub fn initialize_pool() -> Vec<DraftItem> {
+        vec![
+            DraftItem::new(1, "Lightning Bolt", 100),
+            DraftItem::new(2, "Counterspell", 90),
+            DraftItem::new(3, "Giant Growth", 80),
+            DraftItem::new(4, "Dark Ritual", 85),
+            DraftItem::new(5, "Healing Salve", 70),
+            DraftItem::new(6, "Ancestral Recall", 95),
+            DraftItem::new(7, "Black Lotus", 100),
+            DraftItem::new(8, "Mox Pearl", 90),
+            DraftItem::new(9, "Time Walk", 95),
+            DraftItem::new(10, "Swords to Plowshares", 85),
+            DraftItem::new(11, "Force of Will", 90),
+            DraftItem::new(12, "Brainstorm", 75),
+            DraftItem::new(13, "Sol Ring", 85),
+            DraftItem::new(14, "Path to Exile", 80),
+            DraftItem::new(15, "Demonic Tutor", 90),
+        ]
+    }
+
+    /// Get current player based on turn and round
+    fn get_current_player(&self) -> Option<&Owner> {
+        if self.players.is_empty() {
+            return None;
+        }
+
+        let player_index = snake_player_index(self.round, self.current_turn, self.players.len() as u8);
+        self.players.get(player_index as usize)
+    }
+
+    /// Advance to next turn/round
+    fn advance_turn(&mut self) {
+        let player_count = self.players.len() as u8;
+
+        self.current_turn += 1;
+
+        // Check if we completed a round
+        if self.current_turn >= player_count {
+            self.current_turn = 0;
+            self.round += 1;
+
+            // Check if draft is complete
+            if self.round > self.max_rounds {
+                self.status = DraftStatus::Finished;
+            }
+        }
+
+        self.start_turn_clock();
+    }
+
+    /// Step back to the previous turn, undoing `advance_turn`
+    ///
+    /// Snake direction follows from the round, so rewinding past a round
+    /// boundary lands on the last seat of the round before. The turn gets back
+    /// the clock it had, so undoing never buys the picker more time.
+    fn rewind_turn(&mut self, clock: TurnClock) {
+        if self.current_turn == 0 {
+            self.round -= 1;
+            self.current_turn = self.players.len() as u8 - 1;
+        } else {
+            self.current_turn -= 1;
+        }
+
+        self.turn_started_at = clock.started_at;
+        self.turn_deadline = clock.deadline;
+    }
+
+    /// Answer the Lobby's status requests; other messages are not meant for rooms
+    pub(crate) fn execute_message(&mut self, message: Message) {
+        if let Message::RoomStatusRequest = message {
+            let Some(lobby) = self.runtime.message_id().map(|id| id.chain_id) else {
+                return;
+            };
+            self.runtime
+                .prepare_message(self.status_report())
+                .send_to(lobby);
+        }
+    }
+
+    /// The room's current status as reported to the Lobby
+    pub(crate) fn status_report(&self) -> Message {
+        Message::RoomStatusReport {
+            status: self.status.clone(),
+            player_count: self.players.len() as u8,
+        }
+    }
+
+    /// Move into the pick phase at round one
+    fn begin_picking(&mut self) {
+        self.status = DraftStatus::Drafting;
+        self.current_turn = 0;
+        self.round = 1;
+        self.start_turn_clock();
+    }
+
+    /// Leave setup for the ban phase, or straight for picking if the room has no bans
+    fn begin_bans_or_picking(&mut self) {
+        if self.ban_rounds > 0 {
+            self.status = DraftStatus::Banning;
+        } else {
+            self.begin_picking();
+        }
+    }
+
+    /// Each captain's squad so far, in captain order
+    async fn squads(&self) -> Vec<Vec<Owner>> {
+        let mut squads = Vec::with_capacity(self.captains.len());
+        for captain in &self.captains {
+            let squad = self
+                .storage
+                .squads
+                .get(captain)
+                .await
+                .expect("Failed to get captain squad")
+                .unwrap_or_default();
+            squads.push(squad);
+        }
+        squads
+    }
+
+    /// Players neither captaining nor yet drafted into a squad, in seat order
+    async fn undrafted_players(&self) -> Vec<Owner> {
+        let drafted: Vec<Owner> = self.squads().await.into_iter().flatten().collect();
+        self.players
+            .iter()
+            .filter(|player| !self.captains.contains(player) && !drafted.contains(player))
+            .copied()
+            .collect()
+    }
+
+    /// The captain who drafts next: captains alternate, the first listed going first
+    async fn current_captain(&self) -> Option<Owner> {
+        let drafted: usize = self.squads().await.iter().map(Vec::len).sum();
+        self.captains.get(drafted % 2).copied()
+    }
+
+    /// The player who bans next: players take turns in seat order
+    fn current_banner(&self) -> Option<&Owner> {
+        if self.players.is_empty() {
+            return None;
+        }
+        self.players.get(self.bans.len() % self.players.len())
+    }
+
+    /// Record the start of the turn beginning now and set its deadline
+    ///
+    /// Rooms without a turn duration, and finished drafts, have no deadline.
+    fn start_turn_clock(&mut self) {
+        let drafting = self.status == DraftStatus::Drafting;
+        self.turn_started_at = drafting.then(|| self.runtime.system_time());
+        self.turn_deadline = match self.turn_duration_secs {
+            Some(secs) if self.status == DraftStatus::Drafting => {
+                Some(self.runtime.system_time().saturating_add(TimeDelta::from_secs(secs)))
+            }
+            _ => None,
+        };
+    }
+
+    /// Check that the room state is internally consistent
+    ///
+    /// This is a correctness harness: it is exercised by the tests and, with the
+    /// `debug-invariants` feature, after every operation so state bugs fail loudly.
+    pub async fn validate_invariants(&self) -> Result<(), DraftRoomError> {
+        if self.players.len() > self.max_players as usize {
+            return Err(DraftRoomError::InvariantViolation(format!(
+                "{} players exceed capacity of {}",
+                self.players.len(),
+                self.max_players
+            )));
+        }
+
+        for (index, player) in self.players.iter().enumerate() {
+            if self.players[..index].contains(player) {
+                return Err(DraftRoomError::InvariantViolation(format!(
+                    "player {} joined more than once",
+                    player
+                )));
+            }
+
+            let has_picks = self
+                .storage
+                .picks
+                .contains_key(player)
+                .await
+                .expect("Failed to read player picks");
+            if !has_picks {
+                return Err(DraftRoomError::InvariantViolation(format!(
+                    "player {} has no picks entry",
+                    player
+                )));
+            }
+        }
+
+        if self.status == DraftStatus::Drafting && self.current_turn as usize >= self.players.len() {
+            return Err(DraftRoomError::InvariantViolation(format!(
+                "turn {} out of range for {} players",
+                self.current_turn,
+                self.players.len()
+            )));
+        }
+
+        if self.round > self.max_rounds + 1 {
+            return Err(DraftRoomError::InvariantViolation(format!(
+                "round {} past final round {}",
+                self.round, self.max_rounds
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn authenticated_signer(&mut self) -> Result<Owner, DraftRoomError> {
+        self.runtime
+            .authenticated_signer()
+            .ok_or(DraftRoomError::AuthenticationRequired)
+    }
+
+    /// Require the signer to be the room creator and the room to still be waiting
+    fn require_creator_while_waiting(&mut self) -> Result<Owner, DraftRoomError> {
+        let signer = self.authenticated_signer()?;
+
+        if self.creator != Some(signer) {
+            return Err(DraftRoomError::NotCreator);
+        }
+
+        if self.status != DraftStatus::Waiting {
+            return Err(DraftRoomError::NotWaiting);
+        }
+
+        Ok(signer)
+    }
+
+    /// Add a player to the room with an empty picks entry
+    fn seat_player(&mut self, player: Owner) {
+        self.players.push(player);
+
+        // Initialize empty picks for the player
+        self.storage
+            .picks
+            .insert(&player, Vec::new())
+            .expect("Failed to initialize player picks");
+    }
+
+    /// Remove a player from the room along with their picks and wishlist
+    fn unseat_player(&mut self, player: Owner) {
+        self.players.retain(|seated| *seated != player);
+        self.reserved.retain(|reserved| *reserved != player);
+        if let Some(order) = &mut self.turn_order {
+            order.retain(|ordered| *ordered != player);
+        }
+        self.storage.picks.remove(&player).expect("Failed to remove player picks");
+        self.storage.wishlists.remove(&player).expect("Failed to remove player wishlist");
+        self.storage.ready.remove(&player).expect("Failed to remove player ready flag");
+    }
+
+    /// Check that `order` lists every joined player exactly once
+    fn validate_turn_order(&self, order: &[Owner]) -> Result<(), DraftRoomError> {
+        for (index, player) in order.iter().enumerate() {
+            if order[..index].contains(player) {
+                return Err(DraftRoomError::InvalidTurnOrder(format!("player {} is listed more than once", player)));
+            }
+            if !self.players.contains(player) {
+                return Err(DraftRoomError::InvalidTurnOrder(format!("player {} has not joined", player)));
+            }
+        }
+
+        if let Some(missing) = self.players.iter().find(|player| !order.contains(player)) {
+            return Err(DraftRoomError::InvalidTurnOrder(format!("player {} is missing", missing)));
+        }
+
+        Ok(())
+    }
+
+    /// Enforce the final-round power threshold, if configured
+    ///
+    /// A weaker pick is still allowed once no remaining item meets the threshold.
+    fn check_power_threshold(&self, item: &DraftItem) -> Result<(), DraftRoomError> {
+        check_power_threshold(&self.pool, item, self.round, self.max_rounds, self.final_round_min_power)
+    }
+
+    /// Enforce the first-pick handicap, if configured
+    fn check_first_pick_handicap(&self, item: &DraftItem) -> Result<(), DraftRoomError> {
+        check_first_pick_handicap(&self.pool, item, self.round, self.current_turn, self.handicap_first_pick)
+    }
+
+    /// Budget `player` has left, or `None` if the room has no salary cap
+    async fn remaining_budget(&self, player: &Owner) -> Option<u32> {
+        let budget = self.budget?;
+        let remaining = self
+            .storage
+            .budgets
+            .get(player)
+            .await
+            .expect("Failed to get player budget");
+        Some(remaining.unwrap_or(budget))
+    }
+
+    /// Give `player` one copy of `item_id` from the pool and advance the turn
+    ///
+    /// Turn and status checks are the caller's job; this enforces pool rules only.
+    /// In preview rooms the pick is fully validated but only the turn advances.
+    async fn record_pick(&mut self, player: Owner, item_id: u8) -> Result<(), DraftRoomError> {
+        // Take one copy of the item, removing it from the pool at zero
+        let item_index = self
+            .pool
+            .iter()
+            .position(|item| item.id == item_id)
+            .ok_or(DraftRoomError::ItemNotFound)?;
+
+        self.check_power_threshold(&self.pool[item_index])?;
+        self.check_first_pick_handicap(&self.pool[item_index])?;
+
+        // Salary-cap rooms reject picks the player can't afford
+        let budget_after_pick = budget_after_pick(self.remaining_budget(&player).await, self.pool[item_index].cost)?;
+
+        if self.preview {
+            self.last_picker = None;
+            self.advance_turn();
+            return Ok(());
+        }
+
+        if let Some(remaining) = budget_after_pick {
+            self.storage
+                .budgets
+                .insert(&player, remaining)
+                .expect("Failed to update player budget");
+        }
+
+        let pool_item = &mut self.pool[item_index];
+        pool_item.quantity = pool_item.quantity.saturating_sub(1);
+        let picked_item = DraftItem {
+            quantity: 1,
+            ..pool_item.clone()
+        };
+        if pool_item.quantity == 0 {
+            self.pool.remove(item_index);
+        }
+
+        // Add to player's picks
+        let mut player_picks = self
+            .storage
+            .picks
+            .get(&player)
+            .await
+            .expect("Failed to get player picks")
+            .unwrap_or_default();
+
+        player_picks.push(picked_item.clone());
+
+        self.storage
+            .picks
+            .insert(&player, player_picks)
+            .expect("Failed to update player picks");
+
+        // Record the pick with its round so reads can apply reveal rules
+        self.pick_history.push(PickRecord {
+            player,
+            item: picked_item,
+            round: self.round,
+        });
+        self.last_picker = Some(player);
+        self.storage.undo_clock.set(TurnClock {
+            started_at: self.turn_started_at,
+            deadline: self.turn_deadline,
+        });
+
+        // Advance turn
+        self.advance_turn();
+
+        Ok(())
+    }
+
+    /// Pool items `player` may take now: affordable and not barred by the first-pick handicap
+    async fn pickable_items(&self, player: &Owner) -> Vec<DraftItem> {
+        let remaining = self.remaining_budget(player).await.unwrap_or(u32::MAX);
+        self.pool
+            .iter()
+            .filter(|item| item.cost <= remaining && self.check_first_pick_handicap(item).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Choose the item an automatic pick takes for `player`
+    ///
+    /// The player's wishlist is tried in order first, skipping items that are
+    /// gone or fail the power threshold; otherwise the highest-power item.
+    async fn auto_pick_item(&self, player: &Owner) -> Result<u8, DraftRoomError> {
+        let wishlist = self
+            .storage
+            .wishlists
+            .get(player)
+            .await
+            .expect("Failed to get player wishlist")
+            .unwrap_or_default();
+
+        // Only consider items the player can still take
+        let affordable = self.pickable_items(player).await;
+
+        let wished = wishlist.iter().find_map(|id| {
+            affordable
+                .iter()
+                .find(|item| item.id == *id)
+                .filter(|item| self.check_power_threshold(item).is_ok())
+        });
+
+        wished
+            .or_else(|| highest_power_item(&affordable))
+            .map(|item| item.id)
+            .ok_or(DraftRoomError::ItemNotFound)
+    }
+
+    pub(crate) async fn execute_operation(
+        &mut self,
+        operation: DraftRoomOperation,
+    ) -> Result<(), DraftRoomError> {
+        let player_count = self.players.len();
+        let listed_status = RoomStatus::from(self.status.clone());
+        self.apply_operation(operation).await?;
+
+        #[cfg(feature = "debug-invariants")]
+        self.validate_invariants().await?;
+
+        if self.players.len() != player_count {
+            self.notify_player_count();
+        }
+        // Ban-to-pick moves don't change what the Lobby lists, so they aren't announced
+        if RoomStatus::from(self.status.clone()) != listed_status {
+            self.notify_status();
+        }
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Record standings and the winner of a finished draft
+    ///
+    /// After a concession the opponent wins regardless of power and is ranked
+    /// first; otherwise the highest total picked power wins.
+    async fn finalize(&mut self) {
+        let first_finalize = self.standings.is_empty();
+
+        self.standings = compute_standings(&self.players, &self.pick_history, &self.round_weights);
+
+        if let Some(conceder) = self.conceded_by {
+            self.winner = self.players.iter().find(|player| **player != conceder).copied();
+            if let Some(index) = self.standings.iter().position(|standing| Some(standing.player) == self.winner) {
+                let standing = self.standings.remove(index);
+                self.standings.insert(0, standing);
+            }
+        } else {
+            let mut totals = Vec::with_capacity(self.players.len());
+            for player in &self.players {
+                let picks = self
+                    .storage
+                    .picks
+                    .get(player)
+                    .await
+                    .expect("Failed to get player picks")
+                    .unwrap_or_default();
+                totals.push((*player, picks.iter().map(|item| u64::from(item.power)).sum()));
+            }
+            self.winner = draft_winner(&totals);
+        }
+
+        // Count picks toward the Lobby's item popularity once per room
+        if first_finalize {
+            self.notify_draft_finalized();
+        }
+    }
+
+    /// Tell the Lobby the room's status changed, so its listing stays current
+    fn notify_status(&mut self) {
+        if let Some(lobby) = self.lobby {
+            let status = self.status.clone();
+            self.runtime
+                .prepare_message(Message::StatusChanged { status })
+                .send_to(lobby);
+        }
+    }
+
+    /// Tell the Lobby which items this room's draft picked, for its popularity counts
+    fn notify_draft_finalized(&mut self) {
+        if let Some(lobby) = self.lobby {
+            let item_names = self.pick_history.iter().map(|record| record.item.name.clone()).collect();
+            let winner = self.winner;
+            self.runtime
+                .prepare_message(Message::DraftFinalized { item_names, winner })
+                .send_to(lobby);
+        }
+    }
+
+    /// Tell the Lobby how many players are now seated, so its listing stays current
+    fn notify_player_count(&mut self) {
+        if let Some(lobby) = self.lobby {
+            let count = self.players.len() as u8;
+            self.runtime
+                .prepare_message(Message::PlayerCountChanged { count })
+                .send_to(lobby);
+        }
+    }
+
+    async fn apply_operation(&mut self, operation: DraftRoomOperation) -> Result<(), DraftRoomError> {
+        match operation {
+            DraftRoomOperation::JoinRoom { password } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Waiting {
+                    return Err(DraftRoomError::NotWaiting);
+                }
+
+                if self.players.len() >= self.max_players as usize {
+                    return Err(DraftRoomError::RoomFull);
+                }
+
+                if self.players.contains(&signer) {
+                    return Err(DraftRoomError::AlreadyJoined);
+                }
+
+                if self.spectators.contains(&signer) {
+                    return Err(DraftRoomError::Spectating);
+                }
+
+                if let Some(expected) = &self.password_hash {
+                    if password.as_deref().map(password_hash).as_ref() != Some(expected) {
+                        return Err(DraftRoomError::WrongPassword);
+                    }
+                }
+
+                self.seat_player(signer);
+                Ok(())
+            }
+
+            DraftRoomOperation::LeaveRoom => {
+                let signer = self.authenticated_signer()?;
+
+                // Spectators may stop watching at any point in the draft
+                if let Some(index) = self.spectators.iter().position(|spectator| *spectator == signer) {
+                    self.spectators.remove(index);
+                    return Ok(());
+                }
+
+                if self.status != DraftStatus::Waiting {
+                    return Err(DraftRoomError::NotWaiting);
+                }
+
+                if !self.players.contains(&signer) {
+                    return Err(DraftRoomError::NotInRoom);
+                }
+                self.unseat_player(signer);
+
+                // The next player to have joined takes over the room
+                if self.creator == Some(signer) {
+                    self.creator = self.players.first().copied();
+                }
+                Ok(())
+            }
+
+            DraftRoomOperation::KickPlayer { player } => {
+                let signer = self.require_creator_while_waiting()?;
+
+                if player == signer {
+                    return Err(DraftRoomError::CannotKickCreator);
+                }
+
+                if !self.players.contains(&player) {
+                    return Err(DraftRoomError::NotAPlayer);
+                }
+
+                self.unseat_player(player);
+                Ok(())
+            }
+
+            DraftRoomOperation::StartDraft { shuffle } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.creator != Some(signer) {
+                    return Err(DraftRoomError::NotCreator);
+                }
+
+                if self.spectators.contains(&signer) {
+                    return Err(DraftRoomError::Spectating);
+                }
+
+                if self.status != DraftStatus::Waiting {
+                    return Err(DraftRoomError::NotWaiting);
+                }
+
+                if self.players.len() < MIN_PLAYERS_TO_START as usize {
+                    return Err(DraftRoomError::NotEnoughPlayers);
+                }
+                if !self.players.contains(&signer) {
+                    return Err(DraftRoomError::CreatorNotJoined);
+                }
+                if let Some(captain) = self.captains.iter().find(|captain| !self.players.contains(captain)) {
+                    return Err(DraftRoomError::InvalidCaptains(format!("captain {} has not joined", captain)));
+                }
+                for player in &self.players {
+                    let ready = self.storage.ready.get(player).await.expect("Failed to get player ready flag");
+                    if ready != Some(true) {
+                        return Err(DraftRoomError::PlayersNotReady);
+                    }
+                }
+
+                // Players may have joined since the order was set, so check it again
+                let ordered = self.turn_order.is_some();
+                if let Some(order) = self.turn_order.take() {
+                    self.validate_turn_order(&order)?;
+                    self.players = order;
+                }
+
+                if self.pool_size == Some(0) {
+                    return Err(DraftRoomError::InvalidPool("pool size must be at least 1".to_string()));
+                }
+                if self.seed_commitment.is_some() && self.pool_seed.is_none() {
+                    return Err(DraftRoomError::SeedNotRevealed);
+                }
+
+                let candidates = if self.staged_pool.is_empty() {
+                    Self::initialize_pool()
+                } else {
+                    self.staged_pool.clone()
+                };
+                let pool = match self.pool_size {
+                    Some(size) => {
+                        let seed = *self.pool_seed.get_or_insert_with(|| self.runtime.system_time().micros());
+                        sample_pool(&candidates, usize::from(size), seed)
+                    }
+                    None => candidates,
+                };
+                validate_draft_config(self.max_players, self.max_rounds, self.ban_rounds, &pool)?;
+                self.pool = pool;
+
+                // A creator-set order is kept as is; otherwise join order no longer decides who picks first
+                if shuffle && !ordered {
+                    let seed = seat_order_seed(&self.runtime.chain_id(), self.runtime.system_time());
+                    self.players = shuffle_players(&self.players, seed);
+                }
+
+                if let Some(budget) = self.budget {
+                    for player in &self.players {
+                        self.storage
+                            .budgets
+                            .insert(player, budget)
+                            .expect("Failed to set player budget");
+                    }
+                }
+
+                // Captains draft the other players into squads before any bans or picks
+                if !self.captains.is_empty() && !self.undrafted_players().await.is_empty() {
+                    self.status = DraftStatus::CaptainPicking;
+                } else {
+                    self.begin_bans_or_picking();
+                }
+
+                Ok(())
+            }
+
+            DraftRoomOperation::DraftCaptainPick { player } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::CaptainPicking {
+                    return Err(DraftRoomError::NotCaptainPicking);
+                }
+                if self.current_captain().await != Some(signer) {
+                    return Err(DraftRoomError::NotYourTurn);
+                }
+                if !self.players.contains(&player) {
+                    return Err(DraftRoomError::NotAPlayer);
+                }
+                if !self.undrafted_players().await.contains(&player) {
+                    return Err(DraftRoomError::AlreadyInSquad);
+                }
+
+                let mut squad = self
+                    .storage
+                    .squads
+                    .get(&signer)
+                    .await
+                    .expect("Failed to get captain squad")
+                    .unwrap_or_default();
+                squad.push(player);
+                self.storage
+                    .squads
+                    .insert(&signer, squad)
+                    .expect("Failed to update captain squad");
+
+                if self.undrafted_players().await.is_empty() {
+                    self.begin_bans_or_picking();
+                }
+                Ok(())
+            }
+
+            DraftRoomOperation::PickItem { item_id } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                if self.spectators.contains(&signer) {
+                    return Err(DraftRoomError::Spectating);
+                }
+
+                let current_player = self
+                    .get_current_player()
+                    .ok_or(DraftRoomError::NotYourTurn)?;
+
+                if *current_player != signer {
+                    return Err(DraftRoomError::NotYourTurn);
+                }
+
+                // Forced picks are exempt: they only happen once a turn has run long
+                if let (Some(secs), Some(started_at)) = (self.min_pick_interval_secs, self.turn_started_at) {
+                    if self.runtime.system_time() < started_at.saturating_add(TimeDelta::from_secs(secs)) {
+                        return Err(DraftRoomError::PickingTooFast);
+                    }
+                }
+
+                self.record_pick(signer, item_id).await
+            }
+
+            DraftRoomOperation::UndoPick => {
+                let signer = self.authenticated_signer()?;
+
+                // The draft's final pick stands: watchers have already seen the room finish
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                // Only the latest pick, by whoever made it, before anyone picks again or standings are recorded
+                let undoable = self.last_picker == Some(signer)
+                    && self.standings.is_empty()
+                    && self.pick_history.last().is_some_and(|record| record.player == signer);
+                if !undoable {
+                    return Err(DraftRoomError::NothingToUndo);
+                }
+                let record = self.pick_history.pop().expect("Undoable pick is in the history");
+                self.last_picker = None;
+
+                let mut player_picks = self
+                    .storage
+                    .picks
+                    .get(&signer)
+                    .await
+                    .expect("Failed to get player picks")
+                    .unwrap_or_default();
+                if let Some(index) = player_picks.iter().rposition(|item| item.id == record.item.id) {
+                    player_picks.remove(index);
+                }
+                self.storage
+                    .picks
+                    .insert(&signer, player_picks)
+                    .expect("Failed to update player picks");
+
+                if let Some(remaining) = self.remaining_budget(&signer).await {
+                    self.storage
+                        .budgets
+                        .insert(&signer, remaining + record.item.cost)
+                        .expect("Failed to update player budget");
+                }
+
+                // Put the copy back where the item was, ahead of the first higher id
+                match self.pool.iter_mut().find(|item| item.id == record.item.id) {
+                    Some(item) => item.quantity += 1,
+                    None => {
+                        let index = self.pool.iter().position(|item| item.id > record.item.id).unwrap_or(self.pool.len());
+                        self.pool.insert(index, record.item);
+                    }
+                }
+
+                let clock = self.storage.undo_clock.get().clone();
+                self.rewind_turn(clock);
+                Ok(())
+            }
+
+            DraftRoomOperation::ForceAutoPick => {
+                let signer = self.authenticated_signer()?;
+
+                if self.creator != Some(signer) {
+                    return Err(DraftRoomError::NotCreator);
+                }
+
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                // With a turn clock, the current player gets their full turn, extensions included
+                if let Some(deadline) = self.turn_deadline {
+                    if self.runtime.system_time() < deadline {
+                        return Err(DraftRoomError::TurnNotExpired);
+                    }
+                }
+
+                let player = *self
+                    .get_current_player()
+                    .ok_or(DraftRoomError::NotYourTurn)?;
+                let item_id = self.auto_pick_item(&player).await?;
+
+                // Forced picks were not the player's choice, so they cannot be undone
+                self.record_pick(player, item_id).await?;
+                self.last_picker = None;
+                Ok(())
+            }
+
+            DraftRoomOperation::ForcePick => {
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                let deadline = self.turn_deadline.ok_or(DraftRoomError::NoTurnClock)?;
+                if self.runtime.system_time() < deadline {
+                    return Err(DraftRoomError::TurnNotExpired);
+                }
+
+                let player = *self
+                    .get_current_player()
+                    .ok_or(DraftRoomError::NotYourTurn)?;
+                let pickable: Vec<DraftItem> = self
+                    .pickable_items(&player)
+                    .await
+                    .into_iter()
+                    .filter(|item| self.check_power_threshold(item).is_ok())
+                    .collect();
+                let item_id = highest_power_item(&pickable)
+                    .map(|item| item.id)
+                    .ok_or(DraftRoomError::ItemNotFound)?;
+
+                self.record_pick(player, item_id).await?;
+                self.last_picker = None;
+                Ok(())
+            }
+
+            DraftRoomOperation::FinalizeDraft => {
+                if self.status != DraftStatus::Finished {
+                    return Err(DraftRoomError::DraftNotFinished);
+                }
+                self.finalize().await;
+                Ok(())
+            }
+
+            DraftRoomOperation::Concede => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+                if self.players.len() != 2 {
+                    return Err(DraftRoomError::ConcedeNeedsTwoPlayers);
+                }
+                if self.get_current_player() != Some(&signer) {
+                    return Err(DraftRoomError::NotYourTurn);
+                }
+
+                self.conceded_by = Some(signer);
+                self.last_picker = None;
+                self.status = DraftStatus::Finished;
+                self.start_turn_clock();
+                self.finalize().await;
+                Ok(())
+            }
+
+            DraftRoomOperation::UpdatePoolItem { id, name, power } => {
+                self.require_creator_while_waiting()?;
+
+                let item = self
+                    .staged_pool
+                    .iter_mut()
+                    .find(|item| item.id == id)
+                    .ok_or(DraftRoomError::ItemNotFound)?;
+
+                let mut updated = item.clone();
+                if let Some(name) = name {
+                    updated.name = name;
+                }
+                if let Some(power) = power {
+                    updated.power = power;
+                }
+                validate_pool_item(&updated)?;
+
+                *item = updated;
+                Ok(())
+            }
+
+            DraftRoomOperation::AddPoolItem { item } => {
+                self.require_creator_while_waiting()?;
+
+                validate_pool_item(&item)?;
+                if self.staged_pool.iter().any(|staged| staged.id == item.id) {
+                    return Err(DraftRoomError::InvalidPool(format!("item id {} is already staged", item.id)));
+                }
+                if self.staged_pool.len() >= MAX_POOL_ITEMS {
+                    return Err(DraftRoomError::InvalidPool(format!("pool is limited to {} items", MAX_POOL_ITEMS)));
+                }
+
+                self.staged_pool.push(item);
+                Ok(())
+            }
+
+            DraftRoomOperation::RemovePoolItem { id } => {
+                self.require_creator_while_waiting()?;
+
+                let index = self
+                    .staged_pool
+                    .iter()
+                    .position(|item| item.id == id)
+                    .ok_or(DraftRoomError::ItemNotFound)?;
+
+                self.staged_pool.remove(index);
+                Ok(())
+            }
+
+            DraftRoomOperation::SetTurnOrder { order } => {
+                self.require_creator_while_waiting()?;
+
+                self.validate_turn_order(&order)?;
+                self.turn_order = Some(order);
+                Ok(())
+            }
+
+            DraftRoomOperation::SetWishlist { item_ids } => {
+                let signer = self.authenticated_signer()?;
+
+                if !self.players.contains(&signer) {
+                    return Err(DraftRoomError::NotAPlayer);
+                }
+
+                if self.status == DraftStatus::Finished {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                if item_ids.len() > MAX_POOL_ITEMS {
+                    return Err(DraftRoomError::InvalidPool(format!(
+                        "wishlist is limited to {} items",
+                        MAX_POOL_ITEMS
+                    )));
+                }
+
+                self.storage
+                    .wishlists
+                    .insert(&signer, item_ids)
+                    .expect("Failed to store player wishlist");
+                Ok(())
+            }
+
+            DraftRoomOperation::InviteAndReserve { owners } => {
+                self.require_creator_while_waiting()?;
+
+                for (index, owner) in owners.iter().enumerate() {
+                    if self.players.contains(owner) || owners[..index].contains(owner) {
+                        return Err(DraftRoomError::AlreadyJoined);
+                    }
+                }
+
+                if self.players.len() + owners.len() > self.max_players as usize {
+                    return Err(DraftRoomError::RoomFull);
+                }
+
+                for owner in owners {
+                    self.seat_player(owner);
+                    self.reserved.push(owner);
+                }
+                Ok(())
+            }
+
+            DraftRoomOperation::Spectate => {
+                let signer = self.authenticated_signer()?;
+
+                if self.players.contains(&signer) {
+                    return Err(DraftRoomError::AlreadyJoined);
+                }
+
+                // Watching again is a no-op
+                if self.spectators.contains(&signer) {
+                    return Ok(());
+                }
+
+                if self.spectators.len() >= self.max_spectators as usize {
+                    return Err(DraftRoomError::SpectatorLimitReached(self.max_spectators));
+                }
+
+                self.spectators.push(signer);
+                Ok(())
+            }
+
+            DraftRoomOperation::RequestMoreTime => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Drafting {
+                    return Err(DraftRoomError::NotDrafting);
+                }
+
+                if self.get_current_player() != Some(&signer) {
+                    return Err(DraftRoomError::NotYourTurn);
+                }
+
+                let deadline = self.turn_deadline.ok_or(DraftRoomError::NoTurnClock)?;
+                let used = self.time_requests_used.entry(signer).or_insert(0);
+                if *used >= MAX_TIME_REQUESTS {
+                    return Err(DraftRoomError::NoTimeRequestsLeft);
+                }
+
+                *used += 1;
+                self.turn_deadline = Some(deadline.saturating_add(TimeDelta::from_secs(TIME_REQUEST_SECS)));
+                Ok(())
+            }
+
+            DraftRoomOperation::SetMaxPlayers { max_players } => {
+                self.require_creator_while_waiting()?;
+
+                let seated = self.players.len() as u8;
+                if !(MIN_PLAYERS_TO_START..=MAX_ROOM_CAPACITY).contains(&max_players) || max_players < seated {
+                    return Err(DraftRoomError::InvalidCapacity(seated));
+                }
+                if max_players == self.max_players {
+                    return Ok(());
+                }
+
+                self.max_players = max_players;
+                if let Some(lobby) = self.lobby {
+                    self.runtime
+                        .prepare_message(Message::MaxPlayersChanged { max_players })
+                        .send_to(lobby);
+                }
+                Ok(())
+            }
+
+            DraftRoomOperation::RevealSeed { seed } => {
+                self.require_creator_while_waiting()?;
+
+                let commitment = self.seed_commitment.as_ref().ok_or(DraftRoomError::NoSeedCommitment)?;
+                if seed_commitment(seed) != *commitment {
+                    return Err(DraftRoomError::SeedMismatch);
+                }
+                self.pool_seed = Some(seed);
+                Ok(())
+            }
+
+            DraftRoomOperation::SetReady { ready } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Waiting {
+                    return Err(DraftRoomError::NotWaiting);
+                }
+                if !self.players.contains(&signer) {
+                    return Err(DraftRoomError::NotAPlayer);
+                }
+
+                self.storage
+                    .ready
+                    .insert(&signer, ready)
+                    .expect("Failed to store player ready flag");
+                Ok(())
+            }
+
+            DraftRoomOperation::SuggestToPlayer { player, item_id } => {
+                let signer = self.authenticated_signer()?;
+
+                if !self.players.contains(&signer) && !self.spectators.contains(&signer) {
+                    return Err(DraftRoomError::NotInRoom);
+                }
+                if !self.players.contains(&player) {
+                    return Err(DraftRoomError::NotAPlayer);
+                }
+                if !self.pool.iter().any(|item| item.id == item_id) {
+                    return Err(DraftRoomError::ItemNotFound);
+                }
+
+                // Advisory only: the draft itself is untouched
+                let mut suggestions = self
+                    .storage
+                    .suggestions
+                    .get(&player)
+                    .await
+                    .expect("Failed to get player suggestions")
+                    .unwrap_or_default();
+                suggestions.retain(|id| *id != item_id);
+                suggestions.push(item_id);
+                if suggestions.len() > MAX_SUGGESTIONS {
+                    suggestions.remove(0);
+                }
+                self.storage
+                    .suggestions
+                    .insert(&player, suggestions)
+                    .expect("Failed to store player suggestions");
+                Ok(())
+            }
+
+            DraftRoomOperation::BanItem { item_id } => {
+                let signer = self.authenticated_signer()?;
+
+                if self.status != DraftStatus::Banning {
+                    return Err(DraftRoomError::NotBanning);
+                }
+
+                if self.current_banner() != Some(&signer) {
+                    return Err(DraftRoomError::NotYourTurn);
+                }
+
+                // A ban takes one copy out of the pool without giving it to anyone
+                let item_index = self
+                    .pool
+                    .iter()
+                    .position(|item| item.id == item_id)
+                    .ok_or(DraftRoomError::ItemNotFound)?;
+                let pool_item = &mut self.pool[item_index];
+                pool_item.quantity = pool_item.quantity.saturating_sub(1);
+                let item = DraftItem {
+                    quantity: 1,
+                    ..pool_item.clone()
+                };
+                if pool_item.quantity == 0 {
+                    self.pool.remove(item_index);
+                }
+
+                let ban_round = (self.bans.len() / self.players.len()) as u8 + 1;
+                self.bans.push(PickRecord {
+                    player: signer,
+                    item,
+                    round: ban_round,
+                });
+
+                if self.bans.len() >= usize::from(self.ban_rounds) * self.players.len() {
+                    self.begin_picking();
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use linera_sdk::util::BlockingWait;
+
+    use super::*;
+    use crate::RoomOptions;
+
+    fn owner(n: u8) -> Owner {
+        Owner::from_str(&format!("{:064x}", n)).unwrap()
+    }
+
+    fn create_room(max_players: u8, creator: Owner) -> DraftRoom {
+        let runtime = ContractRuntime::new().with_application_parameters(ContractParameters::DraftRoom {
+            max_players,
+            options: RoomOptions::default(),
+            creator,
+            lobby: None,
+        });
+        DraftRoom::load(runtime).blocking_wait()
+    }
+
+    fn execute_as(
+        room: &mut DraftRoom,
+        signer: Owner,
+        operation: DraftRoomOperation,
+    ) -> Result<(), DraftRoomError> {
+        room.runtime.set_authenticated_signer(signer);
+        room.execute_operation(operation).blocking_wait()
+    }
+
+    /// Mark every seated player ready, as `StartDraft` requires
+    fn ready_all(room: &mut DraftRoom) {
+        for player in room.players.clone() {
+            execute_as(room, player, DraftRoomOperation::SetReady { ready: true }).unwrap();
+        }
+    }
+
+    /// A two-player room that has started drafting, with `owner(1)` as creator
+    fn drafting_room() -> DraftRoom {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        room
+    }
+
+    fn assert_violation(room: &DraftRoom) {
+        assert!(matches!(
+            room.validate_invariants().blocking_wait(),
+            Err(DraftRoomError::InvariantViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_invariants_hold_through_normal_play() {
+        let mut room = drafting_room();
+        room.validate_invariants().blocking_wait().unwrap();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        room.validate_invariants().blocking_wait().unwrap();
+    }
+
+    #[test]
+    fn test_invariants_detect_over_capacity() {
+        let mut room = drafting_room();
+        room.players.push(owner(3));
+        room.storage.picks.insert(&owner(3), Vec::new()).unwrap();
+        assert_violation(&room);
+    }
+
+    #[test]
+    fn test_invariants_detect_duplicate_player() {
+        let mut room = create_room(4, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        room.players.push(owner(1));
+        assert_violation(&room);
+    }
+
+    #[test]
+    fn test_invariants_detect_missing_picks_entry() {
+        let mut room = create_room(4, owner(1));
+        room.players.push(owner(2));
+        assert_violation(&room);
+    }
+
+    #[test]
+    fn test_invariants_detect_turn_out_of_range() {
+        let mut room = drafting_room();
+        room.current_turn = 2;
+        assert_violation(&room);
+    }
+
+    #[test]
+    fn test_invariants_detect_round_overflow() {
+        let mut room = drafting_room();
+        room.round = room.max_rounds + 2;
+        assert_violation(&room);
+    }
+
+    #[test]
+    fn test_item_with_quantity_two_can_be_drafted_twice() {
+        let mut room = drafting_room();
+        room.pool = vec![
+            DraftItem { quantity: 2, ..DraftItem::new(1, "Lightning Bolt", 100) },
+            DraftItem::new(2, "Counterspell", 90),
+        ];
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.pool[0].quantity, 1);
+
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.pool, vec![DraftItem::new(2, "Counterspell", 90)]);
+
+        let picks = room.storage.picks.get(&owner(2)).blocking_wait().unwrap().unwrap();
+        assert_eq!(picks, vec![DraftItem::new(1, "Lightning Bolt", 100)]);
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::ItemNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_highest_power_tiebreak_prefers_lowest_id() {
+        let pool = vec![
+            DraftItem::new(9, "Time Walk", 95),
+            DraftItem::new(7, "Black Lotus", 100),
+            DraftItem::new(1, "Lightning Bolt", 100),
+        ];
+        assert_eq!(highest_power_item(&pool).map(|item| item.id), Some(1));
+
+        let reversed: Vec<_> = pool.into_iter().rev().collect();
+        assert_eq!(highest_power_item(&reversed).map(|item| item.id), Some(1));
+        assert_eq!(highest_power_item(&[]), None);
+    }
+
+    #[test]
+    fn test_creator_can_update_staged_item_power() {
+        let mut room = create_room(2, owner(1));
+        room.staged_pool = vec![DraftItem::new(1, "Lightning Bolt", 100)];
+
+        let update = |power| DraftRoomOperation::UpdatePoolItem { id: 1, name: None, power: Some(power) };
+
+        execute_as(&mut room, owner(1), update(120)).unwrap();
+        assert_eq!(room.staged_pool[0].power, 120);
+        assert_eq!(room.staged_pool[0].name, "Lightning Bolt");
+
+        assert!(matches!(execute_as(&mut room, owner(2), update(1)), Err(DraftRoomError::NotCreator)));
+        assert!(matches!(execute_as(&mut room, owner(1), update(0)), Err(DraftRoomError::InvalidPool(_))));
+        assert_eq!(room.staged_pool[0].power, 120);
+    }
+
+    #[test]
+    fn test_creator_can_add_and_remove_staged_items() {
+        let mut room = create_room(2, owner(1));
+        let add = |id, power| DraftRoomOperation::AddPoolItem { item: DraftItem::new(id, "Card", power) };
+
+        execute_as(&mut room, owner(1), add(1, 50)).unwrap();
+        execute_as(&mut room, owner(1), add(2, 60)).unwrap();
+        assert_eq!(room.staged_pool.iter().map(|item| item.id).collect::<Vec<_>>(), vec![1, 2]);
+
+        assert!(matches!(execute_as(&mut room, owner(1), add(1, 70)), Err(DraftRoomError::InvalidPool(_))));
+        assert!(matches!(execute_as(&mut room, owner(1), add(3, 0)), Err(DraftRoomError::InvalidPool(_))));
+        assert!(matches!(execute_as(&mut room, owner(2), add(3, 70)), Err(DraftRoomError::NotCreator)));
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::RemovePoolItem { id: 1 }).unwrap();
+        assert_eq!(room.staged_pool, vec![DraftItem::new(2, "Card", 60)]);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::RemovePoolItem { id: 1 }),
+            Err(DraftRoomError::ItemNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_start_draft_uses_staged_pool() {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        let item = DraftItem { quantity: 6, ..DraftItem::new(42, "Custom Card", 10) };
+        execute_as(&mut room, owner(1), DraftRoomOperation::AddPoolItem { item: item.clone() }).unwrap();
+
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.pool, vec![item.clone()]);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::AddPoolItem { item }),
+            Err(DraftRoomError::NotWaiting)
+        ));
+    }
+
+    #[test]
+    fn test_start_draft_without_staged_pool_uses_default() {
+        let room = drafting_room();
+        assert_eq!(room.pool.len(), 15);
+    }
+
+    #[test]
+    fn test_mid_draft_room_resumes_from_checkpoint() {
+        let mut room = drafting_room();
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 7 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 6 }).unwrap();
+
+        let (current_turn, round, pool) = (room.current_turn, room.round, room.pool.clone());
+        let runtime = room.runtime.clone();
+        room.store().blocking_wait();
+
+        let reloaded = DraftRoom::load(runtime).blocking_wait();
+        assert_eq!(reloaded.current_turn, current_turn);
+        assert_eq!(reloaded.round, round);
+        assert_eq!(reloaded.pool, pool);
+        assert_eq!(reloaded.status, DraftStatus::Drafting);
+        assert_eq!(reloaded.creator, Some(owner(1)));
+        assert_eq!(reloaded.players, vec![owner(1), owner(2)]);
+        assert_eq!(reloaded.pick_history.len(), 3);
+
+        let picks = |player| reloaded.storage.picks.get(&player).blocking_wait().unwrap().unwrap();
+        assert_eq!(picks(owner(1)).iter().map(|item| item.id).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(picks(owner(2)).iter().map(|item| item.id).collect::<Vec<_>>(), vec![1, 6]);
+    }
+
+    #[test]
+    fn test_explicit_turn_order_drives_first_picks() {
+        let mut room = create_room(3, owner(1));
+        for player in [owner(1), owner(2), owner(3)] {
+            execute_as(&mut room, player, DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+
+        let order = vec![owner(3), owner(1), owner(2)];
+        execute_as(&mut room, owner(1), DraftRoomOperation::SetTurnOrder { order: order.clone() }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.players, order);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::NotYourTurn)
+        ));
+        execute_as(&mut room, owner(3), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 2 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 3 }).unwrap();
+    }
+
+    #[test]
+    fn test_turn_order_must_be_permutation_of_players() {
+        let mut room = create_room(3, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        for order in [vec![owner(1)], vec![owner(1), owner(1)], vec![owner(1), owner(2), owner(3)]] {
+            assert!(matches!(
+                execute_as(&mut room, owner(1), DraftRoomOperation::SetTurnOrder { order }),
+                Err(DraftRoomError::InvalidTurnOrder(_))
+            ));
+        }
+        assert_eq!(room.turn_order, None);
+    }
+
+    #[test]
+    fn test_final_round_rejects_pick_below_power_threshold() {
+        let mut room = drafting_room();
+        room.final_round_min_power = Some(90);
+        room.round = room.max_rounds;
+        room.pool = vec![
+            DraftItem::new(1, "Healing Salve", 70),
+            DraftItem::new(2, "Counterspell", 90),
+            DraftItem::new(3, "Brainstorm", 75),
+        ];
+
+        // Final round is odd, so owner(1) picks first
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::BelowPowerThreshold(90))
+        ));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 2 }).unwrap();
+
+        // Nothing left meets the threshold, so any pick is allowed
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+    }
+
+    #[test]
+    fn test_auto_pick_honors_wishlist() {
+        let mut room = drafting_room();
+        execute_as(&mut room, owner(1), DraftRoomOperation::SetWishlist { item_ids: vec![99, 12, 3] }).unwrap();
+
+        // Item 99 isn't in the pool, so the first available wish is taken
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        let picks = room.storage.picks.get(&owner(1)).blocking_wait().unwrap().unwrap();
+        assert_eq!(picks.iter().map(|item| item.id).collect::<Vec<_>>(), vec![12]);
+
+        // Without a wishlist, auto-pick falls back to the highest power item
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        let picks = room.storage.picks.get(&owner(2)).blocking_wait().unwrap().unwrap();
+        assert_eq!(picks.iter().map(|item| item.id).collect::<Vec<_>>(), vec![1]);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::ForceAutoPick),
+            Err(DraftRoomError::NotCreator)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_draft_rejected_at_start() {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        room.max_rounds = 129;
+
+        ready_all(&mut room);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::DraftTooLarge(258))
+        ));
+        assert_eq!(room.status, DraftStatus::Waiting);
+
+        room.max_rounds = 128;
+        room.staged_pool = vec![
+            DraftItem { quantity: 128, ..DraftItem::new(1, "Lightning Bolt", 100) },
+            DraftItem { quantity: 128, ..DraftItem::new(2, "Counterspell", 90) },
+        ];
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+    }
+
+    #[test]
+    fn test_reserved_seats_count_toward_capacity() {
+        let mut room = create_room(3, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        let reserve = |owners| DraftRoomOperation::InviteAndReserve { owners };
+        assert!(matches!(
+            execute_as(&mut room, owner(1), reserve(vec![owner(2), owner(2)])),
+            Err(DraftRoomError::AlreadyJoined)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), reserve(vec![owner(2), owner(3), owner(4)])),
+            Err(DraftRoomError::RoomFull)
+        ));
+
+        execute_as(&mut room, owner(1), reserve(vec![owner(2), owner(3)])).unwrap();
+        assert_eq!(room.players, vec![owner(1), owner(2), owner(3)]);
+        assert_eq!(room.reserved, vec![owner(2), owner(3)]);
+        assert!(room.storage.picks.contains_key(&owner(3)).blocking_wait().unwrap());
+        room.validate_invariants().blocking_wait().unwrap();
+
+        assert!(matches!(
+            execute_as(&mut room, owner(4), DraftRoomOperation::JoinRoom { password: None }),
+            Err(DraftRoomError::RoomFull)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }),
+            Err(DraftRoomError::RoomFull)
+        ));
+    }
+
+    #[test]
+    fn test_round_weights_reorder_standings() {
+        let mut room = drafting_room();
+        room.round_weights = vec![3, 1, 1];
+
+        // Round 1 forward, round 2 backward, round 3 forward
+        for (player, item_id) in [(1, 5), (2, 1), (2, 12), (1, 7), (1, 9), (2, 14)] {
+            execute_as(&mut room, owner(player), DraftRoomOperation::PickItem { item_id }).unwrap();
+        }
+        execute_as(&mut room, owner(1), DraftRoomOperation::FinalizeDraft).unwrap();
+
+        let summary: Vec<_> = room
+            .standings
+            .iter()
+            .map(|standing| (standing.player, standing.raw_power, standing.weighted_power))
+            .collect();
+        assert_eq!(summary, vec![(owner(2), 255, 455), (owner(1), 265, 405)]);
+
+        // Equal weights rank by raw power
+        let unweighted = compute_standings(&room.players, &room.pick_history, &[]);
+        assert_eq!(unweighted[0].player, owner(1));
+        assert!(unweighted.iter().all(|standing| standing.raw_power == standing.weighted_power));
+    }
+
+    #[test]
+    fn test_pick_after_draft_finished_is_rejected() {
+        let mut room = drafting_room();
+        for (player, item_id) in [(1, 5), (2, 1), (2, 12), (1, 7), (1, 9), (2, 14)] {
+            execute_as(&mut room, owner(player), DraftRoomOperation::PickItem { item_id }).unwrap();
+        }
+        assert_eq!(room.status, DraftStatus::Finished);
+        execute_as(&mut room, owner(1), DraftRoomOperation::FinalizeDraft).unwrap();
+
+        let pool = room.pool.clone();
+        let history_len = room.pick_history.len();
+        let standings = room.standings.clone();
+        let picks = |room: &DraftRoom, player| room.storage.picks.get(&owner(player)).blocking_wait().unwrap().unwrap();
+        let (picks_1, picks_2) = (picks(&room, 1), picks(&room, 2));
+
+        // Whoever the stale turn pointer names, a late pick is refused
+        for player in [1, 2] {
+            assert!(matches!(
+                execute_as(&mut room, owner(player), DraftRoomOperation::PickItem { item_id: 2 }),
+                Err(DraftRoomError::NotDrafting)
+            ));
+        }
+
+        assert_eq!(room.pool, pool);
+        assert_eq!(room.pick_history.len(), history_len);
+        assert_eq!(room.standings, standings);
+        assert_eq!((picks(&room, 1), picks(&room, 2)), (picks_1, picks_2));
+    }
+
+    #[test]
+    fn test_pick_over_budget_rejected() {
+        let mut room = create_room(2, owner(1));
+        room.budget = Some(100);
+        room.staged_pool = vec![
+            DraftItem { cost: 70, ..DraftItem::new(1, "Lightning Bolt", 100) },
+            DraftItem { cost: 40, quantity: 3, ..DraftItem::new(2, "Counterspell", 90) },
+            DraftItem { cost: 30, ..DraftItem::new(3, "Healing Salve", 70) },
+            DraftItem { cost: 10, ..DraftItem::new(4, "Giant Growth", 60) },
+        ];
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 3 }).unwrap();
+        assert_eq!(room.storage.budgets.get(&owner(1)).blocking_wait().unwrap(), Some(30));
+
+        // Snake order: owner(2) picks again, then owner(1) with 30 left
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 4 }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 2 }),
+            Err(DraftRoomError::OverBudget { cost: 40, remaining: 30 })
+        ));
+        assert_eq!(room.pool.len(), 1);
+        assert_eq!(room.storage.budgets.get(&owner(1)).blocking_wait().unwrap(), Some(30));
+    }
+
+    #[test]
+    fn test_spectators_capped_at_max() {
+        let mut room = create_room(2, owner(1));
+        room.max_spectators = 2;
+
+        execute_as(&mut room, owner(3), DraftRoomOperation::Spectate).unwrap();
+        execute_as(&mut room, owner(4), DraftRoomOperation::Spectate).unwrap();
+        // Re-spectating doesn't take another slot
+        execute_as(&mut room, owner(4), DraftRoomOperation::Spectate).unwrap();
+        assert_eq!(room.spectators, vec![owner(3), owner(4)]);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(5), DraftRoomOperation::Spectate),
+            Err(DraftRoomError::SpectatorLimitReached(2))
+        ));
+        assert_eq!(room.spectators.len(), 2);
+    }
+
+    #[test]
+    fn test_only_current_player_can_request_more_time() {
+        let mut room = create_room(2, owner(1));
+        room.turn_duration_secs = Some(60);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        let deadline = room.turn_deadline.unwrap();
+
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::RequestMoreTime),
+            Err(DraftRoomError::NotYourTurn)
+        ));
+        assert_eq!(room.turn_deadline, Some(deadline));
+        assert!(room.time_requests_used.is_empty());
+
+        for _ in 0..MAX_TIME_REQUESTS {
+            execute_as(&mut room, owner(1), DraftRoomOperation::RequestMoreTime).unwrap();
+        }
+        let extended = deadline.saturating_add(TimeDelta::from_secs(TIME_REQUEST_SECS * u64::from(MAX_TIME_REQUESTS)));
+        assert_eq!(room.turn_deadline, Some(extended));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::RequestMoreTime),
+            Err(DraftRoomError::NoTimeRequestsLeft)
+        ));
+    }
+
+    #[test]
+    fn test_preview_picks_leave_pool_untouched() {
+        let mut room = drafting_room();
+        room.preview = true;
+        let pool_size = room.pool.len();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+
+        assert_eq!(room.pool.len(), pool_size);
+        assert!(room.pick_history.is_empty());
+        assert_eq!(room.storage.picks.get(&owner(1)).blocking_wait().unwrap(), Some(vec![]));
+        // Turns still advance, and invalid picks are still refused
+        assert_eq!(room.round, 2);
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 99 }),
+            Err(DraftRoomError::ItemNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_ban_phase_then_pick_phase() {
+        let mut room = create_room(2, owner(1));
+        room.ban_rounds = 1;
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.status, DraftStatus::Banning);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::NotDrafting)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::BanItem { item_id: 1 }),
+            Err(DraftRoomError::NotYourTurn)
+        ));
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::BanItem { item_id: 7 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::BanItem { item_id: 1 }).unwrap();
+        assert_eq!(room.status, DraftStatus::Drafting);
+        assert_eq!(room.pool.len(), 13);
+        assert_eq!(room.bans.iter().map(|ban| ban.item.id).collect::<Vec<_>>(), vec![7, 1]);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::ItemNotFound)
+        ));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 6 }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::BanItem { item_id: 2 }),
+            Err(DraftRoomError::NotBanning)
+        ));
+    }
+
+    #[test]
+    fn test_ban_takes_one_copy_of_a_stack() {
+        let mut room = create_room(2, owner(1));
+        room.ban_rounds = 1;
+        room.staged_pool = vec![
+            DraftItem { quantity: 3, ..DraftItem::new(1, "Lightning Bolt", 100) },
+            DraftItem { quantity: 6, ..DraftItem::new(2, "Counterspell", 90) },
+        ];
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::BanItem { item_id: 1 }).unwrap();
+        assert_eq!(room.pool[0].quantity, 2);
+        assert_eq!(room.bans[0].item.quantity, 1);
+        execute_as(&mut room, owner(2), DraftRoomOperation::BanItem { item_id: 1 }).unwrap();
+        assert_eq!(room.status, DraftStatus::Drafting);
+        assert_eq!(room.pool.iter().map(|item| (item.id, item.quantity)).collect::<Vec<_>>(), vec![(1, 1), (2, 6)]);
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.pool.iter().map(|item| item.id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_sample_pool_is_deterministic_and_weighted() {
+        let candidates: Vec<DraftItem> = (1..=20)
+            .map(|id| DraftItem {
+                weight: if id == 1 { 1_000 } else { 1 },
+                ..DraftItem::new(id, "Card", 50)
+            })
+            .collect();
+
+        let sampled = sample_pool(&candidates, 5, 42);
+        assert_eq!(sampled, sample_pool(&candidates, 5, 42));
+        assert_eq!(sampled.len(), 5);
+        assert!(sampled.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+        // The heavy item is drawn for almost every seed
+        let heavy_draws = (0..100).filter(|seed| sample_pool(&candidates, 2, *seed)[0].id == 1).count();
+        assert!(heavy_draws > 90);
+
+        let unweighted = vec![DraftItem { weight: 0, ..DraftItem::new(1, "Card", 50) }, DraftItem::new(2, "Card", 50)];
+        assert_eq!(sample_pool(&unweighted, 2, 7), vec![DraftItem::new(2, "Card", 50)]);
+    }
+
+    #[test]
+    fn test_start_draft_samples_pool_with_seed() {
+        let mut room = create_room(2, owner(1));
+        room.pool_size = Some(6);
+        room.pool_seed = Some(7);
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        assert_eq!(room.pool, sample_pool(&DraftRoom::initialize_pool(), 6, 7));
+        assert_eq!(room.pool_seed, Some(7));
+    }
+
+    #[test]
+    fn test_status_report_reflects_live_state() {
+        let room = drafting_room();
+        assert!(matches!(
+            room.status_report(),
+            Message::RoomStatusReport { status: DraftStatus::Drafting, player_count: 2 }
+        ));
+    }
+
+    #[test]
+    fn test_set_max_players_while_waiting() {
+        let mut room = create_room(4, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(3), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::SetMaxPlayers { max_players: 6 }).unwrap();
+        assert_eq!(room.max_players, 6);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::SetMaxPlayers { max_players: 2 }),
+            Err(DraftRoomError::InvalidCapacity(3))
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::SetMaxPlayers { max_players: 9 }),
+            Err(DraftRoomError::InvalidCapacity(3))
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::SetMaxPlayers { max_players: 5 }),
+            Err(DraftRoomError::NotCreator)
+        ));
+
+        let runtime = room.runtime.clone();
+        room.store().blocking_wait();
+        let reloaded = DraftRoom::load(runtime).blocking_wait();
+        assert_eq!(reloaded.max_players, 6);
+    }
+
+    #[test]
+    fn test_committed_seed_must_be_revealed_before_start() {
+        let mut room = create_room(2, owner(1));
+        room.pool_size = Some(6);
+        room.seed_commitment = Some(seed_commitment(1234));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        ready_all(&mut room);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::SeedNotRevealed)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::RevealSeed { seed: 4321 }),
+            Err(DraftRoomError::SeedMismatch)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::RevealSeed { seed: 1234 }),
+            Err(DraftRoomError::NotCreator)
+        ));
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::RevealSeed { seed: 1234 }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        let revealed = room.pool_seed.unwrap();
+        assert_eq!(Some(seed_commitment(revealed)), room.seed_commitment);
+        assert_eq!(room.pool, sample_pool(&DraftRoom::initialize_pool(), 6, revealed));
+    }
+
+    #[test]
+    fn test_reveal_needs_a_commitment() {
+        let mut room = create_room(2, owner(1));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::RevealSeed { seed: 1 }),
+            Err(DraftRoomError::NoSeedCommitment)
+        ));
+    }
+
+    #[test]
+    fn test_pick_increments_state_version() {
+        let mut room = drafting_room();
+        let before = room.version;
+
+        let item_id = room.pool[0].id;
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id }).unwrap();
+        assert_eq!(room.version, before + 1);
+
+        // Rejected operations leave the version alone
+        assert!(execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id }).is_err());
+        assert_eq!(room.version, before + 1);
+    }
+
+    #[test]
+    fn test_suggestions_are_stored_without_touching_the_draft() {
+        let mut room = drafting_room();
+        execute_as(&mut room, owner(3), DraftRoomOperation::Spectate).unwrap();
+        let (pool, turn) = (room.pool.clone(), room.current_turn);
+        let (first, second) = (pool[0].id, pool[1].id);
+
+        execute_as(&mut room, owner(3), DraftRoomOperation::SuggestToPlayer { player: owner(1), item_id: first }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::SuggestToPlayer { player: owner(1), item_id: second }).unwrap();
+        // Suggesting again moves the item to the back rather than repeating it
+        execute_as(&mut room, owner(3), DraftRoomOperation::SuggestToPlayer { player: owner(1), item_id: first }).unwrap();
+
+        let suggestions = room.storage.suggestions.get(&owner(1)).blocking_wait().unwrap();
+        assert_eq!(suggestions, Some(vec![second, first]));
+        assert_eq!(room.storage.suggestions.get(&owner(2)).blocking_wait().unwrap(), None);
+        assert_eq!((room.pool.clone(), room.current_turn), (pool, turn));
+        assert!(room.pick_history.is_empty());
+
+        assert!(matches!(
+            execute_as(&mut room, owner(4), DraftRoomOperation::SuggestToPlayer { player: owner(1), item_id: first }),
+            Err(DraftRoomError::NotInRoom)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::SuggestToPlayer { player: owner(3), item_id: first }),
+            Err(DraftRoomError::NotAPlayer)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::SuggestToPlayer { player: owner(1), item_id: 200 }),
+            Err(DraftRoomError::ItemNotFound)
+        ));
+    }
+
+    #[test]
+    fn test_custom_pool_from_options_is_drafted() {
+        let pool = vec![
+            DraftItem { quantity: 3, ..DraftItem::new(1, "Rook", 50) },
+            DraftItem { quantity: 3, ..DraftItem::new(2, "Bishop", 30) },
+        ];
+        let runtime = ContractRuntime::new().with_application_parameters(ContractParameters::DraftRoom {
+            max_players: 2,
+            options: RoomOptions { custom_pool: pool.clone(), ..RoomOptions::default() },
+            creator: owner(1),
+            lobby: None,
+        });
+        let mut room = DraftRoom::load(runtime).blocking_wait();
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        assert_eq!(room.pool, pool);
+    }
+
+    #[test]
+    fn test_room_opened_by_lobby_can_be_started_by_its_creator() {
+        let runtime = ContractRuntime::new().with_application_parameters(ContractParameters::Lobby);
+        let mut lobby = crate::Lobby::load(runtime).blocking_wait();
+        // The parameters `CreateRoom` opens the room chain with
+        let parameters = lobby.room_parameters(2, RoomOptions::default(), owner(1));
+
+        let mut room = DraftRoom::load(ContractRuntime::new().with_application_parameters(parameters)).blocking_wait();
+        assert_eq!(room.creator, Some(owner(1)));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::NotCreator)
+        ));
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.status, DraftStatus::Drafting);
+    }
+
+    #[test]
+    fn test_leave_room_frees_the_seat_and_hands_over_creator() {
+        let mut room = create_room(3, owner(1));
+        for player in [owner(1), owner(2), owner(3)] {
+            execute_as(&mut room, player, DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::LeaveRoom).unwrap();
+        assert_eq!(room.players, vec![owner(2), owner(3)]);
+        assert_eq!(room.creator, Some(owner(2)));
+        assert!(!room.storage.picks.contains_key(&owner(1)).blocking_wait().unwrap());
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::LeaveRoom),
+            Err(DraftRoomError::NotInRoom)
+        ));
+        // The freed seat can be taken again
+        execute_as(&mut room, owner(4), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        execute_as(&mut room, owner(3), DraftRoomOperation::LeaveRoom).unwrap();
+        execute_as(&mut room, owner(4), DraftRoomOperation::LeaveRoom).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::LeaveRoom).unwrap();
+        assert!(room.players.is_empty());
+        assert_eq!(room.creator, None);
+    }
+
+    #[test]
+    fn test_leave_room_only_while_waiting() {
+        let mut room = drafting_room();
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::LeaveRoom),
+            Err(DraftRoomError::NotWaiting)
+        ));
+    }
+
+    #[test]
+    fn test_handicapped_first_pick_cannot_take_top_item() {
+        let mut room = drafting_room();
+        room.handicap_first_pick = true;
+        let top = handicapped_item(&room.pool).unwrap();
+        let other = room.pool.iter().find(|item| item.id != top).unwrap().id;
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: top }),
+            Err(DraftRoomError::HandicapRestricted)
+        ));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: other }).unwrap();
+
+        // Only the first pick is restricted
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: top }).unwrap();
+    }
+
+    #[test]
+    fn test_handicapped_auto_pick_skips_top_item() {
+        let mut room = drafting_room();
+        room.handicap_first_pick = true;
+        let top = handicapped_item(&room.pool).unwrap();
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        assert_ne!(room.pick_history[0].item.id, top);
+    }
+
+    #[test]
+    fn test_handicapped_item_breaks_power_ties_by_lowest_id() {
+        let pool = vec![DraftItem::new(2, "Bishop", 50), DraftItem::new(1, "Rook", 50), DraftItem::new(3, "Pawn", 10)];
+        assert_eq!(handicapped_item(&pool), Some(1));
+        assert_eq!(handicapped_item(&[]), None);
+    }
+
+    #[test]
+    fn test_finalize_records_winner_by_total_power() {
+        let mut room = drafting_room();
+        for (player, item_id) in [(1, 5), (2, 1), (2, 12), (1, 7), (1, 9), (2, 14)] {
+            execute_as(&mut room, owner(player), DraftRoomOperation::PickItem { item_id }).unwrap();
+        }
+        assert_eq!(room.winner, None);
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::FinalizeDraft).unwrap();
+        // 70 + 100 + 95 beats 100 + 75 + 80
+        assert_eq!(room.winner, Some(owner(1)));
+    }
+
+    #[test]
+    fn test_draft_winner_ties_go_to_lowest_owner() {
+        assert_eq!(draft_winner(&[(owner(3), 90), (owner(2), 90), (owner(1), 40)]), Some(owner(2)));
+        assert_eq!(draft_winner(&[]), None);
+    }
+
+    #[test]
+    fn test_force_pick_waits_for_the_turn_clock() {
+        let mut room = create_room(2, owner(1));
+        room.turn_duration_secs = Some(60);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        room.runtime.set_system_time(Timestamp::from(30_000_000));
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::ForcePick),
+            Err(DraftRoomError::TurnNotExpired)
+        ));
+
+        // Once the clock runs out anyone may pick the strongest item for the idle player
+        room.runtime.set_system_time(Timestamp::from(60_000_000));
+        execute_as(&mut room, owner(2), DraftRoomOperation::ForcePick).unwrap();
+        assert_eq!(room.pick_history[0].player, owner(1));
+        assert_eq!(room.pick_history[0].item.id, 1);
+
+        // The next turn gets a fresh clock
+        assert_eq!(room.turn_deadline, Some(Timestamp::from(120_000_000)));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::ForcePick),
+            Err(DraftRoomError::TurnNotExpired)
+        ));
+    }
+
+    #[test]
+    fn test_auto_pick_waits_for_the_current_players_clock() {
+        let mut room = create_room(2, owner(1));
+        room.turn_duration_secs = Some(60);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.turn_started_at, Some(Timestamp::from(0)));
+
+        // Owner 1 picks late in their turn; owner 2's clock starts from that pick
+        room.runtime.set_system_time(Timestamp::from(50_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 5 }).unwrap();
+        assert_eq!(room.turn_started_at, Some(Timestamp::from(50_000_000)));
+
+        room.runtime.set_system_time(Timestamp::from(70_000_000));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick),
+            Err(DraftRoomError::TurnNotExpired)
+        ));
+
+        room.runtime.set_system_time(Timestamp::from(110_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        assert_eq!(room.pick_history[1].player, owner(2));
+    }
+
+    #[test]
+    fn test_auto_pick_honours_requested_time() {
+        let mut room = create_room(2, owner(1));
+        room.turn_duration_secs = Some(60);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        execute_as(&mut room, owner(1), DraftRoomOperation::RequestMoreTime).unwrap();
+
+        // Past the base 60s but inside the 30s extension
+        room.runtime.set_system_time(Timestamp::from(70_000_000));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick),
+            Err(DraftRoomError::TurnNotExpired)
+        ));
+        assert!(room.pick_history.is_empty());
+
+        room.runtime.set_system_time(Timestamp::from(90_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        assert_eq!(room.pick_history[0].player, owner(1));
+    }
+
+    #[test]
+    fn test_validate_draft_config_checks_cap_and_pool_size() {
+        let pool = DraftRoom::initialize_pool();
+        assert!(validate_draft_config(2, 3, 0, &pool).is_ok());
+        assert!(validate_draft_config(5, 3, 0, &pool).is_ok());
+        assert!(matches!(
+            validate_draft_config(6, 3, 0, &pool),
+            Err(DraftRoomError::PoolTooSmall { picks: 18, bans: 0, items: 15 })
+        ));
+
+        // Extra copies count toward the pool
+        let copies = vec![DraftItem { quantity: 6, ..DraftItem::new(1, "Rook", 50) }];
+        assert!(validate_draft_config(2, 3, 0, &copies).is_ok());
+        assert!(matches!(
+            validate_draft_config(3, 3, 0, &copies),
+            Err(DraftRoomError::PoolTooSmall { picks: 9, bans: 0, items: 6 })
+        ));
+
+        // The cap is reported before the pool size
+        assert!(matches!(validate_draft_config(2, 129, 0, &pool), Err(DraftRoomError::DraftTooLarge(258))));
+
+        // Bans are taken from the same pool before picking starts
+        assert!(validate_draft_config(2, 3, 4, &pool).is_ok());
+        assert!(matches!(
+            validate_draft_config(2, 3, 5, &pool),
+            Err(DraftRoomError::PoolTooSmall { picks: 6, bans: 10, items: 15 })
+        ));
+    }
+
+    #[test]
+    fn test_start_draft_rejects_pool_smaller_than_draft() {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        room.pool_size = Some(5);
+
+        ready_all(&mut room);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::PoolTooSmall { picks: 6, bans: 0, items: 5 })
+        ));
+        assert_eq!(room.status, DraftStatus::Waiting);
+        assert!(room.pool.is_empty());
+    }
+
+    #[test]
+    fn test_spectator_must_leave_before_joining() {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::Spectate).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }),
+            Err(DraftRoomError::Spectating)
+        ));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::Spectating)
+        ));
+        assert_eq!(room.players, vec![owner(2)]);
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::LeaveRoom).unwrap();
+        assert!(room.spectators.is_empty());
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        // Watching mid-draft takes no seat, and leaving is allowed at any point
+        execute_as(&mut room, owner(3), DraftRoomOperation::Spectate).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(3), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::Spectating)
+        ));
+        assert_eq!(room.players.len(), 2);
+        execute_as(&mut room, owner(3), DraftRoomOperation::LeaveRoom).unwrap();
+        assert!(room.spectators.is_empty());
+    }
+
+    #[test]
+    fn test_creator_can_kick_player_while_waiting() {
+        let mut room = create_room(3, owner(1));
+        for player in [owner(1), owner(2), owner(3)] {
+            execute_as(&mut room, player, DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+        let kick = |player| DraftRoomOperation::KickPlayer { player };
+
+        assert!(matches!(execute_as(&mut room, owner(2), kick(owner(3))), Err(DraftRoomError::NotCreator)));
+        assert!(matches!(execute_as(&mut room, owner(1), kick(owner(1))), Err(DraftRoomError::CannotKickCreator)));
+        assert!(matches!(execute_as(&mut room, owner(1), kick(owner(4))), Err(DraftRoomError::NotAPlayer)));
+
+        execute_as(&mut room, owner(1), kick(owner(2))).unwrap();
+        assert_eq!(room.players, vec![owner(1), owner(3)]);
+        assert_eq!(room.storage.picks.get(&owner(2)).blocking_wait().unwrap(), None);
+
+        // The freed seat can be taken again
+        execute_as(&mut room, owner(4), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert!(matches!(execute_as(&mut room, owner(1), kick(owner(3))), Err(DraftRoomError::NotWaiting)));
+    }
+
+    #[test]
+    fn test_private_room_requires_password_to_join() {
+        let mut room = create_room(3, owner(1));
+        room.password_hash = Some(password_hash("hunter2"));
+        let join = |password: Option<&str>| DraftRoomOperation::JoinRoom { password: password.map(str::to_string) };
+
+        assert!(matches!(execute_as(&mut room, owner(1), join(None)), Err(DraftRoomError::WrongPassword)));
+        assert!(matches!(execute_as(&mut room, owner(1), join(Some("hunter3"))), Err(DraftRoomError::WrongPassword)));
+        assert!(room.players.is_empty());
+
+        execute_as(&mut room, owner(1), join(Some("hunter2"))).unwrap();
+        assert_eq!(room.players, vec![owner(1)]);
+
+        // Open rooms ignore any password given
+        let mut open = create_room(2, owner(1));
+        execute_as(&mut open, owner(1), join(Some("anything"))).unwrap();
+        execute_as(&mut open, owner(2), join(None)).unwrap();
+    }
+
+    #[test]
+    fn test_item_names_reject_control_characters_and_overlong_names() {
+        assert!(validate_item_name(1, "Lightning Bolt").is_ok());
+        assert!(validate_item_name(1, &"x".repeat(MAX_ITEM_NAME_LEN)).is_ok());
+
+        for name in ["Lightning\nBolt", "Bolt\r", "Tab\there", "Bell\u{7}", "", "   "] {
+            assert!(matches!(validate_item_name(1, name), Err(DraftRoomError::InvalidPool(_))), "{:?}", name);
+        }
+        assert!(validate_item_name(1, &"x".repeat(MAX_ITEM_NAME_LEN + 1)).is_err());
+
+        // Every pool-setup path goes through the same check
+        let mut room = create_room(2, owner(1));
+        let newline = DraftItem::new(1, "Black\nLotus", 100);
+        assert!(execute_as(&mut room, owner(1), DraftRoomOperation::AddPoolItem { item: newline.clone() }).is_err());
+        assert!(!validate_pool(&[newline]).is_empty());
+
+        room.staged_pool = vec![DraftItem::new(1, "Black Lotus", 100)];
+        let rename = DraftRoomOperation::UpdatePoolItem { id: 1, name: Some("Black\nLotus".to_string()), power: None };
+        assert!(execute_as(&mut room, owner(1), rename).is_err());
+        assert_eq!(room.staged_pool[0].name, "Black Lotus");
+    }
+
+    #[test]
+    fn test_shuffle_players_is_deterministic_permutation() {
+        let players: Vec<Owner> = (1..=6).map(owner).collect();
+        let seed = seat_order_seed(&ChainId::root(0), Timestamp::from(1_000_000));
+
+        let shuffled = shuffle_players(&players, seed);
+        assert_eq!(shuffled, shuffle_players(&players, seed));
+        let mut sorted = shuffled.clone();
+        sorted.sort();
+        assert_eq!(sorted, players);
+
+        assert_eq!(seed, seat_order_seed(&ChainId::root(0), Timestamp::from(1_000_000)));
+        assert_ne!(seed, seat_order_seed(&ChainId::root(1), Timestamp::from(1_000_000)));
+        assert_ne!(seed, seat_order_seed(&ChainId::root(0), Timestamp::from(1_000_001)));
+    }
+
+    #[test]
+    fn test_start_draft_shuffles_seats_unless_order_set() {
+        let mut room = create_room(4, owner(1));
+        room.runtime.set_chain_id(ChainId::root(0));
+        room.runtime.set_system_time(Timestamp::from(1_000_000));
+        for n in 1..=4 {
+            execute_as(&mut room, owner(n), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+        let joined = room.players.clone();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: true }).unwrap();
+        let seed = seat_order_seed(&ChainId::root(0), Timestamp::from(1_000_000));
+        assert_eq!(room.players, shuffle_players(&joined, seed));
+
+        let mut room = create_room(2, owner(1));
+        room.runtime.set_chain_id(ChainId::root(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(1), DraftRoomOperation::SetTurnOrder { order: vec![owner(2), owner(1)] }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: true }).unwrap();
+        assert_eq!(room.players, vec![owner(2), owner(1)]);
+    }
+
+    #[test]
+    fn test_undo_pick_restores_item_and_turn() {
+        let mut room = drafting_room();
+        let pool_before = room.pool.clone();
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        // owner(2) closes round one and opens round two, crossing the snake turn
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 2 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 3 }).unwrap();
+        assert_eq!((room.round, room.current_turn), (2, 1));
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::UndoPick),
+            Err(DraftRoomError::NothingToUndo)
+        ));
+        execute_as(&mut room, owner(2), DraftRoomOperation::UndoPick).unwrap();
+        assert_eq!((room.round, room.current_turn), (2, 0));
+        assert_eq!(room.get_current_player(), Some(&owner(2)));
+        assert_eq!(room.pick_history.len(), 2);
+        let picks = room.storage.picks.get(&owner(2)).blocking_wait().unwrap().unwrap();
+        assert_eq!(picks.iter().map(|item| item.id).collect::<Vec<_>>(), vec![2]);
+
+        // One step only: the pick before was owner(2)'s too, but it stands
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::UndoPick),
+            Err(DraftRoomError::NothingToUndo)
+        ));
+
+        // Undoing the last pick of round one hands the turn back across the round boundary
+        let mut boundary = drafting_room();
+        execute_as(&mut boundary, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut boundary, owner(2), DraftRoomOperation::PickItem { item_id: 2 }).unwrap();
+        assert_eq!((boundary.round, boundary.current_turn), (2, 0));
+        assert!(boundary.pool.iter().all(|item| item.id != 2));
+        execute_as(&mut boundary, owner(2), DraftRoomOperation::UndoPick).unwrap();
+        assert_eq!((boundary.round, boundary.current_turn), (1, 1));
+        assert_eq!(boundary.get_current_player(), Some(&owner(2)));
+        assert_eq!(boundary.pool.iter().find(|item| item.id == 2).map(|item| item.quantity), Some(1));
+
+        let mut fresh = drafting_room();
+        execute_as(&mut fresh, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut fresh, owner(1), DraftRoomOperation::UndoPick).unwrap();
+        assert_eq!(fresh.pool, pool_before);
+        assert_eq!((fresh.round, fresh.current_turn), (1, 0));
+    }
+
+    #[test]
+    fn test_undo_pick_restores_the_previous_clock() {
+        let mut room = create_room(2, owner(1));
+        room.turn_duration_secs = Some(60);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        room.runtime.set_system_time(Timestamp::from(50_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.turn_deadline, Some(Timestamp::from(110_000_000)));
+
+        // Undoing hands back the turn as it was, not a fresh 60 seconds
+        room.runtime.set_system_time(Timestamp::from(55_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::UndoPick).unwrap();
+        assert_eq!(room.turn_started_at, Some(Timestamp::from(0)));
+        assert_eq!(room.turn_deadline, Some(Timestamp::from(60_000_000)));
+
+        // Repeating pick and undo does not keep the turn alive
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        execute_as(&mut room, owner(1), DraftRoomOperation::UndoPick).unwrap();
+        room.runtime.set_system_time(Timestamp::from(61_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::ForcePick).unwrap();
+        assert_eq!(room.pick_history.len(), 1);
+    }
+
+    #[test]
+    fn test_final_pick_cannot_be_undone() {
+        let mut room = drafting_room();
+        // Snake order over three rounds: 1, 2 | 2, 1 | 1, 2
+        for (player, item_id) in [(1, 1), (2, 2), (2, 3), (1, 4), (1, 5), (2, 6)] {
+            execute_as(&mut room, owner(player), DraftRoomOperation::PickItem { item_id }).unwrap();
+        }
+        assert_eq!(room.status, DraftStatus::Finished);
+
+        assert!(matches!(
+            execute_as(&mut room, owner(2), DraftRoomOperation::UndoPick),
+            Err(DraftRoomError::NotDrafting)
+        ));
+        assert_eq!(room.status, DraftStatus::Finished);
+        assert_eq!(room.pick_history.len(), 6);
+    }
+
+    #[test]
+    fn test_min_pick_interval_rejects_instant_pick() {
+        let mut room = create_room(2, owner(1));
+        room.min_pick_interval_secs = Some(5);
+        room.runtime.set_system_time(Timestamp::from(0));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::PickingTooFast)
+        ));
+        assert!(room.pick_history.is_empty());
+
+        room.runtime.set_system_time(Timestamp::from(5_000_000));
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.pick_history.len(), 1);
+    }
+
+    #[test]
+    fn test_start_draft_needs_two_players_including_creator() {
+        let mut room = create_room(3, owner(1));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::NotEnoughPlayers)
+        ));
+
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::NotEnoughPlayers)
+        ));
+
+        execute_as(&mut room, owner(3), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::CreatorNotJoined)
+        ));
+
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.status, DraftStatus::Drafting);
+    }
+
+    #[test]
+    fn test_start_draft_waits_for_every_player_ready() {
+        let mut room = create_room(2, owner(1));
+        execute_as(&mut room, owner(1), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(3), DraftRoomOperation::SetReady { ready: true }),
+            Err(DraftRoomError::NotAPlayer)
+        ));
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::SetReady { ready: true }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::PlayersNotReady)
+        ));
+
+        // Un-readying counts as not ready
+        execute_as(&mut room, owner(2), DraftRoomOperation::SetReady { ready: true }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::SetReady { ready: false }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }),
+            Err(DraftRoomError::PlayersNotReady)
+        ));
+
+        execute_as(&mut room, owner(2), DraftRoomOperation::SetReady { ready: true }).unwrap();
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::SetReady { ready: false }),
+            Err(DraftRoomError::NotWaiting)
+        ));
+    }
+
+    #[test]
+    fn test_forced_auto_picks_play_out_whole_draft() {
+        // simulateDraft plays a room out with nothing but the creator's forced picks
+        let mut room = drafting_room();
+        let total_picks = room.players.len() * room.max_rounds as usize;
+        for _ in 0..total_picks {
+            execute_as(&mut room, owner(1), DraftRoomOperation::ForceAutoPick).unwrap();
+        }
+        assert_eq!(room.status, DraftStatus::Finished);
+        assert_eq!(room.pick_history.len(), total_picks);
+
+        execute_as(&mut room, owner(1), DraftRoomOperation::FinalizeDraft).unwrap();
+        assert_eq!(room.standings.len(), 2);
+    }
+
+    #[test]
+    fn test_captains_draft_squads_then_items() {
+        let runtime = ContractRuntime::new().with_application_parameters(ContractParameters::DraftRoom {
+            max_players: 4,
+            options: RoomOptions { captains: vec![owner(1), owner(2)], ..RoomOptions::default() },
+            creator: owner(1),
+            lobby: None,
+        });
+        let mut room = DraftRoom::load(runtime).blocking_wait();
+        for n in 1..=4 {
+            execute_as(&mut room, owner(n), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert_eq!(room.status, DraftStatus::CaptainPicking);
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }),
+            Err(DraftRoomError::NotDrafting)
+        ));
+
+        let pick = |player| DraftRoomOperation::DraftCaptainPick { player };
+        assert!(matches!(execute_as(&mut room, owner(2), pick(owner(3))), Err(DraftRoomError::NotYourTurn)));
+        assert!(matches!(execute_as(&mut room, owner(1), pick(owner(2))), Err(DraftRoomError::AlreadyInSquad)));
+        execute_as(&mut room, owner(1), pick(owner(4))).unwrap();
+        assert!(matches!(execute_as(&mut room, owner(2), pick(owner(4))), Err(DraftRoomError::AlreadyInSquad)));
+        execute_as(&mut room, owner(2), pick(owner(3))).unwrap();
+
+        assert_eq!(room.squads().blocking_wait(), vec![vec![owner(4)], vec![owner(3)]]);
+        assert_eq!(room.status, DraftStatus::Drafting);
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 1 }).unwrap();
+        assert_eq!(room.get_current_player(), Some(&owner(2)));
+    }
+
+    #[test]
+    fn test_validate_captains() {
+        assert!(validate_captains(&[]).is_ok());
+        assert!(validate_captains(&[owner(1), owner(2)]).is_ok());
+        assert!(matches!(validate_captains(&[owner(1)]), Err(DraftRoomError::InvalidCaptains(_))));
+        assert!(matches!(validate_captains(&[owner(1), owner(1)]), Err(DraftRoomError::InvalidCaptains(_))));
+    }
+
+    #[test]
+    fn test_concede_finalizes_with_opponent_as_winner() {
+        let mut room = drafting_room();
+        execute_as(&mut room, owner(1), DraftRoomOperation::PickItem { item_id: 5 }).unwrap();
+        execute_as(&mut room, owner(2), DraftRoomOperation::PickItem { item_id: 7 }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::Concede),
+            Err(DraftRoomError::NotYourTurn)
+        ));
+
+        // owner(2) is ahead on power but concedes, so owner(1) wins and ranks first
+        execute_as(&mut room, owner(2), DraftRoomOperation::Concede).unwrap();
+        assert_eq!(room.status, DraftStatus::Finished);
+        assert_eq!(room.conceded_by, Some(owner(2)));
+        assert_eq!(room.winner, Some(owner(1)));
+        assert_eq!(room.standings[0].player, owner(1));
+        assert!(room.turn_started_at.is_none());
+
+        // Finalizing again keeps the concession result
+        execute_as(&mut room, owner(1), DraftRoomOperation::FinalizeDraft).unwrap();
+        assert_eq!(room.winner, Some(owner(1)));
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::Concede),
+            Err(DraftRoomError::NotDrafting)
+        ));
+    }
+
+    #[test]
+    fn test_concede_needs_two_players() {
+        let mut room = create_room(3, owner(1));
+        for n in 1..=3 {
+            execute_as(&mut room, owner(n), DraftRoomOperation::JoinRoom { password: None }).unwrap();
+        }
+        ready_all(&mut room);
+        execute_as(&mut room, owner(1), DraftRoomOperation::StartDraft { shuffle: false }).unwrap();
+        assert!(matches!(
+            execute_as(&mut room, owner(1), DraftRoomOperation::Concede),
+            Err(DraftRoomError::ConcedeNeedsTwoPlayers)
+        ));
+    }
+}
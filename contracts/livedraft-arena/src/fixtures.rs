@@ -0,0 +1,137 @@
+//! Deterministic DraftRoom state for tests
+//!
+//! Builds a fully-populated room and serializes it in the shape the service
+//! reads from DraftRoom chains, so off-chain parsing can be tested against
+//! real contract types instead of hand-written JSON. Enabled in this crate's
+//! tests and, for other crates, by the `test-fixtures` feature.
+
+use std::str::FromStr;
+
+use linera_sdk::base::Owner;
+use serde::Serialize;
+
+use crate::draft_room::DraftRoomCheckpoint;
+use crate::{DraftItem, DraftRoom, DraftRoomStatus, PickRecord, RoomOptions};
+
+/// Deterministic owner `n`, matching the owners used throughout the tests
+pub fn fixture_owner(n: u8) -> Owner {
+    Owner::from_str(&format!("{:064x}", n)).expect("Fixture owner is valid")
+}
+
+/// A DraftRoom's plain state and options, serialized as the chain reports them
+#[derive(Debug, Clone, Serialize)]
+pub struct DraftRoomFixture {
+    #[serde(flatten)]
+    pub checkpoint: DraftRoomCheckpoint,
+    #[serde(flatten)]
+    pub options: RoomOptions,
+}
+
+/// Wraps a fixture the way `LiveDraftArena::DraftRoom` is tagged on the wire
+#[derive(Serialize)]
+enum FixtureState<'a> {
+    DraftRoom(&'a DraftRoomFixture),
+}
+
+impl DraftRoomFixture {
+    /// A two-player draft in round 2 with one round of picks made
+    ///
+    /// `fixture_owner(1)` created the room and picked Lightning Bolt;
+    /// `fixture_owner(2)` picked Black Lotus and is next to pick.
+    pub fn mid_draft() -> Self {
+        let (creator, other) = (fixture_owner(1), fixture_owner(2));
+        let mut pool = DraftRoom::initialize_pool();
+        let picked: Vec<DraftItem> = [1, 7]
+            .iter()
+            .map(|id| pool.iter().find(|item| item.id == *id).cloned().expect("Picked item is in the default pool"))
+            .collect();
+        pool.retain(|item| !picked.contains(item));
+
+        DraftRoomFixture {
+            checkpoint: DraftRoomCheckpoint {
+                players: vec![creator, other],
+                max_players: 4,
+                current_turn: 0,
+                round: 2,
+                max_rounds: 3,
+                pool,
+                staged_pool: Vec::new(),
+                pick_history: vec![
+                    PickRecord { player: creator, item: picked[0].clone(), round: 1 },
+                    PickRecord { player: other, item: picked[1].clone(), round: 1 },
+                ],
+                status: DraftRoomStatus::Drafting,
+                creator: Some(creator),
+                turn_order: None,
+                reserved: Vec::new(),
+                standings: Vec::new(),
+                winner: None,
+                spectators: vec![fixture_owner(3)],
+                turn_deadline: None,
+                turn_started_at: None,
+                time_requests_used: [(other, 1)].into_iter().collect(),
+                bans: Vec::new(),
+                pool_seed: None,
+                seed_commitment: None,
+                last_picker: Some(other),
+                conceded_by: None,
+                version: 7,
+            },
+            options: RoomOptions {
+                reveal_after_round: true,
+                final_round_min_power: Some(80),
+                round_weights: vec![1, 2, 3],
+                turn_duration_secs: Some(30),
+                budget: None,
+                max_spectators: 10,
+                preview: false,
+                ban_rounds: 0,
+                pool_size: None,
+                pool_seed: None,
+                seed_commitment: None,
+                custom_pool: Vec::new(),
+                handicap_first_pick: false,
+                password_hash: None,
+                min_pick_interval_secs: None,
+                captains: Vec::new(),
+                auto_close_after_secs: None,
+            },
+        }
+    }
+
+    /// Replace the room options
+    pub fn with_options(mut self, options: RoomOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Replace the room status
+    pub fn with_status(mut self, status: DraftRoomStatus) -> Self {
+        self.checkpoint.status = status;
+        self
+    }
+
+    /// The state as a DraftRoom chain returns it from a state query
+    pub fn state_json(&self) -> String {
+        serde_json::to_string(&FixtureState::DraftRoom(self)).expect("Fixture serializes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_is_deterministic_and_tagged() {
+        let json = DraftRoomFixture::mid_draft().state_json();
+        assert_eq!(json, DraftRoomFixture::mid_draft().state_json());
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let room = &value["DraftRoom"];
+        assert_eq!(room["status"], "Drafting");
+        assert_eq!(room["round"], 2);
+        assert_eq!(room["players"][1], fixture_owner(2).to_string());
+        assert_eq!(room["pool"].as_array().unwrap().len(), 13);
+        assert_eq!(room["turn_duration_secs"], 30);
+    }
+}
@@ -1,45 +1,633 @@
 use linera_sdk::{
-    base::{ChainId, WithContractAbi, ContractAbi},
-    views::{MapView, RootView, View},
+    base::{ChainId, ContractAbi, Owner, TimeDelta, Timestamp, WithContractAbi},
+    views::{linera_views, MapView, RootView, View, ViewStorageContext},
     Contract, ContractRuntime,
 };
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+pub mod draft_room;
 pub mod service;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
 
-/// Draft room status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub use draft_room::{
+    DraftItem, DraftRoom, DraftRoomError, DraftRoomMessage, DraftRoomOperation,
+    DraftStatus as DraftRoomStatus, PickRecord, Standing, MAX_ROOM_CAPACITY, MIN_PLAYERS_TO_START,
+    password_hash, seed_commitment,
+};
+
+/// Draft room status (for lobby metadata)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum RoomStatus {
     Waiting,
     Drafting,
     Finished,
 }
 
+impl From<DraftRoomStatus> for RoomStatus {
+    fn from(status: DraftRoomStatus) -> Self {
+        match status {
+            DraftRoomStatus::Waiting => RoomStatus::Waiting,
+            // The lobby treats captain picks and the ban phase as part of the draft
+            DraftRoomStatus::CaptainPicking | DraftRoomStatus::Banning | DraftRoomStatus::Drafting => RoomStatus::Drafting,
+            DraftRoomStatus::Finished => RoomStatus::Finished,
+        }
+    }
+}
+
 /// Metadata for a draft room
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DraftRoomMetadata {
     pub room_name: String,
     pub max_players: u8,
     pub status: RoomStatus,
+    /// Block time the room was created; the epoch for rooms created before this was recorded
+    #[serde(default)]
+    pub created_at: Timestamp,
+    /// Seated players as last reported by the room
+    #[serde(default)]
+    pub current_players: u8,
+    /// Owner who created the room; `None` for rooms created before this was recorded
+    #[serde(default)]
+    pub creator: Option<Owner>,
+    /// Joining needs a password; the hash itself is kept only by the room
+    #[serde(default)]
+    pub is_private: bool,
 }
 
-/// Operations
+/// A finalized room's outcome, kept by the Lobby even after the room is cleaned up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomResult {
+    pub room_name: String,
+    pub winner: Option<Owner>,
+    /// Block time the Lobby learned the draft was finalized
+    pub finalized_at: Timestamp,
+}
+
+/// Spectator cap for rooms that don't choose one
+pub const DEFAULT_MAX_SPECTATORS: u8 = 20;
+
+fn default_max_spectators() -> u8 {
+    DEFAULT_MAX_SPECTATORS
+}
+
+/// Per-room options chosen by the creator at `CreateRoom`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoomOptions {
+    /// Hide other players' picks from the current round until the round completes
+    pub reveal_after_round: bool,
+    /// In the final round, picks must reach this power while any remaining item does
+    pub final_round_min_power: Option<u32>,
+    /// Score multiplier per round (index 0 is round 1); missing rounds weigh 1
+    #[serde(default)]
+    pub round_weights: Vec<u32>,
+    /// Seconds each player has per pick; sets each turn's deadline
+    #[serde(default)]
+    pub turn_duration_secs: Option<u64>,
+    /// Salary cap per player; picks spend the item's `cost` from it
+    #[serde(default)]
+    pub budget: Option<u32>,
+    /// Most spectators the room accepts
+    #[serde(default = "default_max_spectators")]
+    pub max_spectators: u8,
+    /// Rehearsal mode: picks are validated and take turns but keep nothing
+    #[serde(default)]
+    pub preview: bool,
+    /// Rounds of bans before picking starts; each player bans once per round
+    #[serde(default)]
+    pub ban_rounds: u8,
+    /// Draw this many items from the candidate pool at `StartDraft`, weighted by item weight
+    #[serde(default)]
+    pub pool_size: Option<u8>,
+    /// Seed for pool sampling; derived from the block time at `StartDraft` when unset
+    #[serde(default)]
+    pub pool_seed: Option<u64>,
+    /// Hex SHA-256 of a secret pool seed, revealed with `RevealSeed` before `StartDraft`
+    #[serde(default)]
+    pub seed_commitment: Option<String>,
+    /// The room's own draftable items; the Wave-5 cards when empty
+    #[serde(default)]
+    pub custom_pool: Vec<DraftItem>,
+    /// Bar the very first pick from taking the single highest-power item
+    #[serde(default)]
+    pub handicap_first_pick: bool,
+    /// Hex SHA-256 of the password players must give to `JoinRoom`; see `password_hash`
+    #[serde(default)]
+    pub password_hash: Option<String>,
+    /// Seconds a turn must run before its player may pick, to keep drafts human-paced
+    #[serde(default)]
+    pub min_pick_interval_secs: Option<u64>,
+    /// Two players who draft the others into squads before the item draft; a plain draft when empty
+    #[serde(default)]
+    pub captains: Vec<Owner>,
+    /// Seconds after finalizing before `CleanupFinished` may remove the room from the Lobby; kept listed if unset
+    #[serde(default)]
+    pub auto_close_after_secs: Option<u64>,
+}
+
+impl Default for RoomOptions {
+    fn default() -> Self {
+        RoomOptions {
+            reveal_after_round: false,
+            final_round_min_power: None,
+            round_weights: Vec::new(),
+            turn_duration_secs: None,
+            budget: None,
+            max_spectators: DEFAULT_MAX_SPECTATORS,
+            preview: false,
+            ban_rounds: 0,
+            pool_size: None,
+            pool_seed: None,
+            seed_commitment: None,
+            custom_pool: Vec::new(),
+            handicap_first_pick: false,
+            password_hash: None,
+            min_pick_interval_secs: None,
+            captains: Vec::new(),
+            auto_close_after_secs: None,
+        }
+    }
+}
+
+/// Parameters to determine contract type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ContractParameters {
+    Lobby,
+    DraftRoom {
+        max_players: u8,
+        options: RoomOptions,
+        /// The signer of the `CreateRoom` that opened the room
+        creator: Owner,
+        /// The Lobby chain that opened the room
+        #[serde(default)]
+        lobby: Option<ChainId>,
+    },
+}
+
+/// Operations for Lobby
 #[derive(Debug, Deserialize, Serialize)]
-pub enum Operation {
+pub enum LobbyOperation {
     CreateRoom {
         room_name: String,
         max_players: u8,
+        options: RoomOptions,
     },
+    /// Ask a room's chain for its current status to repair drifted metadata
+    RefreshRoom { chain_id: ChainId },
+    /// Delist finalized rooms whose `auto_close_after_secs` has passed; their results are kept
+    CleanupFinished,
+    /// Fix a room's name; creator only, and only while the room is waiting
+    RenameRoom { chain_id: ChainId, new_name: String },
 }
 
-/// Messages
-#[derive(Debug, Deserialize, Serialize)]
-pub enum Message {}
+/// Check a room name submitted for creation or renaming
+fn validate_room_name(name: &str) -> Result<(), LobbyError> {
+    if name.trim().is_empty() {
+        return Err(LobbyError::EmptyRoomName);
+    }
+    Ok(())
+}
 
-/// Application state
+/// On-chain storage for the Lobby
 #[derive(RootView)]
-pub struct LiveDraftArena {
+#[view(context = "ViewStorageContext")]
+pub struct LobbyStorage {
     pub rooms: MapView<ChainId, DraftRoomMetadata>,
+    /// Times each item name has been picked across finalized drafts
+    pub item_popularity: MapView<String, u32>,
+    /// The auto-close delay of rooms created with one
+    pub auto_close_after_secs: MapView<ChainId, u64>,
+    /// When each finalized room with an auto-close delay may be delisted
+    pub closes_at: MapView<ChainId, Timestamp>,
+    /// Outcome of every finalized room, including rooms no longer listed
+    pub results: MapView<ChainId, RoomResult>,
+}
+
+/// The Lobby application state.
+pub struct Lobby {
+    pub storage: LobbyStorage,
+    runtime: ContractRuntime<LiveDraftArena>,
+}
+
+impl Lobby {
+    async fn load(runtime: ContractRuntime<LiveDraftArena>) -> Self {
+        Lobby {
+            storage: LobbyStorage::load(runtime.root_view_storage_context())
+                .await
+                .expect("Failed to load lobby state"),
+            runtime,
+        }
+    }
+
+    async fn store(mut self) {
+        self.storage.save().await.expect("Failed to save lobby state");
+    }
+
+    async fn instantiate(&mut self, _argument: ()) {
+        // Lobby starts with no rooms
+    }
+
+    /// Application parameters for a room `creator` opens from this Lobby
+    fn room_parameters(&mut self, max_players: u8, options: RoomOptions, creator: Owner) -> ContractParameters {
+        ContractParameters::DraftRoom {
+            max_players,
+            options,
+            creator,
+            lobby: Some(self.runtime.chain_id()),
+        }
+    }
+
+    async fn execute_operation(&mut self, operation: LobbyOperation) -> Result<(), LobbyError> {
+        match operation {
+            LobbyOperation::CreateRoom {
+                room_name,
+                max_players,
+                options,
+            } => {
+                // Validate input
+                validate_room_name(&room_name)?;
+                if !(MIN_PLAYERS_TO_START..=MAX_ROOM_CAPACITY).contains(&max_players) {
+                    return Err(LobbyError::InvalidMaxPlayers);
+                }
+                if !options.custom_pool.is_empty() {
+                    if let Some(error) = draft_room::validate_pool(&options.custom_pool).into_iter().next() {
+                        return Err(LobbyError::InvalidPool(error));
+                    }
+                }
+                let candidates = if options.custom_pool.is_empty() {
+                    DraftRoom::initialize_pool()
+                } else {
+                    options.custom_pool.clone()
+                };
+                draft_room::validate_draft_config(max_players, draft_room::DEFAULT_MAX_ROUNDS, options.ban_rounds, &candidates)
+                    .map_err(LobbyError::InvalidDraftConfig)?;
+                draft_room::validate_captains(&options.captains).map_err(LobbyError::InvalidDraftConfig)?;
+
+                let is_private = options.password_hash.is_some();
+                let auto_close_after_secs = options.auto_close_after_secs;
+
+                // Require authenticated signer
+                let creator = self
+                    .runtime
+                    .authenticated_signer()
+                    .ok_or(LobbyError::AuthenticationRequired)?;
+
+                // Open new microchain for the draft room
+                let chain_id = self
+                    .runtime
+                    .open_chain(
+                        self.runtime.application_id(),
+                        self.room_parameters(max_players, options, creator),
+                    )
+                    .await
+                    .expect("Failed to open new chain");
+
+                // Store room metadata
+                let metadata = DraftRoomMetadata {
+                    room_name,
+                    max_players,
+                    status: RoomStatus::Waiting,
+                    created_at: self.runtime.system_time(),
+                    current_players: 0,
+                    creator: Some(creator),
+                    is_private,
+                };
+
+                self.storage
+                    .rooms
+                    .insert(&chain_id, metadata)
+                    .expect("Failed to store room metadata");
+                if let Some(secs) = auto_close_after_secs {
+                    self.storage
+                        .auto_close_after_secs
+                        .insert(&chain_id, secs)
+                        .expect("Failed to store room auto-close delay");
+                }
+
+                Ok(())
+            }
+
+            LobbyOperation::CleanupFinished => {
+                self.runtime
+                    .authenticated_signer()
+                    .ok_or(LobbyError::AuthenticationRequired)?;
+
+                let now = self.runtime.system_time();
+                let closing = self.storage.closes_at.indices().await.expect("Failed to list room close times");
+                for chain_id in closing {
+                    let closes_at = self
+                        .storage
+                        .closes_at
+                        .get(&chain_id)
+                        .await
+                        .expect("Failed to read room close time");
+                    if closes_at.is_some_and(|closes_at| closes_at <= now) {
+                        self.storage.rooms.remove(&chain_id).expect("Failed to remove room metadata");
+                        self.storage.closes_at.remove(&chain_id).expect("Failed to remove room close time");
+                        self.storage
+                            .auto_close_after_secs
+                            .remove(&chain_id)
+                            .expect("Failed to remove room auto-close delay");
+                    }
+                }
+                Ok(())
+            }
+
+            LobbyOperation::RenameRoom { chain_id, new_name } => {
+                validate_room_name(&new_name)?;
+                let signer = self
+                    .runtime
+                    .authenticated_signer()
+                    .ok_or(LobbyError::AuthenticationRequired)?;
+
+                let mut metadata = self
+                    .storage
+                    .rooms
+                    .get(&chain_id)
+                    .await
+                    .expect("Failed to read room metadata")
+                    .ok_or(LobbyError::RoomNotFound(chain_id))?;
+                if metadata.creator != Some(signer) {
+                    return Err(LobbyError::NotCreator);
+                }
+                // Names are locked once play starts so standings and shared links stay stable
+                if !matches!(metadata.status, RoomStatus::Waiting) {
+                    return Err(LobbyError::RoomLocked);
+                }
+
+                metadata.room_name = new_name;
+                self.storage
+                    .rooms
+                    .insert(&chain_id, metadata)
+                    .expect("Failed to store room metadata");
+                Ok(())
+            }
+
+            LobbyOperation::RefreshRoom { chain_id } => {
+                self.runtime
+                    .authenticated_signer()
+                    .ok_or(LobbyError::AuthenticationRequired)?;
+
+                let known = self
+                    .storage
+                    .rooms
+                    .contains_key(&chain_id)
+                    .await
+                    .expect("Failed to read room metadata");
+                if !known {
+                    return Err(LobbyError::RoomNotFound(chain_id));
+                }
+
+                // The room answers with `Message::RoomStatusReport`
+                self.runtime
+                    .prepare_message(Message::RoomStatusRequest)
+                    .send_to(chain_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Overwrite a room's status and player count with what the room reported
+    ///
+    /// Reports from chains the Lobby never opened are ignored.
+    async fn apply_room_report(&mut self, origin: ChainId, status: DraftRoomStatus, player_count: u8) {
+        let Some(mut metadata) = self
+            .storage
+            .rooms
+            .get(&origin)
+            .await
+            .expect("Failed to read room metadata")
+        else {
+            return;
+        };
+
+        metadata.status = status.into();
+        metadata.current_players = player_count;
+        self.storage
+            .rooms
+            .insert(&origin, metadata)
+            .expect("Failed to store room metadata");
+    }
+
+    /// Record a room's new capacity, as announced by the room itself
+    async fn apply_max_players(&mut self, origin: ChainId, max_players: u8) {
+        let Some(mut metadata) = self
+            .storage
+            .rooms
+            .get(&origin)
+            .await
+            .expect("Failed to read room metadata")
+        else {
+            return;
+        };
+
+        metadata.max_players = max_players;
+        self.storage
+            .rooms
+            .insert(&origin, metadata)
+            .expect("Failed to store room metadata");
+    }
+
+    /// Record a room's seated player count, as announced by the room itself
+    async fn apply_player_count(&mut self, origin: ChainId, count: u8) {
+        let Some(mut metadata) = self
+            .storage
+            .rooms
+            .get(&origin)
+            .await
+            .expect("Failed to read room metadata")
+        else {
+            return;
+        };
+
+        metadata.current_players = count;
+        self.storage
+            .rooms
+            .insert(&origin, metadata)
+            .expect("Failed to store room metadata");
+    }
+
+    /// Record a room's new status, as announced by the room itself
+    async fn apply_status(&mut self, origin: ChainId, status: DraftRoomStatus) {
+        let Some(mut metadata) = self
+            .storage
+            .rooms
+            .get(&origin)
+            .await
+            .expect("Failed to read room metadata")
+        else {
+            return;
+        };
+
+        metadata.status = status.into();
+        self.storage
+            .rooms
+            .insert(&origin, metadata)
+            .expect("Failed to store room metadata");
+    }
+
+    /// Record a finalized room's result and count its picks toward each item's popularity
+    ///
+    /// Only rooms listed in this Lobby are counted. Rooms created with an
+    /// auto-close delay become due for `CleanupFinished` once it has passed.
+    async fn apply_draft_finalized(&mut self, origin: ChainId, item_names: Vec<String>, winner: Option<Owner>) {
+        let Some(metadata) = self
+            .storage
+            .rooms
+            .get(&origin)
+            .await
+            .expect("Failed to read room metadata")
+        else {
+            return;
+        };
+
+        let finalized_at = self.runtime.system_time();
+        let result = RoomResult { room_name: metadata.room_name, winner, finalized_at };
+        self.storage.results.insert(&origin, result).expect("Failed to store room result");
+
+        let auto_close_after_secs = self
+            .storage
+            .auto_close_after_secs
+            .get(&origin)
+            .await
+            .expect("Failed to read room auto-close delay");
+        if let Some(secs) = auto_close_after_secs {
+            let closes_at = finalized_at.saturating_add(TimeDelta::from_secs(secs));
+            self.storage.closes_at.insert(&origin, closes_at).expect("Failed to store room close time");
+        }
+
+        for name in item_names {
+            let count = self
+                .storage
+                .item_popularity
+                .get(&name)
+                .await
+                .expect("Failed to read item popularity")
+                .unwrap_or(0);
+            self.storage
+                .item_popularity
+                .insert(&name, count.saturating_add(1))
+                .expect("Failed to store item popularity");
+        }
+    }
+
+    async fn execute_message(&mut self, message: Message) {
+        let Some(origin) = self.runtime.message_id().map(|id| id.chain_id) else {
+            return;
+        };
+        match message {
+            Message::RoomStatusReport { status, player_count } => {
+                self.apply_room_report(origin, status, player_count).await
+            }
+            Message::MaxPlayersChanged { max_players } => self.apply_max_players(origin, max_players).await,
+            Message::PlayerCountChanged { count } => self.apply_player_count(origin, count).await,
+            Message::StatusChanged { status } => self.apply_status(origin, status).await,
+            Message::DraftFinalized { item_names, winner } => {
+                self.apply_draft_finalized(origin, item_names, winner).await
+            }
+            Message::RoomStatusRequest => {}
+        }
+    }
+}
+
+/// Unified operations
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Operation {
+    // Lobby operations
+    CreateRoom {
+        room_name: String,
+        max_players: u8,
+        options: RoomOptions,
+    },
+    // DraftRoom operations
+    JoinRoom { password: Option<String> },
+    LeaveRoom,
+    KickPlayer { player: Owner },
+    StartDraft { shuffle: bool },
+    PickItem { item_id: u8 },
+    UndoPick,
+    FinalizeDraft,
+    UpdatePoolItem {
+        id: u8,
+        name: Option<String>,
+        power: Option<u32>,
+    },
+    AddPoolItem { item: DraftItem },
+    RemovePoolItem { id: u8 },
+    SetTurnOrder { order: Vec<Owner> },
+    SetWishlist { item_ids: Vec<u8> },
+    ForceAutoPick,
+    ForcePick,
+    InviteAndReserve { owners: Vec<Owner> },
+    Spectate,
+    RequestMoreTime,
+    BanItem { item_id: u8 },
+    SetMaxPlayers { max_players: u8 },
+    RevealSeed { seed: u64 },
+    SuggestToPlayer { player: Owner, item_id: u8 },
+    SetReady { ready: bool },
+    DraftCaptainPick { player: Owner },
+    Concede,
+    // Lobby maintenance
+    RefreshRoom { chain_id: ChainId },
+    RenameRoom { chain_id: ChainId, new_name: String },
+    CleanupFinished,
+}
+
+/// Unified messages
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Message {
+    /// Lobby to DraftRoom: report your current status
+    RoomStatusRequest,
+    /// DraftRoom to Lobby: the room's status and seated player count
+    RoomStatusReport { status: DraftRoomStatus, player_count: u8 },
+    /// DraftRoom to Lobby: the creator changed the room's capacity
+    MaxPlayersChanged { max_players: u8 },
+    /// DraftRoom to Lobby: players joined or left the room
+    PlayerCountChanged { count: u8 },
+    /// DraftRoom to Lobby: the room's lobby-visible status changed
+    StatusChanged { status: DraftRoomStatus },
+    /// DraftRoom to Lobby: the draft was finalized with picks of these items, one entry per pick
+    DraftFinalized { item_names: Vec<String>, winner: Option<Owner> },
+}
+
+/// Errors that can occur during contract execution.
+#[derive(Debug, Error)]
+pub enum LobbyError {
+    #[error("Room name cannot be empty")]
+    EmptyRoomName,
+    #[error("Max players must be between {MIN_PLAYERS_TO_START} and {MAX_ROOM_CAPACITY}")]
+    InvalidMaxPlayers,
+    #[error("Authentication required")]
+    AuthenticationRequired,
+    #[error("Room {0} is not in the lobby")]
+    RoomNotFound(ChainId),
+    #[error("Only the room creator can do this")]
+    NotCreator,
+    #[error("Room name cannot change once the draft has started")]
+    RoomLocked,
+    #[error(transparent)]
+    InvalidPool(DraftRoomError),
+    #[error(transparent)]
+    InvalidDraftConfig(DraftRoomError),
+}
+
+/// Unified errors
+#[derive(Debug, Error)]
+pub enum LiveDraftArenaError {
+    #[error("Lobby error: {0}")]
+    Lobby(#[from] LobbyError),
+    #[error("DraftRoom error: {0}")]
+    DraftRoom(#[from] DraftRoomError),
+    #[error("Wrong chain type for this operation: {0} chains do not accept it")]
+    WrongContractType(&'static str),
+}
+
+/// Unified application state
+pub enum LiveDraftArena {
+    Lobby(Lobby),
+    DraftRoom(DraftRoom),
 }
 
 impl ContractAbi for LiveDraftArena {
@@ -53,54 +641,417 @@ impl WithContractAbi for LiveDraftArena {
 
 impl Contract for LiveDraftArena {
     type Message = Message;
-    type Parameters = ();
-    type InstantiationArgument = ();
+    type Parameters = ContractParameters;
+    type InstantiationArgument = (); // A DraftRoom's creator comes with its parameters
     type EventValue = ();
 
     async fn load(runtime: ContractRuntime<Self>) -> Self {
-        LiveDraftArena {
-            rooms: MapView::load(runtime.root_view_storage_context())
-                .await
-                .expect("Failed to load rooms"),
+        match runtime.application_parameters() {
+            ContractParameters::Lobby => {
+                let lobby = Lobby::load(runtime).await;
+                LiveDraftArena::Lobby(lobby)
+            }
+            ContractParameters::DraftRoom { .. } => {
+                let draft_room = DraftRoom::load(runtime).await;
+                LiveDraftArena::DraftRoom(draft_room)
+            }
         }
     }
 
-    async fn instantiate(&mut self, _argument: Self::InstantiationArgument) {
-        // Initialize with empty rooms
+    async fn instantiate(&mut self, argument: Self::InstantiationArgument) {
+        if let LiveDraftArena::Lobby(lobby) = self {
+            lobby.instantiate(argument).await;
+        }
     }
 
     async fn execute_operation(&mut self, operation: Operation) -> Vec<Self::Message> {
-        match operation {
-            Operation::CreateRoom { room_name, max_players } => {
-                // Validate input
-                if room_name.trim().is_empty() {
-                    return vec![];
-                }
-                if max_players < 2 || max_players > 8 {
-                    return vec![];
-                }
+        if let Err(error) = self.dispatch_operation(operation).await {
+            panic!("{}", error);
+        }
 
-                // Store room metadata
-                let metadata = DraftRoomMetadata {
-                    room_name,
-                    max_players,
-                    status: RoomStatus::Waiting,
-                };
+        vec![]
+    }
+
+    async fn execute_message(&mut self, message: Self::Message) {
+        match self {
+            LiveDraftArena::Lobby(lobby) => lobby.execute_message(message).await,
+            LiveDraftArena::DraftRoom(draft_room) => draft_room.execute_message(message),
+        }
+    }
 
-                // Use a dummy chain ID for now
-                let chain_id = ChainId::root(0);
-                let _ = self.rooms.insert(&chain_id, metadata);
+    async fn store(self) {
+        match self {
+            LiveDraftArena::Lobby(lobby) => lobby.store().await,
+            LiveDraftArena::DraftRoom(draft_room) => draft_room.store().await,
+        }
+    }
+}
 
-                vec![]
+impl LiveDraftArena {
+    /// Route an operation to the Lobby or DraftRoom it was sent to
+    ///
+    /// Operations meant for the other contract type are rejected rather than
+    /// ignored, so the sender learns it targeted the wrong chain.
+    async fn dispatch_operation(&mut self, operation: Operation) -> Result<(), LiveDraftArenaError> {
+        match (self, operation) {
+            (LiveDraftArena::Lobby(lobby), Operation::CreateRoom { room_name, max_players, options }) => {
+                lobby
+                    .execute_operation(LobbyOperation::CreateRoom { room_name, max_players, options })
+                    .await
+                    .map_err(Into::into)
+            }
+            (LiveDraftArena::Lobby(lobby), Operation::RefreshRoom { chain_id }) => {
+                lobby.execute_operation(LobbyOperation::RefreshRoom { chain_id }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::Lobby(lobby), Operation::RenameRoom { chain_id, new_name }) => {
+                lobby.execute_operation(LobbyOperation::RenameRoom { chain_id, new_name }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::Lobby(lobby), Operation::CleanupFinished) => {
+                lobby.execute_operation(LobbyOperation::CleanupFinished).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::JoinRoom { password }) => {
+                draft_room.execute_operation(DraftRoomOperation::JoinRoom { password }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::LeaveRoom) => {
+                draft_room.execute_operation(DraftRoomOperation::LeaveRoom).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::KickPlayer { player }) => {
+                draft_room.execute_operation(DraftRoomOperation::KickPlayer { player }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::StartDraft { shuffle }) => {
+                draft_room.execute_operation(DraftRoomOperation::StartDraft { shuffle }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::PickItem { item_id }) => {
+                draft_room
+                    .execute_operation(DraftRoomOperation::PickItem { item_id })
+                    .await
+                    .map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::FinalizeDraft) => {
+                draft_room.execute_operation(DraftRoomOperation::FinalizeDraft).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::UpdatePoolItem { id, name, power }) => {
+                draft_room
+                    .execute_operation(DraftRoomOperation::UpdatePoolItem { id, name, power })
+                    .await
+                    .map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::AddPoolItem { item }) => {
+                draft_room.execute_operation(DraftRoomOperation::AddPoolItem { item }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::RemovePoolItem { id }) => {
+                draft_room.execute_operation(DraftRoomOperation::RemovePoolItem { id }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::SetTurnOrder { order }) => {
+                draft_room.execute_operation(DraftRoomOperation::SetTurnOrder { order }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::SetWishlist { item_ids }) => {
+                draft_room.execute_operation(DraftRoomOperation::SetWishlist { item_ids }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::ForceAutoPick) => {
+                draft_room.execute_operation(DraftRoomOperation::ForceAutoPick).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::ForcePick) => {
+                draft_room.execute_operation(DraftRoomOperation::ForcePick).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::InviteAndReserve { owners }) => {
+                draft_room.execute_operation(DraftRoomOperation::InviteAndReserve { owners }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::Spectate) => {
+                draft_room.execute_operation(DraftRoomOperation::Spectate).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::UndoPick) => {
+                draft_room.execute_operation(DraftRoomOperation::UndoPick).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::RequestMoreTime) => {
+                draft_room.execute_operation(DraftRoomOperation::RequestMoreTime).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::BanItem { item_id }) => {
+                draft_room.execute_operation(DraftRoomOperation::BanItem { item_id }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::SetMaxPlayers { max_players }) => {
+                draft_room.execute_operation(DraftRoomOperation::SetMaxPlayers { max_players }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::RevealSeed { seed }) => {
+                draft_room.execute_operation(DraftRoomOperation::RevealSeed { seed }).await.map_err(Into::into)
             }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::SuggestToPlayer { player, item_id }) => {
+                draft_room.execute_operation(DraftRoomOperation::SuggestToPlayer { player, item_id }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::SetReady { ready }) => {
+                draft_room.execute_operation(DraftRoomOperation::SetReady { ready }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::DraftCaptainPick { player }) => {
+                draft_room.execute_operation(DraftRoomOperation::DraftCaptainPick { player }).await.map_err(Into::into)
+            }
+            (LiveDraftArena::DraftRoom(draft_room), Operation::Concede) => {
+                draft_room.execute_operation(DraftRoomOperation::Concede).await.map_err(Into::into)
+            }
+            (LiveDraftArena::Lobby(_), _) => Err(LiveDraftArenaError::WrongContractType("Lobby")),
+            (LiveDraftArena::DraftRoom(_), _) => Err(LiveDraftArenaError::WrongContractType("DraftRoom")),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::util::BlockingWait;
+
+    use super::*;
+    use crate::fixtures::fixture_owner;
+
+    fn load(parameters: ContractParameters) -> LiveDraftArena {
+        let runtime = ContractRuntime::new().with_application_parameters(parameters);
+        LiveDraftArena::load(runtime).blocking_wait()
+    }
 
-    async fn execute_message(&mut self, _message: Self::Message) {
-        // No message handling needed yet
+    fn lobby() -> Lobby {
+        let LiveDraftArena::Lobby(lobby) = load(ContractParameters::Lobby) else {
+            panic!("Lobby parameters load a Lobby");
+        };
+        lobby
     }
 
-    async fn store(self) {
-        // Store the contract state
+    /// A waiting four-player "Friday Draft" listing with `current_players` seated
+    fn listed_room(current_players: u8) -> DraftRoomMetadata {
+        DraftRoomMetadata {
+            room_name: "Friday Draft".to_string(),
+            max_players: 4,
+            status: RoomStatus::Waiting,
+            created_at: Timestamp::from(0),
+            current_players,
+            creator: None,
+            is_private: false,
+        }
+    }
+
+    #[test]
+    fn test_draft_room_operation_rejected_on_lobby() {
+        let mut lobby = load(ContractParameters::Lobby);
+        assert!(matches!(
+            lobby.dispatch_operation(Operation::PickItem { item_id: 1 }).blocking_wait(),
+            Err(LiveDraftArenaError::WrongContractType("Lobby"))
+        ));
+    }
+
+    #[test]
+    fn test_lobby_operation_rejected_on_draft_room() {
+        let mut room = load(ContractParameters::DraftRoom {
+            max_players: 2,
+            options: RoomOptions::default(),
+            creator: fixture_owner(1),
+            lobby: None,
+        });
+        let operation = Operation::CreateRoom {
+            room_name: "Friday Draft".to_string(),
+            max_players: 2,
+            options: RoomOptions::default(),
+        };
+        assert!(matches!(
+            room.dispatch_operation(operation).blocking_wait(),
+            Err(LiveDraftArenaError::WrongContractType("DraftRoom"))
+        ));
+    }
+
+    #[test]
+    fn test_room_status_report_corrects_drifted_metadata() {
+        let mut lobby = lobby();
+        let room_chain = ChainId::root(1);
+        lobby.storage.rooms.insert(&room_chain, listed_room(1)).unwrap();
+
+        lobby.apply_room_report(room_chain, DraftRoomStatus::Finished, 3).blocking_wait();
+        lobby.apply_room_report(ChainId::root(2), DraftRoomStatus::Drafting, 2).blocking_wait();
+
+        let refreshed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert!(matches!(refreshed.status, RoomStatus::Finished));
+        assert_eq!(refreshed.current_players, 3);
+        assert!(!lobby.storage.rooms.contains_key(&ChainId::root(2)).blocking_wait().unwrap());
+    }
+
+    #[test]
+    fn test_max_players_change_updates_listing() {
+        let mut lobby = lobby();
+        let room_chain = ChainId::root(1);
+        lobby.storage.rooms.insert(&room_chain, listed_room(2)).unwrap();
+
+        lobby.apply_max_players(room_chain, 6).blocking_wait();
+
+        let listed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert_eq!(listed.max_players, 6);
+        assert_eq!(listed.current_players, 2);
+    }
+
+    #[test]
+    fn test_creator_renames_waiting_room() {
+        let mut lobby = lobby();
+        let (creator, other) = (fixture_owner(1), fixture_owner(2));
+        let room_chain = ChainId::root(1);
+        let metadata = DraftRoomMetadata {
+            room_name: "Firday Draft".to_string(),
+            creator: Some(creator),
+            ..listed_room(1)
+        };
+        lobby.storage.rooms.insert(&room_chain, metadata).unwrap();
+        let rename = |new_name: &str| LobbyOperation::RenameRoom { chain_id: room_chain, new_name: new_name.to_string() };
+
+        lobby.runtime.set_authenticated_signer(other);
+        assert!(matches!(lobby.execute_operation(rename("Mine now")).blocking_wait(), Err(LobbyError::NotCreator)));
+
+        lobby.runtime.set_authenticated_signer(creator);
+        assert!(matches!(lobby.execute_operation(rename("  ")).blocking_wait(), Err(LobbyError::EmptyRoomName)));
+        lobby.execute_operation(rename("Friday Draft")).blocking_wait().unwrap();
+        let renamed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert_eq!(renamed.room_name, "Friday Draft");
+
+        lobby.apply_room_report(room_chain, DraftRoomStatus::Drafting, 2).blocking_wait();
+        assert!(matches!(lobby.execute_operation(rename("Saturday Draft")).blocking_wait(), Err(LobbyError::RoomLocked)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_create_room_rejects_invalid_custom_pool() {
+        let mut lobby = lobby();
+        lobby.runtime.set_authenticated_signer(fixture_owner(1));
+        let create = |pool: Vec<DraftItem>| LobbyOperation::CreateRoom {
+            room_name: "Custom Draft".to_string(),
+            max_players: 2,
+            options: RoomOptions { custom_pool: pool, ..RoomOptions::default() },
+        };
+
+        let duplicate = vec![DraftItem::new(1, "Rook", 50), DraftItem::new(1, "Bishop", 30)];
+        assert!(matches!(
+            lobby.execute_operation(create(duplicate)).blocking_wait(),
+            Err(LobbyError::InvalidPool(DraftRoomError::InvalidPool(_)))
+        ));
+        let zero_id = vec![DraftItem::new(0, "Pawn", 10)];
+        assert!(matches!(
+            lobby.execute_operation(create(zero_id)).blocking_wait(),
+            Err(LobbyError::InvalidPool(DraftRoomError::InvalidPool(_)))
+        ));
+    }
+
+    #[test]
+    fn test_create_room_rejects_pool_smaller_than_draft() {
+        let mut lobby = lobby();
+        lobby.runtime.set_authenticated_signer(fixture_owner(1));
+        let create = |max_players: u8, custom_pool: Vec<DraftItem>| LobbyOperation::CreateRoom {
+            room_name: "Custom Draft".to_string(),
+            max_players,
+            options: RoomOptions { custom_pool, ..RoomOptions::default() },
+        };
+
+        let small = vec![DraftItem::new(1, "Rook", 50), DraftItem::new(2, "Bishop", 30)];
+        assert!(matches!(
+            lobby.execute_operation(create(2, small)).blocking_wait(),
+            Err(LobbyError::InvalidDraftConfig(DraftRoomError::PoolTooSmall { picks: 6, bans: 0, items: 2 }))
+        ));
+        // The default pool has 15 items, too few for 6 players over 3 rounds
+        assert!(matches!(
+            lobby.execute_operation(create(6, Vec::new())).blocking_wait(),
+            Err(LobbyError::InvalidDraftConfig(DraftRoomError::PoolTooSmall { picks: 18, bans: 0, items: 15 }))
+        ));
+        // Five ban rounds for two players leave only 5 of the 15 default items for 6 picks
+        let many_bans = LobbyOperation::CreateRoom {
+            room_name: "Custom Draft".to_string(),
+            max_players: 2,
+            options: RoomOptions { ban_rounds: 5, ..RoomOptions::default() },
+        };
+        assert!(matches!(
+            lobby.execute_operation(many_bans).blocking_wait(),
+            Err(LobbyError::InvalidDraftConfig(DraftRoomError::PoolTooSmall { picks: 6, bans: 10, items: 15 }))
+        ));
+    }
+
+    #[test]
+    fn test_player_count_change_updates_listing() {
+        let mut lobby = lobby();
+        let room_chain = ChainId::root(1);
+        lobby.storage.rooms.insert(&room_chain, listed_room(0)).unwrap();
+
+        lobby.apply_player_count(room_chain, 3).blocking_wait();
+        lobby.apply_player_count(ChainId::root(2), 1).blocking_wait();
+
+        let listed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert_eq!(listed.current_players, 3);
+        assert!(lobby.storage.rooms.get(&ChainId::root(2)).blocking_wait().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_status_change_updates_listing() {
+        let mut lobby = lobby();
+        let room_chain = ChainId::root(1);
+        lobby.storage.rooms.insert(&room_chain, listed_room(2)).unwrap();
+
+        lobby.apply_status(room_chain, DraftRoomStatus::Banning).blocking_wait();
+        let listed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert_eq!(listed.status, RoomStatus::Drafting);
+        assert_eq!(listed.current_players, 2);
+
+        lobby.apply_status(room_chain, DraftRoomStatus::Finished).blocking_wait();
+        let listed = lobby.storage.rooms.get(&room_chain).blocking_wait().unwrap().unwrap();
+        assert_eq!(listed.status, RoomStatus::Finished);
+    }
+
+    #[test]
+    fn test_finalized_drafts_aggregate_item_popularity() {
+        let mut lobby = lobby();
+        for n in 1..=2 {
+            let metadata = DraftRoomMetadata {
+                room_name: format!("Draft {}", n),
+                max_players: 2,
+                status: RoomStatus::Finished,
+                ..listed_room(2)
+            };
+            lobby.storage.rooms.insert(&ChainId::root(n), metadata).unwrap();
+        }
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+        lobby.apply_draft_finalized(ChainId::root(1), names(&["Lightning Bolt", "Black Lotus"]), None).blocking_wait();
+        lobby.apply_draft_finalized(ChainId::root(2), names(&["Lightning Bolt", "Counterspell", "Lightning Bolt"]), None).blocking_wait();
+        // Rooms the Lobby doesn't list are ignored
+        lobby.apply_draft_finalized(ChainId::root(3), names(&["Black Lotus"]), None).blocking_wait();
+
+        let count = |name: &str| lobby.storage.item_popularity.get(&name.to_string()).blocking_wait().unwrap();
+        assert_eq!(count("Lightning Bolt"), Some(3));
+        assert_eq!(count("Black Lotus"), Some(1));
+        assert_eq!(count("Counterspell"), Some(1));
+        assert_eq!(count("Time Walk"), None);
+    }
+
+    #[test]
+    fn test_cleanup_removes_finished_rooms_past_delay_but_keeps_results() {
+        let mut lobby = lobby();
+        for n in 1..=2 {
+            let metadata = DraftRoomMetadata {
+                room_name: format!("Draft {}", n),
+                max_players: 2,
+                status: RoomStatus::Finished,
+                ..listed_room(2)
+            };
+            lobby.storage.rooms.insert(&ChainId::root(n), metadata).unwrap();
+        }
+        lobby.storage.auto_close_after_secs.insert(&ChainId::root(1), 60).unwrap();
+
+        lobby.runtime.set_system_time(Timestamp::from(10_000_000));
+        for n in 1..=2 {
+            lobby.apply_draft_finalized(ChainId::root(n), Vec::new(), Some(fixture_owner(n as u8))).blocking_wait();
+        }
+        lobby.runtime.set_authenticated_signer(fixture_owner(1));
+        let listed = |lobby: &Lobby, n| lobby.storage.rooms.contains_key(&ChainId::root(n)).blocking_wait().unwrap();
+
+        // Not yet due
+        lobby.runtime.set_system_time(Timestamp::from(69_000_000));
+        lobby.execute_operation(LobbyOperation::CleanupFinished).blocking_wait().unwrap();
+        assert!(listed(&lobby, 1));
+
+        lobby.runtime.set_system_time(Timestamp::from(70_000_000));
+        lobby.execute_operation(LobbyOperation::CleanupFinished).blocking_wait().unwrap();
+        assert!(!listed(&lobby, 1));
+        // Rooms without a delay stay listed
+        assert!(listed(&lobby, 2));
+
+        let result = lobby.storage.results.get(&ChainId::root(1)).blocking_wait().unwrap().unwrap();
+        assert_eq!(result.room_name, "Draft 1");
+        assert_eq!(result.winner, Some(fixture_owner(1)));
+        assert_eq!(result.finalized_at, Timestamp::from(10_000_000));
+    }
+}
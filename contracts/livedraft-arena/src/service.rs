@@ -1,13 +1,26 @@
 use async_graphql::{Request, Response, Schema, SimpleObject};
-use linera_sdk::{Service, ServiceRuntime, Contract};
+use linera_sdk::{
+    base::ChainId,
+    views::{MapView, View},
+    Service, ServiceRuntime,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::LiveDraftArena;
+use crate::{DraftRoomMetadata, LobbyStorage};
+
+/// Version of this GraphQL schema, bumped whenever its shape changes
+///
+/// Off-chain clients read `schemaVersion` to decide whether they can use the
+/// schema or must fall back to parsing raw state from older deployments.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Lobby room index as stored by the contract
+type Rooms = MapView<ChainId, DraftRoomMetadata>;
 
 /// GraphQL service
 pub struct LiveDraftArenaService {
-    state: Arc<LiveDraftArena>,
+    state: Arc<Rooms>,
 }
 
 /// Room data for GraphQL responses
@@ -21,16 +34,21 @@ pub struct RoomData {
 
 /// GraphQL query root
 pub struct QueryRoot {
-    state: Arc<LiveDraftArena>,
+    state: Arc<Rooms>,
 }
 
 #[async_graphql::Object]
 impl QueryRoot {
+    /// Version of this schema
+    async fn schema_version(&self) -> u32 {
+        SCHEMA_VERSION
+    }
+
     /// Get all draft rooms
     async fn rooms(&self) -> Vec<RoomData> {
         let mut rooms = Vec::new();
         
-        if let Ok(iter) = self.state.rooms.iter().await {
+        if let Ok(iter) = self.state.iter().await {
             for (chain_id, metadata) in iter {
                 rooms.push(RoomData {
                     chain_id: chain_id.to_string(),
@@ -49,11 +67,12 @@ impl Service for LiveDraftArenaService {
     type Parameters = ();
 
     async fn new(runtime: ServiceRuntime<Self>) -> Self {
-        let state = LiveDraftArena::load(runtime.root_view_storage_context().into())
-            .await;
+        let storage = LobbyStorage::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load lobby state");
         
         LiveDraftArenaService {
-            state: Arc::new(state),
+            state: Arc::new(storage.rooms),
         }
     }
 
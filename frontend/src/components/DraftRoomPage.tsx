@@ -258,6 +258,7 @@ const DraftRoomPage: React.FC<DraftRoomPageProps> = ({
               >
                 <h4>{item.name}</h4>
                 <p>Power: {item.power}</p>
+                {item.quantity > 1 && <p>Remaining: {item.quantity}</p>}
                 <button
                   onClick={() => handlePickItem(item.id)}
                   disabled={!isMyTurn || submitting}
@@ -48,6 +48,10 @@ export const QUERIES = {
           id
           name
           power
+          quantity
+          tags
+          cost
+          weight
         }
         status
       }
@@ -4,6 +4,10 @@ export interface DraftItem {
   id: number;
   name: string;
   power: number;
+  quantity: number;
+  tags: string[];
+  cost: number;
+  weight: number;
 }
 
 export interface RoomData {
@@ -22,7 +26,7 @@ export interface DraftRoomState {
   round: number;
   maxRounds: number;
   pool: DraftItem[];
-  status: 'Waiting' | 'Drafting' | 'Finished';
+  status: 'Waiting' | 'CaptainPicking' | 'Banning' | 'Drafting' | 'Finished';
 }
 
 export interface OperationResult {
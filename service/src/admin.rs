@@ -0,0 +1,57 @@
+use warp::http::HeaderMap;
+
+/// Operator authentication for admin-only queries
+///
+/// Admin access is enabled by setting `ADMIN_TOKEN`; requests prove it by
+/// sending the same value in the `X-Admin-Token` header. With no token
+/// configured, no request is ever treated as admin.
+
+/// Header carrying the operator's admin token
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// The configured admin token, if admin queries are enabled
+pub fn admin_token_from_env() -> Option<String> {
+    std::env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty())
+}
+
+/// Whether a request's headers carry the configured admin token
+pub fn is_admin_request(headers: &HeaderMap, admin_token: Option<&str>) -> bool {
+    let Some(expected) = admin_token else {
+        return false;
+    };
+
+    headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|provided| tokens_match(provided.as_bytes(), expected.as_bytes()))
+}
+
+/// Compare tokens without exiting early on the first differing byte
+fn tokens_match(provided: &[u8], expected: &[u8]) -> bool {
+    provided.len() == expected.len()
+        && provided.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers_with_token(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ADMIN_TOKEN_HEADER, token.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_matching_token_is_admin() {
+        assert!(is_admin_request(&headers_with_token("s3cret"), Some("s3cret")));
+    }
+
+    #[test]
+    fn test_wrong_or_missing_token_is_not_admin() {
+        assert!(!is_admin_request(&headers_with_token("guess"), Some("s3cret")));
+        assert!(!is_admin_request(&headers_with_token("s3cret-longer"), Some("s3cret")));
+        assert!(!is_admin_request(&HeaderMap::new(), Some("s3cret")));
+        assert!(!is_admin_request(&headers_with_token("s3cret"), None));
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Server clock readings shared with clients
+///
+/// Clients compare these against their local clock to correct for skew when
+/// showing turn countdowns.
+
+/// Current server time as an RFC 3339 timestamp in UTC (millisecond precision)
+pub fn now_rfc3339() -> String {
+    to_rfc3339(Utc::now())
+}
+
+/// Format a server-side time the same way as `now_rfc3339`
+pub fn to_rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_time_is_parseable_rfc3339() {
+        let before = Utc::now();
+        let parsed = DateTime::parse_from_rfc3339(&now_rfc3339()).unwrap();
+        assert!((parsed.with_timezone(&Utc) - before).num_seconds().abs() < 5);
+    }
+}
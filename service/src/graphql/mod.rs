@@ -1,12 +1,20 @@
 mod query;
 mod mutation;
+mod subscription;
 
 pub use query::QueryRoot;
 pub use mutation::MutationRoot;
+pub use subscription::SubscriptionRoot;
 
-use async_graphql::Context;
+use async_graphql::{Context, ErrorExtensions, Result};
 use linera_core::data_types::Owner;
+use linera_sdk::base::crypto::KeyPair;
+use std::sync::Arc;
+use warp::http::HeaderMap;
+use crate::admin::is_admin_request;
 use crate::identity::player_id_to_owner;
+use crate::keys::player_keys;
+use crate::types::DraftItemInput;
 
 /// GraphQL context containing player identity information
 /// 
@@ -16,34 +24,145 @@ use crate::identity::player_id_to_owner;
 pub struct GraphQLContext {
     /// Unique player ID for this session
     pub player_id: String,
-    /// Linera Owner address derived from player ID
-    pub player_owner: Owner,
+    /// Linera Owner address derived from player ID; `None` for anonymous reads
+    pub player_owner: Option<Owner>,
+    /// Correlation ID for this request, echoed in logs, headers and results
+    pub request_id: String,
+    /// Whether the request carried the configured admin token
+    pub is_admin: bool,
 }
 
 impl GraphQLContext {
     /// Create new GraphQL context with player identity
-    pub fn new(player_id: String) -> Self {
-        let player_owner = player_id_to_owner(&player_id)
-            .expect("Failed to create Owner from player ID");
-        
+    pub fn new(player_id: String, request_id: String) -> Self {
         Self {
+            player_owner: Some(player_id_to_owner(&player_id)),
             player_id,
-            player_owner,
+            request_id,
+            is_admin: false,
         }
     }
     
+    /// Grant admin access if the request headers carry the configured admin token
+    /// 
+    /// Leaves `is_admin` false when no token is configured.
+    pub fn with_admin_token(mut self, headers: &HeaderMap, admin_token: Option<&str>) -> Self {
+        self.is_admin = is_admin_request(headers, admin_token);
+        self
+    }
+
     /// Get player ID from GraphQL context
     pub fn get_player_id(&self) -> &str {
         &self.player_id
     }
     
-    /// Get Linera Owner for this player
-    pub fn get_player_owner(&self) -> &Owner {
-        &self.player_owner
+    /// Get Linera Owner for this player, if the request has one
+    pub fn get_player_owner(&self) -> Option<&Owner> {
+        self.player_owner.as_ref()
+    }
+
+    /// Get Linera Owner for this player, failing for anonymous requests
+    /// 
+    /// Use this wherever the caller's identity matters: signing operations,
+    /// "my" queries and turn checks.
+    pub fn require_player_owner(&self) -> Result<&Owner> {
+        self.player_owner.as_ref().ok_or_else(|| {
+            async_graphql::Error::new("Player identity required").extend_with(|_, e| e.set("code", "UNAUTHENTICATED"))
+        })
+    }
+
+    /// The key this session's operations are signed with
+    /// 
+    /// Its Owner is `player_owner`, so the contract authenticates the player themselves.
+    pub fn signing_key(&self) -> Arc<KeyPair> {
+        player_keys().key_pair(&self.player_id)
     }
 }
 
 /// Helper function to extract GraphQL context from async-graphql Context
 pub fn get_context(ctx: &Context<'_>) -> &GraphQLContext {
     ctx.data_unchecked::<GraphQLContext>()
-}
\ No newline at end of file
+}
+
+/// Reject the request unless it was authenticated as admin
+/// 
+/// Every admin-only resolver goes through this check, so non-admins always
+/// see the same `UNAUTHORIZED` error.
+pub(crate) fn require_admin(context: &GraphQLContext) -> Result<()> {
+    if context.is_admin {
+        Ok(())
+    } else {
+        Err(async_graphql::Error::new("Unauthorized").extend_with(|_, e| e.set("code", "UNAUTHORIZED")))
+    }
+}
+
+/// Convert a GraphQL pool item into the contract's item type
+fn to_contract_item(input: DraftItemInput) -> livedraft_arena::DraftItem {
+    livedraft_arena::DraftItem {
+        quantity: input.quantity.unwrap_or(1),
+        tags: input.tags.unwrap_or_default(),
+        cost: input.cost.unwrap_or(0),
+        weight: input.weight.unwrap_or(1),
+        ..livedraft_arena::DraftItem::new(input.id, &input.name, input.power)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(is_admin: bool) -> GraphQLContext {
+        GraphQLContext {
+            is_admin,
+            ..GraphQLContext::new("1234567890abcdef".to_string(), "request".to_string())
+        }
+    }
+
+    #[test]
+    fn test_context_admin_flag_follows_token() {
+        let mut headers = HeaderMap::new();
+        headers.insert(crate::admin::ADMIN_TOKEN_HEADER, "s3cret".parse().unwrap());
+
+        let with_token = GraphQLContext::new("1234567890abcdef".to_string(), "request".to_string())
+            .with_admin_token(&headers, Some("s3cret"));
+        assert!(with_token.is_admin);
+
+        let not_configured = GraphQLContext::new("1234567890abcdef".to_string(), "request".to_string())
+            .with_admin_token(&headers, None);
+        assert!(!not_configured.is_admin);
+
+        let without_header = GraphQLContext::new("1234567890abcdef".to_string(), "request".to_string())
+            .with_admin_token(&HeaderMap::new(), Some("s3cret"));
+        assert!(!without_header.is_admin);
+    }
+
+    #[test]
+    fn test_require_admin_allows_admin() {
+        assert!(require_admin(&context(true)).is_ok());
+    }
+
+    #[test]
+    fn test_require_admin_rejects_non_admin() {
+        let err = require_admin(&context(false)).unwrap_err();
+        assert_eq!(err.message, "Unauthorized");
+        let code = err.extensions.as_ref().and_then(|ext| ext.get("code")).cloned();
+        assert_eq!(code, Some(async_graphql::Value::from("UNAUTHORIZED")));
+    }
+
+    #[test]
+    fn test_anonymous_context_reads_but_cannot_act() {
+        let anonymous = GraphQLContext {
+            player_owner: None,
+            ..context(false)
+        };
+        assert!(anonymous.get_player_owner().is_none());
+
+        let err = anonymous.require_player_owner().unwrap_err();
+        assert_eq!(err.message, "Player identity required");
+        let code = err.extensions.as_ref().and_then(|ext| ext.get("code")).cloned();
+        assert_eq!(code, Some(async_graphql::Value::from("UNAUTHENTICATED")));
+
+        let player = context(false);
+        assert_eq!(player.require_player_owner().ok(), Some(&player_id_to_owner("1234567890abcdef")));
+    }
+}
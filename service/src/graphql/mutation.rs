@@ -1,103 +1,259 @@
 use async_graphql::{Context, Object, Result};
+use futures::stream::{self, StreamExt};
 use linera_client::ClientContext;
 use linera_core::data_types::{ApplicationId, ChainId};
-use tracing::{error, info};
+use linera_sdk::base::Owner;
+use std::sync::Arc;
+use tracing::{error, info, warn};
 
-use crate::types::{CreateRoomInput, OperationResult, PickItemInput};
-use super::get_context;
+use crate::clock::now_rfc3339;
+use crate::jobs::JobStore;
+use crate::op_limit::OperationLimiter;
+use crate::reports::ReportStore;
+use crate::room_index::RoomIndex;
+use crate::share_code::decode_share_code;
+use crate::types::{CreateRoomInput, DraftItemInput, FinalizeOutcome, OperationInput, OperationKind, OperationResult, PickItemInput, StandingData};
+use super::{get_context, require_admin, to_contract_item, GraphQLContext};
+use super::query::{needs_finalizing, room_standings, simulation_picks, QueryRoot};
 
 // Import the Operation enum from the contract
-use livedraft_arena::Operation;
+use livedraft_arena::{password_hash, Operation, RoomOptions, DEFAULT_MAX_SPECTATORS};
+
+/// Maximum number of rooms `finalizePending` works on at once
+const FINALIZE_CONCURRENCY: usize = 8;
 
 /// GraphQL Mutation root
+#[derive(Clone)]
 pub struct MutationRoot {
     client: ClientContext,
     app_id: ApplicationId,
     default_chain_id: ChainId,
+    room_index: Arc<RoomIndex>,
+    reports: Arc<ReportStore>,
+    jobs: Arc<JobStore>,
+    limiter: Arc<OperationLimiter>,
+    /// Reads room state for mutations that act on it
+    query: QueryRoot,
 }
 
 impl MutationRoot {
-    pub fn new(client: ClientContext, app_id: ApplicationId, default_chain_id: ChainId) -> Self {
+    pub fn new(client: ClientContext, app_id: ApplicationId, default_chain_id: ChainId, room_index: Arc<RoomIndex>, reports: Arc<ReportStore>, jobs: Arc<JobStore>, limiter: Arc<OperationLimiter>, query: QueryRoot) -> Self {
         Self {
             client,
             app_id,
             default_chain_id,
+            room_index,
+            reports,
+            jobs,
+            limiter,
+            query,
         }
     }
-}
 
-#[Object]
-impl MutationRoot {
-    /// Create a new draft room on the Lobby chain
-    /// 
-    /// This executes a CreateRoom operation on the Lobby contract, which:
-    /// 1. Validates the room parameters
-    /// 2. Opens a new microchain for the DraftRoom
-    /// 3. Stores the room metadata in the Lobby state
+    /// A DraftRoom's state version right after an operation, if it can be read
     /// 
+    /// Lobby operations and failed reads leave the result without a version.
+    async fn room_version(&self, chain_id: ChainId) -> Option<u64> {
+        if chain_id == self.default_chain_id {
+            return None;
+        }
+        match self.query.load_draft_room(chain_id).await {
+            Ok(room) => Some(room.version),
+            Err(e) => {
+                warn!("Could not read state version of chain {} after operation: {}", chain_id, e.message);
+                None
+            }
+        }
+    }
+
+    /// Validate a new room and execute CreateRoom on the Lobby chain
+    ///
     /// The operation is signed with the player's deterministic Owner identity.
-    async fn create_room(&self, ctx: &Context<'_>, input: CreateRoomInput) -> Result<OperationResult> {
-        let context = get_context(ctx);
+    async fn submit_create_room(
+        &self,
+        context: &GraphQLContext,
+        room_name: String,
+        max_players: u8,
+        options: RoomOptions,
+    ) -> OperationResult {
         let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
-        
-        info!("Player {} creating room: {} with {} max players", 
-              player_id, input.room_name, input.max_players);
+
+        info!("Player {} creating room: {} with {} max players",
+              player_id, room_name, max_players);
 
         // Validate input on the service side for better UX
-        if input.room_name.trim().is_empty() {
-            return Ok(OperationResult {
-                success: false,
-                message: "Room name cannot be empty".to_string(),
-                transaction_hash: None,
-            });
+        if room_name.trim().is_empty() {
+            return failed(context, "Room name cannot be empty");
         }
 
-        if input.max_players < 2 || input.max_players > 8 {
-            return Ok(OperationResult {
-                success: false,
-                message: "Max players must be between 2 and 8".to_string(),
-                transaction_hash: None,
-            });
+        if max_players < 2 || max_players > 8 {
+            return failed(context, "Max players must be between 2 and 8");
         }
 
         // Create the operation matching the contract's Operation enum
         // This will be executed on the Lobby chain (default_chain_id)
         let operation = Operation::CreateRoom {
-            room_name: input.room_name.clone(),
-            max_players: input.max_players,
+            room_name: room_name.clone(),
+            max_players,
+            options,
         };
 
         // Execute operation on the Lobby chain using the player's Owner identity
         // The Linera client will:
         // 1. Serialize the operation
-        // 2. Create a transaction signed by the player's Owner
+        // 2. Create a transaction signed by the player's own key
         // 3. Submit to the Lobby chain on Conway testnet
         // 4. Wait for confirmation
-        match self.client.execute_operation(
+        let _permit = self.limiter.acquire("create room", player_id, self.default_chain_id).await;
+        match self.client.execute_operation_as(
+            &context.signing_key(),
             self.default_chain_id, 
             self.app_id, 
             &operation,
         ).await {
             Ok(response) => {
-                info!("Player {} successfully created room '{}'", player_id, input.room_name);
-                Ok(OperationResult {
-                    success: true,
-                    message: format!("Room '{}' created successfully", input.room_name),
-                    transaction_hash: Some(format!("{:?}", response)), // Extract actual transaction hash
-                })
+                info!("Player {} successfully created room '{}'", player_id, room_name);
+                submitted(context, format!("Room '{}' created successfully", room_name), format!("{:?}", response), None)
             }
             Err(e) => {
-                error!("Player {} failed to create room '{}': {}", player_id, input.room_name, e);
-                Ok(OperationResult {
-                    success: false,
-                    message: format!("Failed to create room: {}", e),
-                    transaction_hash: None,
-                })
+                error!("Player {} failed to create room '{}': {}", player_id, room_name, e);
+                failed(context, format!("Failed to create room: {}", e))
             }
         }
     }
 
+    /// Execute an operation on a DraftRoom microchain and report the outcome
+    /// 
+    /// `action` describes the operation for logs and failure messages
+    /// (e.g. "update pool item"); `success_message` is returned on success.
+    async fn execute_room_operation(
+        &self,
+        context: &GraphQLContext,
+        chain_id: ChainId,
+        operation: Operation,
+        action: &str,
+        success_message: &str,
+    ) -> OperationResult {
+        let player_id = context.get_player_id();
+        let _permit = self.limiter.acquire(action, player_id, chain_id).await;
+        match self.client.execute_operation_as(
+            &context.signing_key(),
+            chain_id, 
+            self.app_id, 
+            &operation,
+        ).await {
+            Ok(response) => {
+                info!("Player {} succeeded to {} on chain {}", player_id, action, chain_id);
+                submitted(context, success_message, format!("{:?}", response), self.room_version(chain_id).await)
+            }
+            Err(e) => {
+                error!("Player {} failed to {} on chain {}: {}", player_id, action, chain_id, e);
+                failed(context, format!("Failed to {}: {}", action, e))
+            }
+        }
+    }
+}
+
+/// A result for this request that nothing was submitted for
+fn operation_result(context: &GraphQLContext, success: bool, message: impl Into<String>) -> OperationResult {
+    OperationResult {
+        success,
+        message: message.into(),
+        transaction_hash: None,
+        request_id: context.request_id.clone(),
+        server_time: now_rfc3339(),
+        state_version: None,
+    }
+}
+
+/// A failed result for this request
+fn failed(context: &GraphQLContext, message: impl Into<String>) -> OperationResult {
+    operation_result(context, false, message)
+}
+
+/// A successful result for an operation the chain accepted
+fn submitted(context: &GraphQLContext, message: impl Into<String>, transaction_hash: String, state_version: Option<u64>) -> OperationResult {
+    OperationResult {
+        transaction_hash: Some(transaction_hash),
+        state_version,
+        ..operation_result(context, true, message)
+    }
+}
+
+/// Convert a queued operation into the DraftRoom operation it submits
+fn to_room_operation(input: &OperationInput) -> Result<Operation> {
+    match input.kind {
+        OperationKind::CreateRoom => Err(async_graphql::Error::new("CreateRoom cannot be queued; use createRoom")),
+        OperationKind::JoinRoom => Ok(Operation::JoinRoom { password: input.password.clone() }),
+        OperationKind::StartDraft => Ok(Operation::StartDraft { shuffle: true }),
+        OperationKind::PickItem => input.item_id
+            .map(|item_id| Operation::PickItem { item_id })
+            .ok_or_else(|| async_graphql::Error::new("PickItem requires itemId")),
+        OperationKind::FinalizeDraft => Ok(Operation::FinalizeDraft),
+    }
+}
+
+#[Object]
+impl MutationRoot {
+    /// Create a new draft room on the Lobby chain
+    /// 
+    /// This executes a CreateRoom operation on the Lobby contract, which:
+    /// 1. Validates the room parameters
+    /// 2. Opens a new microchain for the DraftRoom
+    /// 3. Stores the room metadata in the Lobby state
+    /// 
+    /// The operation is signed with the player's deterministic Owner identity.
+    async fn create_room(&self, ctx: &Context<'_>, input: CreateRoomInput) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        context.require_player_owner()?;
+
+        let captains = input.captains.iter()
+            .flatten()
+            .map(|owner| owner.parse::<Owner>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid captain owner: {}", e)))?;
+
+        let options = RoomOptions {
+            reveal_after_round: input.reveal_after_round.unwrap_or(false),
+            final_round_min_power: input.final_round_min_power,
+            round_weights: input.round_weights.clone().unwrap_or_default(),
+            turn_duration_secs: input.turn_duration_secs,
+            budget: input.budget,
+            max_spectators: input.max_spectators.unwrap_or(DEFAULT_MAX_SPECTATORS),
+            preview: input.preview.unwrap_or(false),
+            ban_rounds: input.ban_rounds.unwrap_or(0),
+            pool_size: input.pool_size,
+            pool_seed: input.pool_seed,
+            seed_commitment: input.seed_commitment.clone(),
+            custom_pool: input.custom_pool.clone().unwrap_or_default().into_iter().map(to_contract_item).collect(),
+            handicap_first_pick: input.handicap_first_pick.unwrap_or(false),
+            password_hash: input.password.as_deref().map(password_hash),
+            min_pick_interval_secs: input.min_pick_interval_secs,
+            captains,
+            auto_close_after_secs: input.auto_close_after_secs,
+        };
+
+        Ok(self.submit_create_room(context, input.room_name, input.max_players, options).await)
+    }
+
+    /// Create a new room with the settings encoded in a share code
+    ///
+    /// The code comes from `shareCode`; its settings are checked before the
+    /// room is created, and the new room takes `newName`.
+    async fn create_from_share_code(&self, ctx: &Context<'_>, code: String, new_name: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        context.require_player_owner()?;
+
+        let template = match decode_share_code(&code) {
+            Ok(template) => template,
+            Err(e) => {
+                return Ok(failed(context, format!("Invalid share code: {}", e)));
+            }
+        };
+
+        Ok(self.submit_create_room(context, new_name, template.max_players, template.options).await)
+    }
+
     /// Join a draft room on a specific microchain
     /// 
     /// This executes a JoinRoom operation on the DraftRoom contract, which:
@@ -106,11 +262,14 @@ impl MutationRoot {
     /// 3. Adds the player to the room
     /// 4. Initializes empty picks for the player
     /// 
+    /// Private rooms also need `password`, which the contract checks against
+    /// the room's hash.
+    /// 
     /// The operation is signed with the player's deterministic Owner identity.
-    async fn join_room(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+    async fn join_room(&self, ctx: &Context<'_>, chain_id: String, password: Option<String>) -> Result<OperationResult> {
         let context = get_context(ctx);
         let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
+        context.require_player_owner()?;
         
         info!("Player {} joining room on chain: {}", player_id, chain_id);
 
@@ -119,34 +278,51 @@ impl MutationRoot {
             .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
 
         // Create the JoinRoom operation for the DraftRoom contract
-        let operation = Operation::JoinRoom;
+        let operation = Operation::JoinRoom { password };
 
         // Execute operation on the DraftRoom microchain
         // The player's Owner identity will be used for authentication in the contract
-        match self.client.execute_operation(
+        let _permit = self.limiter.acquire("join room", player_id, chain_id).await;
+        match self.client.execute_operation_as(
+            &context.signing_key(),
             chain_id, 
             self.app_id, 
             &operation,
         ).await {
             Ok(response) => {
                 info!("Player {} successfully joined room on chain {}", player_id, chain_id);
-                Ok(OperationResult {
-                    success: true,
-                    message: "Joined room successfully".to_string(),
-                    transaction_hash: Some(format!("{:?}", response)),
-                })
+                self.room_index.record(player_id, &chain_id.to_string());
+                Ok(submitted(context, "Joined room successfully", format!("{:?}", response), self.room_version(chain_id).await))
             }
             Err(e) => {
                 error!("Player {} failed to join room on chain {}: {}", player_id, chain_id, e);
-                Ok(OperationResult {
-                    success: false,
-                    message: format!("Failed to join room: {}", e),
-                    transaction_hash: None,
-                })
+                Ok(failed(context, format!("Failed to join room: {}", e)))
             }
         }
     }
 
+    /// Leave a room before its draft starts
+    /// 
+    /// This executes a LeaveRoom operation on the DraftRoom contract, which
+    /// frees the player's seat and, if they created the room, hands it to the
+    /// next player who joined.
+    async fn leave_room(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} leaving room on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let result = self.execute_room_operation(context, chain_id, Operation::LeaveRoom, "leave room", "Left room successfully").await;
+        if result.success {
+            self.room_index.remove(player_id, &chain_id.to_string());
+        }
+        Ok(result)
+    }
+
     /// Start a draft (creator only)
     /// 
     /// This executes a StartDraft operation on the DraftRoom contract, which:
@@ -154,12 +330,13 @@ impl MutationRoot {
     /// 2. Initializes the hardcoded Wave-5 card pool
     /// 3. Sets the room status to Drafting
     /// 4. Resets turn/round counters
+    /// 5. Shuffles the seat order, unless `shuffle` is false or the creator set an order
     /// 
     /// Only the room creator can start the draft.
-    async fn start_draft(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+    async fn start_draft(&self, ctx: &Context<'_>, chain_id: String, shuffle: Option<bool>) -> Result<OperationResult> {
         let context = get_context(ctx);
         let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
+        context.require_player_owner()?;
         
         info!("Player {} starting draft on chain: {}", player_id, chain_id);
 
@@ -168,30 +345,24 @@ impl MutationRoot {
             .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
 
         // Create the StartDraft operation for the DraftRoom contract
-        let operation = Operation::StartDraft;
+        let operation = Operation::StartDraft { shuffle: shuffle.unwrap_or(true) };
 
         // Execute operation on the DraftRoom microchain
         // The contract will verify the caller is the creator
-        match self.client.execute_operation(
+        let _permit = self.limiter.acquire("start draft", player_id, chain_id).await;
+        match self.client.execute_operation_as(
+            &context.signing_key(),
             chain_id, 
             self.app_id, 
             &operation,
         ).await {
             Ok(response) => {
                 info!("Player {} successfully started draft on chain {}", player_id, chain_id);
-                Ok(OperationResult {
-                    success: true,
-                    message: "Draft started successfully".to_string(),
-                    transaction_hash: Some(format!("{:?}", response)),
-                })
+                Ok(submitted(context, "Draft started successfully", format!("{:?}", response), self.room_version(chain_id).await))
             }
             Err(e) => {
                 error!("Player {} failed to start draft on chain {}: {}", player_id, chain_id, e);
-                Ok(OperationResult {
-                    success: false,
-                    message: format!("Failed to start draft: {}", e),
-                    transaction_hash: None,
-                })
+                Ok(failed(context, format!("Failed to start draft: {}", e)))
             }
         }
     }
@@ -208,7 +379,7 @@ impl MutationRoot {
     async fn pick_item(&self, ctx: &Context<'_>, chain_id: String, input: PickItemInput) -> Result<OperationResult> {
         let context = get_context(ctx);
         let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
+        context.require_player_owner()?;
         
         info!("Player {} picking item {} on chain: {}", player_id, input.item_id, chain_id);
 
@@ -223,26 +394,20 @@ impl MutationRoot {
 
         // Execute operation on the DraftRoom microchain
         // The contract will verify it's the player's turn and handle the pick logic
-        match self.client.execute_operation(
+        let _permit = self.limiter.acquire("pick item", player_id, chain_id).await;
+        match self.client.execute_operation_as(
+            &context.signing_key(),
             chain_id, 
             self.app_id, 
             &operation,
         ).await {
             Ok(response) => {
                 info!("Player {} successfully picked item {} on chain {}", player_id, input.item_id, chain_id);
-                Ok(OperationResult {
-                    success: true,
-                    message: "Item picked successfully".to_string(),
-                    transaction_hash: Some(format!("{:?}", response)),
-                })
+                Ok(submitted(context, "Item picked successfully", format!("{:?}", response), self.room_version(chain_id).await))
             }
             Err(e) => {
                 error!("Player {} failed to pick item {} on chain {}: {}", player_id, input.item_id, chain_id, e);
-                Ok(OperationResult {
-                    success: false,
-                    message: format!("Failed to pick item: {}", e),
-                    transaction_hash: None,
-                })
+                Ok(failed(context, format!("Failed to pick item: {}", e)))
             }
         }
     }
@@ -254,7 +419,7 @@ impl MutationRoot {
     async fn finalize_draft(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
         let context = get_context(ctx);
         let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
+        context.require_player_owner()?;
         
         info!("Player {} finalizing draft on chain: {}", player_id, chain_id);
 
@@ -262,31 +427,527 @@ impl MutationRoot {
         let chain_id = chain_id.parse::<ChainId>()
             .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
 
-        // Create the FinalizeDraft operation for the DraftRoom contract
-        let operation = Operation::FinalizeDraft;
+        Ok(self.execute_room_operation(context, chain_id, Operation::FinalizeDraft, "finalize draft", "Draft finalized successfully").await)
+    }
 
-        // Execute operation on the DraftRoom microchain
-        match self.client.execute_operation(
-            chain_id, 
-            self.app_id, 
-            &operation,
-        ).await {
-            Ok(response) => {
-                info!("Player {} successfully finalized draft on chain {}", player_id, chain_id);
-                Ok(OperationResult {
-                    success: true,
-                    message: "Draft finalized successfully".to_string(),
-                    transaction_hash: Some(format!("{:?}", response)),
-                })
-            }
-            Err(e) => {
-                error!("Player {} failed to finalize draft on chain {}: {}", player_id, chain_id, e);
-                Ok(OperationResult {
-                    success: false,
-                    message: format!("Failed to finalize draft: {}", e),
-                    transaction_hash: None,
-                })
+    /// Concede a two-player draft on your turn, finishing it with your opponent as winner
+    async fn concede(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} conceding on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::Concede, "concede", "Draft conceded").await)
+    }
+
+    /// Submit `FinalizeDraft` for every listed room that finished without standings (admin only)
+    /// 
+    /// For operators cleaning up drafts nobody finalized. Rooms are checked
+    /// and finalized concurrently; each gets its own outcome, and rooms that
+    /// are unreadable, unfinished or already finalized are skipped.
+    async fn finalize_pending(&self, ctx: &Context<'_>, chain_ids: Vec<String>) -> Result<Vec<FinalizeOutcome>> {
+        let context = get_context(ctx);
+        require_admin(context)?;
+        context.require_player_owner()?;
+
+        info!("Admin {} finalizing pending drafts in {} rooms", context.get_player_id(), chain_ids.len());
+
+        let outcomes = stream::iter(chain_ids)
+            .map(|raw_chain_id| async move {
+                let skip = |reason: String| FinalizeOutcome {
+                    chain_id: raw_chain_id.clone(),
+                    result: None,
+                    skipped: Some(reason),
+                };
+
+                let chain_id = match raw_chain_id.parse::<ChainId>() {
+                    Ok(chain_id) => chain_id,
+                    Err(e) => return skip(format!("Invalid chain ID: {}", e)),
+                };
+                match self.query.load_draft_room(chain_id).await {
+                    Ok(room) if needs_finalizing(&room) => {}
+                    Ok(_) => return skip("Room is not waiting to be finalized".to_string()),
+                    Err(e) => return skip(e.message),
+                }
+
+                let result = self.execute_room_operation(context, chain_id, Operation::FinalizeDraft, "finalize draft", "Draft finalized successfully").await;
+                FinalizeOutcome {
+                    chain_id: raw_chain_id.clone(),
+                    result: Some(result),
+                    skipped: None,
+                }
+            })
+            .buffered(FINALIZE_CONCURRENCY)
+            .collect()
+            .await;
+
+        Ok(outcomes)
+    }
+
+    /// Play out the rest of a draft and return its final standings (admin only)
+    /// 
+    /// For load testing and demos. Every remaining turn is filled with the
+    /// creator's `ForceAutoPick`, which takes the player's top wishlist item or
+    /// else the highest-power one, and the draft is then finalized. The admin
+    /// must be the room's creator, and the room must be drafting without a
+    /// turn clock.
+    async fn simulate_draft(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<StandingData>> {
+        let context = get_context(ctx);
+        require_admin(context)?;
+        let player_owner = context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.query.load_draft_room(chain_id).await?;
+        let picks = simulation_picks(&room, player_owner)?;
+        info!("Admin {} simulating {} picks on chain: {}", context.get_player_id(), picks, chain_id);
+
+        for _ in 0..picks {
+            let result = self.execute_room_operation(context, chain_id, Operation::ForceAutoPick, "auto-pick", "Auto-picked").await;
+            if !result.success {
+                return Err(async_graphql::Error::new(result.message));
             }
         }
+
+        let result = self.execute_room_operation(context, chain_id, Operation::FinalizeDraft, "finalize draft", "Draft finalized successfully").await;
+        if !result.success {
+            return Err(async_graphql::Error::new(result.message));
+        }
+
+        let room = self.query.load_draft_room(chain_id).await?;
+        Ok(room_standings(&room))
+    }
+
+    /// Edit an item in the staged custom pool (creator only, before the draft starts)
+    /// 
+    /// Only the provided fields change. The contract validates the edited item
+    /// with the same rules used when the pool is built.
+    async fn update_pool_item(
+        &self,
+        ctx: &Context<'_>,
+        chain_id: String,
+        item_id: u8,
+        name: Option<String>,
+        power: Option<u32>,
+    ) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} updating pool item {} on chain: {}", player_id, item_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::UpdatePoolItem { id: item_id, name, power };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "update pool item", "Pool item updated successfully").await)
+    }
+
+    /// Add an item to the staged custom pool (creator only, before the draft starts)
+    /// 
+    /// Items are staged one at a time; `startDraft` uses the staged pool
+    /// instead of the default one when it is non-empty.
+    async fn add_pool_item(&self, ctx: &Context<'_>, chain_id: String, item: DraftItemInput) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} staging pool item {} on chain: {}", player_id, item.id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::AddPoolItem { item: to_contract_item(item) };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "add pool item", "Pool item added successfully").await)
+    }
+
+    /// Remove an item from the staged custom pool (creator only, before the draft starts)
+    async fn remove_pool_item(&self, ctx: &Context<'_>, chain_id: String, item_id: u8) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} removing pool item {} on chain: {}", player_id, item_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::RemovePoolItem { id: item_id };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "remove pool item", "Pool item removed successfully").await)
+    }
+
+    /// Set an explicit pick order (creator only, before the draft starts)
+    /// 
+    /// `order` must list every joined player exactly once; the draft uses it
+    /// in place of join order, e.g. for seeded tournaments.
+    async fn set_turn_order(&self, ctx: &Context<'_>, chain_id: String, order: Vec<String>) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} setting turn order on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let order = order.iter()
+            .map(|owner| owner.parse::<Owner>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid owner in turn order: {}", e)))?;
+
+        let operation = Operation::SetTurnOrder { order };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "set turn order", "Turn order set successfully").await)
+    }
+
+    /// Set the caller's preferred auto-pick order
+    /// 
+    /// When an auto-pick fires for this player, the first listed item still
+    /// in the pool is taken instead of the highest-power item.
+    async fn set_wishlist(&self, ctx: &Context<'_>, chain_id: String, item_ids: Vec<u8>) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} setting wishlist of {} items on chain: {}", player_id, item_ids.len(), chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::SetWishlist { item_ids };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "set wishlist", "Wishlist saved successfully").await)
     }
-}
\ No newline at end of file
+
+    /// Auto-pick for the player whose turn it is (creator only)
+    async fn force_auto_pick(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} forcing auto-pick on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::ForceAutoPick, "force auto-pick", "Auto-pick made successfully").await)
+    }
+
+    /// Pick the strongest item for the current player once their turn clock has run out
+    /// 
+    /// Anyone in the room may call this; the contract rejects it before the deadline.
+    async fn force_pick(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} forcing a timed-out pick on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::ForcePick, "force pick", "Timed-out pick made successfully").await)
+    }
+
+    /// Remove an idle player from a room (creator only, before the draft starts)
+    /// 
+    /// `player` is the target's Owner; the creator cannot kick themselves.
+    async fn kick_player(&self, ctx: &Context<'_>, chain_id: String, player: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} kicking {} from chain: {}", player_id, player, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let player = player.parse::<Owner>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid owner: {}", e)))?;
+
+        let operation = Operation::KickPlayer { player };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "kick player", "Player kicked successfully").await)
+    }
+
+    /// Reserve seats for invited owners (creator only, before the draft starts)
+    /// 
+    /// Reserved owners are seated immediately and don't need to join themselves.
+    async fn invite_and_reserve(&self, ctx: &Context<'_>, chain_id: String, owners: Vec<String>) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} reserving {} seats on chain: {}", player_id, owners.len(), chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let owners = owners.iter()
+            .map(|owner| owner.parse::<Owner>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid owner: {}", e)))?;
+
+        let operation = Operation::InviteAndReserve { owners };
+
+        Ok(self.execute_room_operation(context, chain_id, operation, "reserve seats", "Seats reserved successfully").await)
+    }
+
+    /// Flag a room for moderator review
+    /// 
+    /// Each player's report counts once per room; reporting again only
+    /// replaces the reason.
+    async fn report_room(&self, ctx: &Context<'_>, chain_id: String, reason: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let first_report = self.reports.report(&chain_id.to_string(), player_id, &reason);
+        info!("Player {} reported room on chain {} (new report: {})", player_id, chain_id, first_report);
+
+        let message = if first_report {
+            "Room reported"
+        } else {
+            "Report updated; this room was already reported by you"
+        };
+
+        Ok(operation_result(context, true, message))
+    }
+
+    /// Watch a room without taking a player seat
+    async fn spectate(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} spectating room on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::Spectate, "spectate", "Spectating room").await)
+    }
+
+    /// Take back the caller's most recent pick, returning the item to the pool
+    /// 
+    /// Only allowed before anyone else picks, and never for the draft's final
+    /// pick; the turn rewinds to the caller with the clock it had.
+    async fn undo_pick(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} undoing pick on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::UndoPick, "undo pick", "Pick undone").await)
+    }
+
+    /// Extend the caller's own turn clock (current player only, limited uses)
+    async fn request_more_time(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} requesting more time on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::RequestMoreTime, "request more time", "Turn extended").await)
+    }
+
+    /// Remove an item from the pool during the ban phase (current banner only)
+    async fn ban_item(&self, ctx: &Context<'_>, chain_id: String, item_id: u8) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} banning item {} on chain: {}", player_id, item_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::BanItem { item_id }, "ban item", "Item banned").await)
+    }
+
+    /// Change a waiting room's capacity (creator only)
+    /// 
+    /// The room tells the Lobby, so listings pick up the new capacity.
+    async fn set_max_players(&self, ctx: &Context<'_>, chain_id: String, max_players: u8) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} setting max players to {} on chain: {}", player_id, max_players, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::SetMaxPlayers { max_players };
+        Ok(self.execute_room_operation(context, chain_id, operation, "set max players", "Room capacity updated").await)
+    }
+
+    /// Reveal the seed behind a room's commitment (creator only, while waiting)
+    /// 
+    /// The room only accepts the seed its commitment was made to, and a
+    /// committed room cannot start until it has been revealed.
+    async fn reveal_seed(&self, ctx: &Context<'_>, chain_id: String, seed: u64) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} revealing the pool seed on chain: {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::RevealSeed { seed }, "reveal seed", "Seed revealed").await)
+    }
+
+    /// Suggest an item to a player in the room (players and spectators)
+    /// 
+    /// Suggestions are advisory: the target sees them via `suggestionsForMe`,
+    /// but nothing is picked on their behalf.
+    async fn suggest_to_player(&self, ctx: &Context<'_>, chain_id: String, player: String, item_id: u8) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} suggesting item {} to {} on chain: {}", player_id, item_id, player, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+        let player = player.parse::<Owner>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid player owner: {}", e)))?;
+
+        let operation = Operation::SuggestToPlayer { player, item_id };
+        Ok(self.execute_room_operation(context, chain_id, operation, "suggest item", "Suggestion sent").await)
+    }
+
+    /// Mark the caller ready, or not, to start (players only, while the room is waiting)
+    /// 
+    /// `startDraft` is refused until every seated player is ready.
+    async fn set_ready(&self, ctx: &Context<'_>, chain_id: String, ready: bool) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} setting ready={} on chain: {}", player_id, ready, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(self.execute_room_operation(context, chain_id, Operation::SetReady { ready }, "set ready", "Ready status updated").await)
+    }
+
+    /// Draft a player into the caller's squad (the captain whose turn it is, in a captains room)
+    /// 
+    /// Captains alternate until every other player is in a squad; the item
+    /// draft then begins.
+    async fn draft_captain_pick(&self, ctx: &Context<'_>, chain_id: String, player: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} drafting {} into their squad on chain: {}", player_id, player, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+        let player = player.parse::<Owner>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid player owner: {}", e)))?;
+
+        let operation = Operation::DraftCaptainPick { player };
+        Ok(self.execute_room_operation(context, chain_id, operation, "draft captain pick", "Player drafted into squad").await)
+    }
+
+    /// Rename a room in the Lobby (creator only, while the room is waiting)
+    async fn rename_room(&self, ctx: &Context<'_>, chain_id: String, new_name: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} renaming room {} to '{}'", player_id, chain_id, new_name);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::RenameRoom { chain_id, new_name };
+        Ok(self.execute_room_operation(context, self.default_chain_id, operation, "rename room", "Room renamed").await)
+    }
+
+    /// Ask the Lobby to re-read a room's status and player count from its chain
+    /// 
+    /// A repair path for Lobby listings that have drifted from the room; the
+    /// Lobby updates its metadata once the room's reply arrives.
+    async fn refresh_room(&self, ctx: &Context<'_>, chain_id: String) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        context.require_player_owner()?;
+
+        info!("Player {} requesting a Lobby refresh of room {}", player_id, chain_id);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let operation = Operation::RefreshRoom { chain_id };
+        Ok(self.execute_room_operation(context, self.default_chain_id, operation, "refresh room", "Room refresh requested").await)
+    }
+
+    /// Delist finished rooms whose auto-close delay has passed (admin only)
+    /// 
+    /// Only rooms created with `autoCloseAfterSecs` are delisted; their results
+    /// stay available through `roomResult`.
+    async fn cleanup_finished(&self, ctx: &Context<'_>) -> Result<OperationResult> {
+        let context = get_context(ctx);
+        require_admin(context)?;
+        context.require_player_owner()?;
+
+        info!("Admin {} cleaning up finished rooms", context.get_player_id());
+
+        Ok(self.execute_room_operation(context, self.default_chain_id, Operation::CleanupFinished, "clean up finished rooms", "Finished rooms cleaned up").await)
+    }
+
+    /// Queue a DraftRoom operation and return a job ID without waiting for the chain
+    /// 
+    /// The operation is submitted in the background; poll `jobStatus(jobId)`
+    /// for its result. Useful on slow networks where holding the request
+    /// open until confirmation times out.
+    async fn enqueue_operation(&self, ctx: &Context<'_>, chain_id: String, operation: OperationInput) -> Result<String> {
+        let context = get_context(ctx).clone();
+        context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+        let room_operation = to_room_operation(&operation)?;
+
+        let job_id = self.jobs.enqueue();
+        info!("Player {} queued {:?} on chain {} as job {}", context.get_player_id(), operation.kind, chain_id, job_id);
+
+        let mutation = self.clone();
+        let background_job_id = job_id.clone();
+        tokio::spawn(async move {
+            let result = mutation
+                .execute_room_operation(&context, chain_id, room_operation, "run queued operation", "Queued operation completed")
+                .await;
+            if result.success && operation.kind == OperationKind::JoinRoom {
+                mutation.room_index.record(context.get_player_id(), &chain_id.to_string());
+            }
+            mutation.jobs.complete(&background_job_id, result);
+        });
+
+        Ok(job_id)
+    }
+}
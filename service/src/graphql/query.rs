@@ -1,41 +1,98 @@
-use async_graphql::{Context, Object, Result};
+use async_graphql::{Context, Json, Object, Result};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
 use linera_client::ClientContext;
 use linera_core::data_types::{ApplicationId, ChainId};
 use linera_sdk::base::Owner;
 use serde_json;
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 use tracing::{error, info, warn};
 
-use crate::types::{DraftRoomState, RoomData, RoomStatus};
-use super::get_context;
+use crate::clock::{now_rfc3339, to_rfc3339};
+use crate::jobs::JobStore;
+use crate::reports::ReportStore;
+use crate::room_index::RoomIndex;
+use crate::identity::{decode_resume_token, derive_owner, encode_resume_token, player_id_to_owner};
+use crate::share_code::{encode_share_code, RoomTemplate};
+use crate::types::{Bootstrap, CurrentTurn, DraftItemInput, DraftResults, DraftRoomState, ExecutionCheck, JobStatus, LobbyExportEntry, OperationInput, OperationKind, PickValidation, PlayerBudget, PickEntry, PlayerComparison, RandomnessInfo, RoomResult, RoundComparison, PlayerInfo, PlayerPickIds, PlayerPicks, PlayerTimeRequests, PlayerScore, PlayerTotal, PoolValidation, PopularItem, ReportedRoom, ResumeData, RoomData, RoomOverview, RoomStatus, ShareInfo, SigningInfo, Squad, StaleRoom, StandingData, StartRequirements, TurnSlot};
+use super::{get_context, require_admin, to_contract_item};
 
 // Import contract types for state queries
 use livedraft_arena::{
     LiveDraftArena, 
+    LiveDraftArenaError,
     DraftRoomMetadata, 
     RoomStatus as ContractRoomStatus, 
     DraftRoom,
     Lobby,
-    draft_room::{DraftItem as ContractDraftItem, DraftStatus as ContractDraftStatus}
+    RoomOptions,
+    RoomResult as ContractRoomResult,
+    service::SCHEMA_VERSION,
+    DEFAULT_MAX_SPECTATORS,
+    draft_room::{budget_after_pick, check_first_pick_handicap, check_power_threshold, handicapped_item, DraftItem as ContractDraftItem, DraftRoomError, DraftStatus as ContractDraftStatus, MAX_TIME_REQUESTS, MAX_TOTAL_PICKS, PickRecord as ContractPickRecord, compute_standings, password_hash, snake_player_index, validate_pool, MIN_PLAYERS_TO_START}
 };
 
+/// Maximum number of rooms included in a lobby overview
+const LOBBY_OVERVIEW_MAX_ROOMS: usize = 50;
+
+/// Maximum number of DraftRoom chains queried at once for a lobby overview
+const LOBBY_OVERVIEW_CONCURRENCY: usize = 8;
+
 /// GraphQL Query root
+#[derive(Clone)]
 pub struct QueryRoot {
     client: ClientContext,
     app_id: ApplicationId,
     default_chain_id: ChainId,
+    room_index: Arc<RoomIndex>,
+    reports: Arc<ReportStore>,
+    jobs: Arc<JobStore>,
+    /// How long picks are withheld from non-participants, for streamed drafts
+    reveal_delay: Option<Duration>,
+    pick_sightings: Arc<PickSightings>,
+}
+
+/// When the service first saw each pick in each room
+///
+/// Pick records carry no timestamp, so reveal delays are measured from the
+/// first time this service observed a pick.
+#[derive(Default)]
+struct PickSightings {
+    seen_at: RwLock<HashMap<ChainId, Vec<DateTime<Utc>>>>,
+}
+
+impl PickSightings {
+    /// Record `now` for picks not seen before and return every pick's sighting time
+    fn observe(&self, chain_id: ChainId, pick_count: usize, now: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut seen_at = self.seen_at.write().expect("Pick sightings lock poisoned");
+        let room = seen_at.entry(chain_id).or_default();
+        room.resize(pick_count, now);
+        room.clone()
+    }
 }
 
 impl QueryRoot {
-    pub fn new(client: ClientContext, app_id: ApplicationId, default_chain_id: ChainId) -> Self {
+    pub fn new(client: ClientContext, app_id: ApplicationId, default_chain_id: ChainId, room_index: Arc<RoomIndex>, reports: Arc<ReportStore>, jobs: Arc<JobStore>) -> Self {
         Self {
             client,
             app_id,
             default_chain_id,
+            room_index,
+            reports,
+            jobs,
+            reveal_delay: None,
+            pick_sightings: Arc::new(PickSightings::default()),
         }
     }
 
+    /// Withhold picks from non-participants until they are `delay` old
+    pub fn with_reveal_delay(mut self, delay: Option<Duration>) -> Self {
+        self.reveal_delay = delay;
+        self
+    }
+
     /// Helper function to deserialize Lobby state from query response
     /// 
     /// Linera query responses contain the serialized application state.
@@ -64,12 +121,12 @@ impl QueryRoot {
             
             // Case 3: Direct rooms object (if Linera serializes MapView directly)
             if let Some(rooms_obj) = json_value.get("rooms") {
-                return self.extract_rooms_from_json_object(rooms_obj).await;
+                return Self::extract_rooms_from_json_object(rooms_obj);
             }
             
             // Case 4: The entire response is the rooms MapView
             if json_value.is_object() {
-                return self.extract_rooms_from_json_object(&json_value).await;
+                return Self::extract_rooms_from_json_object(&json_value);
             }
         }
         
@@ -106,7 +163,7 @@ impl QueryRoot {
     /// Extract rooms from Lobby JSON object
     async fn extract_rooms_from_lobby_json(&self, lobby_obj: &serde_json::Value) -> Result<HashMap<ChainId, DraftRoomMetadata>> {
         if let Some(rooms_obj) = lobby_obj.get("rooms") {
-            self.extract_rooms_from_json_object(rooms_obj).await
+            Self::extract_rooms_from_json_object(rooms_obj)
         } else {
             warn!("No 'rooms' field found in Lobby JSON object");
             Ok(HashMap::new())
@@ -114,7 +171,12 @@ impl QueryRoot {
     }
 
     /// Extract rooms from a JSON object representing the MapView
-    async fn extract_rooms_from_json_object(&self, rooms_obj: &serde_json::Value) -> Result<HashMap<ChainId, DraftRoomMetadata>> {
+    /// 
+    /// Keys are parsed with `ChainId::from_str`, so the map is keyed by the
+    /// chain ID itself rather than its spelling: a key re-parsed from
+    /// `ChainId::to_string` finds the same room. Two keys naming the same chain
+    /// are logged, and the later entry wins.
+    fn extract_rooms_from_json_object(rooms_obj: &serde_json::Value) -> Result<HashMap<ChainId, DraftRoomMetadata>> {
         let mut rooms = HashMap::new();
         
         if let Some(rooms_map) = rooms_obj.as_object() {
@@ -123,7 +185,9 @@ impl QueryRoot {
                 if let Ok(chain_id) = ChainId::from_str(chain_id_str) {
                     // Deserialize metadata
                     if let Ok(metadata) = serde_json::from_value::<DraftRoomMetadata>(metadata_value.clone()) {
-                        rooms.insert(chain_id, metadata);
+                        if rooms.insert(chain_id, metadata).is_some() {
+                            warn!("Duplicate Lobby entry for chain {} under key {}", chain_id, chain_id_str);
+                        }
                     } else {
                         warn!("Failed to deserialize room metadata for chain {}", chain_id_str);
                     }
@@ -152,6 +216,70 @@ impl QueryRoot {
         Ok(rooms)
     }
 
+    /// Extract per-item pick counts from the Lobby state, empty if it has none
+    ///
+    /// Accepts the same JSON shapes as `deserialize_lobby_state`; the MapView
+    /// may come as an object keyed by item name or as `[name, count]` pairs.
+    fn extract_item_popularity(response_bytes: &[u8]) -> HashMap<String, u32> {
+        let Ok(json_value) = serde_json::from_slice::<serde_json::Value>(response_bytes) else {
+            return HashMap::new();
+        };
+        let lobby_obj = json_value.get("Lobby")
+            .or_else(|| json_value.get("state").and_then(|state| state.get("Lobby")))
+            .unwrap_or(&json_value);
+        let Some(popularity) = lobby_obj.get("item_popularity") else {
+            return HashMap::new();
+        };
+
+        if let Some(counts) = popularity.as_object() {
+            counts.iter()
+                .filter_map(|(name, count)| Some((name.clone(), count.as_u64()? as u32)))
+                .collect()
+        } else if let Some(entries) = popularity.as_array() {
+            entries.iter()
+                .filter_map(|entry| {
+                    let entry = entry.as_array().filter(|entry| entry.len() == 2)?;
+                    Some((entry[0].as_str()?.to_string(), entry[1].as_u64()? as u32))
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        }
+    }
+
+    /// Extract finalized room results from the Lobby state, empty if it has none
+    ///
+    /// Accepts the same JSON shapes as `extract_item_popularity`.
+    fn extract_room_results(response_bytes: &[u8]) -> HashMap<ChainId, ContractRoomResult> {
+        let Ok(json_value) = serde_json::from_slice::<serde_json::Value>(response_bytes) else {
+            return HashMap::new();
+        };
+        let lobby_obj = json_value.get("Lobby")
+            .or_else(|| json_value.get("state").and_then(|state| state.get("Lobby")))
+            .unwrap_or(&json_value);
+        let Some(results) = lobby_obj.get("results") else {
+            return HashMap::new();
+        };
+
+        let parse = |chain_id: &str, result: &serde_json::Value| {
+            Some((ChainId::from_str(chain_id).ok()?, serde_json::from_value(result.clone()).ok()?))
+        };
+        if let Some(results) = results.as_object() {
+            results.iter()
+                .filter_map(|(chain_id, result)| parse(chain_id, result))
+                .collect()
+        } else if let Some(entries) = results.as_array() {
+            entries.iter()
+                .filter_map(|entry| {
+                    let entry = entry.as_array().filter(|entry| entry.len() == 2)?;
+                    parse(entry[0].as_str()?, &entry[1])
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        }
+    }
+
     /// Helper function to deserialize DraftRoom state from query response
     /// 
     /// For DraftRoom, this is LiveDraftArena::DraftRoom(DraftRoom) where DraftRoom
@@ -164,21 +292,31 @@ impl QueryRoot {
         if let Ok(json_value) = serde_json::from_slice::<serde_json::Value>(response_bytes) {
             info!("Successfully parsed DraftRoom response as JSON");
             
+            // Case 0: Response from the contract's GraphQL schema. Contracts deployed
+            // before the schema existed answer without a supported version, in which
+            // case we fall through to the legacy state parsing below.
+            if json_value.get("data").is_some() || json_value.get("errors").is_some() {
+                if let Some(draft_room_obj) = schema_draft_room(&json_value) {
+                    return Self::extract_draft_room_from_json(draft_room_obj, chain_id).await;
+                }
+                warn!("Chain {} has no supported contract schema, falling back to legacy state parsing", chain_id);
+            }
+            
             // Case 1: Direct LiveDraftArena enum serialization
             if let Some(draft_room_obj) = json_value.get("DraftRoom") {
-                return self.extract_draft_room_from_json(draft_room_obj, chain_id).await;
+                return Self::extract_draft_room_from_json(draft_room_obj, chain_id).await;
             }
             
             // Case 2: Wrapped in additional structure
             if let Some(state_obj) = json_value.get("state") {
                 if let Some(draft_room_obj) = state_obj.get("DraftRoom") {
-                    return self.extract_draft_room_from_json(draft_room_obj, chain_id).await;
+                    return Self::extract_draft_room_from_json(draft_room_obj, chain_id).await;
                 }
             }
             
             // Case 3: The entire response is the DraftRoom object
             if json_value.is_object() && json_value.get("players").is_some() {
-                return self.extract_draft_room_from_json(&json_value, chain_id).await;
+                return Self::extract_draft_room_from_json(&json_value, chain_id).await;
             }
         }
         
@@ -201,7 +339,7 @@ impl QueryRoot {
             if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(json_str) {
                 info!("Successfully parsed DraftRoom response as string-encoded JSON");
                 if let Some(draft_room_obj) = json_value.get("DraftRoom") {
-                    return self.extract_draft_room_from_json(draft_room_obj, chain_id).await;
+                    return Self::extract_draft_room_from_json(draft_room_obj, chain_id).await;
                 }
             }
         }
@@ -211,9 +349,9 @@ impl QueryRoot {
     }
 
     /// Extract DraftRoom data from JSON object
-    async fn extract_draft_room_from_json(&self, draft_room_obj: &serde_json::Value, chain_id: ChainId) -> Result<Option<DraftRoomStateData>> {
+    pub(super) async fn extract_draft_room_from_json(draft_room_obj: &serde_json::Value, chain_id: ChainId) -> Result<Option<DraftRoomStateData>> {
         // Extract all the DraftRoom fields with proper error handling
-        let players = self.extract_players_from_json(draft_room_obj)?;
+        let players = Self::extract_players_from_json(draft_room_obj)?;
         let max_players = draft_room_obj.get("max_players")
             .and_then(|v| v.as_u64())
             .unwrap_or(0) as u8;
@@ -226,9 +364,108 @@ impl QueryRoot {
         let max_rounds = draft_room_obj.get("max_rounds")
             .and_then(|v| v.as_u64())
             .unwrap_or(3) as u8;
-        let pool = self.extract_pool_from_json(draft_room_obj)?;
-        let status = self.extract_status_from_json(draft_room_obj)?;
-        let creator = self.extract_creator_from_json(draft_room_obj)?;
+        let pool = Self::extract_pool_from_json(draft_room_obj)?;
+        let staged_pool = Self::extract_items_from_json(draft_room_obj, "staged_pool")?;
+        let status = Self::extract_status_from_json(draft_room_obj)?;
+        let creator = Self::extract_creator_from_json(draft_room_obj)?;
+        let turn_order = draft_room_obj.get("turn_order")
+            .filter(|v| v.is_array())
+            .map(|_| Self::extract_owners_from_json(draft_room_obj, "turn_order"))
+            .transpose()?;
+        let reserved_players = Self::extract_owners_from_json(draft_room_obj, "reserved")?;
+        let pick_history = Self::extract_pick_records_from_json(draft_room_obj, "pick_history")?;
+        let bans = Self::extract_pick_records_from_json(draft_room_obj, "bans")?;
+        let ready = draft_room_obj.get("ready").and_then(|v| v.as_object());
+        let ready_players = players.iter()
+            .filter(|player| ready.and_then(|ready| ready.get(player.as_str())).and_then(|v| v.as_bool()) == Some(true))
+            .cloned()
+            .collect();
+        let captains = Self::extract_owners_from_json(draft_room_obj, "captains")?;
+        let squads = draft_room_obj.get("squads")
+            .and_then(|v| v.as_object())
+            .map(|squads| squads.iter()
+                .filter_map(|(captain, players)| {
+                    let players = players.as_array()?.iter().filter_map(|p| p.as_str().map(|p| p.to_string())).collect();
+                    Some((captain.clone(), players))
+                })
+                .collect())
+            .unwrap_or_default();
+        let suggestions = draft_room_obj.get("suggestions")
+            .and_then(|v| v.as_object())
+            .map(|suggested| suggested.iter()
+                .filter_map(|(player, ids)| {
+                    let ids = ids.as_array()?.iter().filter_map(|id| id.as_u64()).map(|id| id as u8).collect();
+                    Some((player.clone(), ids))
+                })
+                .collect())
+            .unwrap_or_default();
+        let version = draft_room_obj.get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let winner = draft_room_obj.get("winner")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        let conceded_by = draft_room_obj.get("conceded_by")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        let finalized = draft_room_obj.get("standings")
+            .and_then(|v| v.as_array())
+            .is_some_and(|standings| !standings.is_empty());
+        let reveal_after_round = draft_room_obj.get("reveal_after_round")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let final_round_min_power = draft_room_obj.get("final_round_min_power")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+        let round_weights = draft_room_obj.get("round_weights")
+            .and_then(|v| v.as_array())
+            .map(|weights| weights.iter().filter_map(|w| w.as_u64()).map(|w| w as u32).collect())
+            .unwrap_or_default();
+        let turn_duration_secs = draft_room_obj.get("turn_duration_secs")
+            .and_then(|v| v.as_u64());
+        let min_pick_interval_secs = draft_room_obj.get("min_pick_interval_secs")
+            .and_then(|v| v.as_u64());
+        let auto_close_after_secs = draft_room_obj.get("auto_close_after_secs")
+            .and_then(|v| v.as_u64());
+        let turn_deadline = draft_room_obj.get("turn_deadline")
+            .and_then(|v| v.as_u64())
+            .and_then(|micros| DateTime::from_timestamp_micros(micros as i64));
+        let turn_started_at = draft_room_obj.get("turn_started_at")
+            .and_then(|v| v.as_u64())
+            .and_then(|micros| DateTime::from_timestamp_micros(micros as i64));
+        let budget = draft_room_obj.get("budget")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32);
+        let spectators = Self::extract_owners_from_json(draft_room_obj, "spectators")?;
+        let max_spectators = draft_room_obj.get("max_spectators")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_MAX_SPECTATORS, |v| v as u8);
+        let time_requests_used = draft_room_obj.get("time_requests_used")
+            .and_then(|v| v.as_object())
+            .map(|used| used.iter()
+                .filter_map(|(player, count)| Some((player.clone(), count.as_u64()? as u8)))
+                .collect())
+            .unwrap_or_default();
+        let preview = draft_room_obj.get("preview")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let ban_rounds = draft_room_obj.get("ban_rounds")
+            .and_then(|v| v.as_u64())
+            .map_or(0, |v| v as u8);
+        let pool_size = draft_room_obj.get("pool_size")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u8);
+        let pool_seed = draft_room_obj.get("pool_seed")
+            .and_then(|v| v.as_u64());
+        let seed_commitment = draft_room_obj.get("seed_commitment")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
+        let handicap_first_pick = draft_room_obj.get("handicap_first_pick")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let password_hash = draft_room_obj.get("password_hash")
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string());
         
         let room_state = DraftRoomStateData {
             chain_id,
@@ -238,8 +475,40 @@ impl QueryRoot {
             round,
             max_rounds,
             pool,
+            staged_pool,
             status,
             creator,
+            turn_order,
+            reserved_players,
+            pick_history,
+            reveal_after_round,
+            final_round_min_power,
+            round_weights,
+            turn_duration_secs,
+            min_pick_interval_secs,
+            auto_close_after_secs,
+            turn_deadline,
+            turn_started_at,
+            budget,
+            spectators,
+            max_spectators,
+            time_requests_used,
+            preview,
+            ban_rounds,
+            bans,
+            pool_size,
+            pool_seed,
+            seed_commitment,
+            handicap_first_pick,
+            password_hash,
+            finalized,
+            winner,
+            conceded_by,
+            version,
+            suggestions,
+            ready_players,
+            captains,
+            squads,
         };
         
         info!("Successfully extracted DraftRoom state for chain {}: {} players, {} pool items", 
@@ -293,13 +562,7 @@ impl QueryRoot {
         if let Some(picks_map) = picks_obj.as_object() {
             if let Some(player_picks_value) = picks_map.get(&owner_str) {
                 if let Ok(contract_items) = serde_json::from_value::<Vec<ContractDraftItem>>(player_picks_value.clone()) {
-                    let service_items = contract_items.into_iter().map(|item| {
-                        crate::types::DraftItem {
-                            id: item.id as u32,
-                            name: item.name,
-                            power: item.power,
-                        }
-                    }).collect();
+                    let service_items = contract_items.into_iter().map(to_service_item).collect();
                     
                     info!("Found {} picks for player {}", service_items.len(), player_owner);
                     return Ok(service_items);
@@ -315,13 +578,7 @@ impl QueryRoot {
                         if let Some(key_str) = entry_array[0].as_str() {
                             if key_str == owner_str {
                                 if let Ok(contract_items) = serde_json::from_value::<Vec<ContractDraftItem>>(entry_array[1].clone()) {
-                                    let service_items = contract_items.into_iter().map(|item| {
-                                        crate::types::DraftItem {
-                                            id: item.id as u32,
-                                            name: item.name,
-                                            power: item.power,
-                                        }
-                                    }).collect();
+                                    let service_items = contract_items.into_iter().map(to_service_item).collect();
                                     
                                     info!("Found {} picks for player {} (array format)", service_items.len(), player_owner);
                                     return Ok(service_items);
@@ -338,8 +595,12 @@ impl QueryRoot {
     }
 
     // Helper methods for JSON extraction
-    fn extract_players_from_json(&self, draft_room_obj: &serde_json::Value) -> Result<Vec<String>> {
-        if let Some(players_array) = draft_room_obj.get("players").and_then(|v| v.as_array()) {
+    fn extract_players_from_json(draft_room_obj: &serde_json::Value) -> Result<Vec<String>> {
+        Self::extract_owners_from_json(draft_room_obj, "players")
+    }
+
+    fn extract_owners_from_json(draft_room_obj: &serde_json::Value, field: &str) -> Result<Vec<String>> {
+        if let Some(players_array) = draft_room_obj.get(field).and_then(|v| v.as_array()) {
             let players = players_array.iter()
                 .filter_map(|v| v.as_str().map(|s| s.to_string()))
                 .collect();
@@ -349,16 +610,16 @@ impl QueryRoot {
         }
     }
 
-    fn extract_pool_from_json(&self, draft_room_obj: &serde_json::Value) -> Result<Vec<crate::types::DraftItem>> {
-        if let Some(pool_array) = draft_room_obj.get("pool").and_then(|v| v.as_array()) {
+    fn extract_pool_from_json(draft_room_obj: &serde_json::Value) -> Result<Vec<crate::types::DraftItem>> {
+        Self::extract_items_from_json(draft_room_obj, "pool")
+    }
+
+    fn extract_items_from_json(draft_room_obj: &serde_json::Value, field: &str) -> Result<Vec<crate::types::DraftItem>> {
+        if let Some(pool_array) = draft_room_obj.get(field).and_then(|v| v.as_array()) {
             let mut pool = Vec::new();
             for item_value in pool_array {
                 if let Ok(contract_item) = serde_json::from_value::<ContractDraftItem>(item_value.clone()) {
-                    pool.push(crate::types::DraftItem {
-                        id: contract_item.id as u32,
-                        name: contract_item.name,
-                        power: contract_item.power,
-                    });
+                    pool.push(to_service_item(contract_item));
                 }
             }
             Ok(pool)
@@ -367,10 +628,12 @@ impl QueryRoot {
         }
     }
 
-    fn extract_status_from_json(&self, draft_room_obj: &serde_json::Value) -> Result<RoomStatus> {
+    fn extract_status_from_json(draft_room_obj: &serde_json::Value) -> Result<RoomStatus> {
         if let Some(status_str) = draft_room_obj.get("status").and_then(|v| v.as_str()) {
             match status_str {
                 "Waiting" => Ok(RoomStatus::Waiting),
+                "CaptainPicking" => Ok(RoomStatus::CaptainPicking),
+                "Banning" => Ok(RoomStatus::Banning),
                 "Drafting" => Ok(RoomStatus::Drafting),
                 "Finished" => Ok(RoomStatus::Finished),
                 _ => Ok(RoomStatus::Waiting),
@@ -380,192 +643,2897 @@ impl QueryRoot {
         }
     }
 
-    fn extract_creator_from_json(&self, draft_room_obj: &serde_json::Value) -> Result<Option<String>> {
+    fn extract_creator_from_json(draft_room_obj: &serde_json::Value) -> Result<Option<String>> {
         Ok(draft_room_obj.get("creator")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string()))
     }
-}
 
-/// Intermediate struct for DraftRoom state data
-struct DraftRoomStateData {
-    chain_id: ChainId,
-    players: Vec<String>,
-    max_players: u8,
-    current_turn: u8,
-    round: u8,
-    max_rounds: u8,
-    pool: Vec<crate::types::DraftItem>,
-    status: RoomStatus,
-    creator: Option<String>,
-}
+    fn extract_pick_records_from_json(draft_room_obj: &serde_json::Value, key: &str) -> Result<Vec<ContractPickRecord>> {
+        if let Some(history_array) = draft_room_obj.get(key).and_then(|v| v.as_array()) {
+            let history = history_array.iter()
+                .filter_map(|v| serde_json::from_value::<ContractPickRecord>(v.clone()).ok())
+                .collect();
+            Ok(history)
+        } else {
+            Ok(vec![])
+        }
+    }
 
-#[Object]
-impl QueryRoot {
-    /// Get all draft rooms from the Lobby chain
-    /// 
-    /// This queries the Lobby contract state and deserializes the MapView<ChainId, DraftRoomMetadata>
-    /// to return all created rooms with their metadata.
-    async fn rooms(&self, ctx: &Context<'_>) -> Result<Vec<RoomData>> {
-        let context = get_context(ctx);
-        let player_id = context.get_player_id();
-        
-        info!("Player {} querying rooms from Lobby on chain: {}", player_id, self.default_chain_id);
+    /// Query and deserialize the Lobby's room index
+    async fn load_lobby_rooms(&self) -> Result<HashMap<ChainId, DraftRoomMetadata>> {
+        let response = self.client.query_application(self.default_chain_id, self.app_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to query Lobby: {}", e)))?;
 
-        // Query the Lobby application state on the default chain
-        // This returns the serialized LiveDraftArena::Lobby state
-        match self.client.query_application(self.default_chain_id, self.app_id).await {
-            Ok(response) => {
-                info!("Player {} successfully queried Lobby state, deserializing rooms...", player_id);
-                
-                // Deserialize the Lobby state to extract the rooms MapView
-                match self.deserialize_lobby_state(&response).await {
-                    Ok(rooms_map) => {
-                        // Convert HashMap<ChainId, DraftRoomMetadata> to Vec<RoomData>
-                        let mut rooms = Vec::new();
-                        
-                        for (chain_id, metadata) in rooms_map {
-                            // Convert contract types to service types
-                            let status = match metadata.status {
-                                ContractRoomStatus::Waiting => RoomStatus::Waiting,
-                                ContractRoomStatus::Drafting => RoomStatus::Drafting,
-                                ContractRoomStatus::Finished => RoomStatus::Finished,
-                            };
-                            
-                            rooms.push(RoomData {
-                                chain_id: chain_id.to_string(),
-                                room_name: metadata.room_name,
-                                max_players: metadata.max_players,
-                                current_players: 0, // TODO: Query actual player count from DraftRoom
-                                status,
-                            });
-                        }
-                        
-                        info!("Player {} successfully retrieved {} rooms from Lobby", player_id, rooms.len());
-                        Ok(rooms)
-                    }
-                    Err(e) => {
-                        error!("Player {} failed to deserialize Lobby state: {}", player_id, e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Player {} failed to query Lobby state on chain {}: {}", player_id, self.default_chain_id, e);
-                Err(async_graphql::Error::new(format!("Failed to query Lobby: {}", e)))
+        self.deserialize_lobby_state(&response).await
+    }
+
+    /// How many of a room's picks `viewer` may see yet
+    /// 
+    /// Streamed drafts lag for non-participants; players always see current state.
+    fn revealed_picks(&self, chain_id: ChainId, room: &DraftRoomStateData, viewer: Option<&Owner>) -> usize {
+        let seen_at = self.pick_sightings.observe(chain_id, room.pick_history.len(), Utc::now());
+        match self.reveal_delay {
+            Some(delay) if !viewer.is_some_and(|viewer| room.players.contains(&viewer.to_string())) => {
+                revealed_pick_count(&seen_at, Utc::now(), delay)
             }
+            _ => room.pick_history.len(),
         }
     }
 
-    /// Get the state of a specific draft room
-    /// 
-    /// This queries a DraftRoom contract on its microchain and deserializes the complete
-    /// room state including players, turn order, card pool, and draft status.
-    async fn room_state(&self, ctx: &Context<'_>, chain_id: String) -> Result<Option<DraftRoomState>> {
-        let context = get_context(ctx);
-        let player_id = context.get_player_id();
-        
-        info!("Player {} querying DraftRoom state for chain: {}", player_id, chain_id);
+    /// Load a room with only the picks `viewer` may see yet, see `DraftRoomStateData::seen_by`
+    pub(super) async fn load_room_seen_by(&self, chain_id: ChainId, viewer: Option<&Owner>) -> Result<DraftRoomStateData> {
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, viewer);
+        Ok(room.seen_by(revealed, viewer))
+    }
 
-        // Parse chain ID for the DraftRoom microchain
-        let chain_id = chain_id.parse::<ChainId>()
-            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+    /// Query and deserialize a DraftRoom, treating missing state as an error
+    pub(super) async fn load_draft_room(&self, chain_id: ChainId) -> Result<DraftRoomStateData> {
+        let response = self.client.query_application(chain_id, self.app_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to query DraftRoom: {}", e)))?;
 
-        // Query the DraftRoom application state on the specified microchain
-        // This returns the serialized LiveDraftArena::DraftRoom state
-        match self.client.query_application(chain_id, self.app_id).await {
-            Ok(response) => {
-                info!("Player {} successfully queried DraftRoom state, deserializing...", player_id);
-                
-                // Deserialize the DraftRoom state
-                match self.deserialize_draft_room_state(&response, chain_id).await {
-                    Ok(Some(room_data)) => {
-                        // Convert to GraphQL response type
-                        let room_state = DraftRoomState {
-                            chain_id: room_data.chain_id.to_string(),
-                            players: room_data.players,
-                            max_players: room_data.max_players,
-                            current_turn: room_data.current_turn,
-                            round: room_data.round,
-                            max_rounds: room_data.max_rounds,
-                            pool: room_data.pool,
-                            status: room_data.status,
-                        };
-                        
-                        info!("Player {} successfully retrieved DraftRoom state for chain {}", player_id, chain_id);
-                        Ok(Some(room_state))
-                    }
-                    Ok(None) => {
-                        warn!("Player {} found no DraftRoom state for chain {}", player_id, chain_id);
-                        Ok(None)
-                    }
-                    Err(e) => {
-                        error!("Player {} failed to deserialize DraftRoom state for chain {}: {}", player_id, chain_id, e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Player {} failed to query DraftRoom state for chain {}: {}", player_id, chain_id, e);
-                Err(async_graphql::Error::new(format!("Failed to query DraftRoom: {}", e)))
-            }
+        self.deserialize_draft_room_state(&response, chain_id).await?
+            .ok_or_else(|| async_graphql::Error::new(format!("No DraftRoom state found for chain {}", chain_id)))
+    }
+}
+
+/// Extract the draft room from a contract schema response, if the schema is supported
+///
+/// Returns `None` for contracts that predate the schema (no `schemaVersion`,
+/// or a GraphQL error for the unknown field) or report an older version.
+fn schema_draft_room(response: &serde_json::Value) -> Option<&serde_json::Value> {
+    let data = response.get("data")?;
+    let version = data.get("schemaVersion")?.as_u64()?;
+    if version < SCHEMA_VERSION as u64 {
+        return None;
+    }
+    data.get("draftRoom").filter(|room| room.is_object())
+}
+
+/// Run the contract's pool validation over a prospective pool
+fn pool_validation(items: Vec<DraftItemInput>) -> PoolValidation {
+    let items: Vec<ContractDraftItem> = items.into_iter().map(to_contract_item).collect();
+    let errors: Vec<String> = validate_pool(&items).iter().map(ToString::to_string).collect();
+
+    PoolValidation {
+        valid: errors.is_empty(),
+        errors,
+    }
+}
+
+/// Convert a contract DraftItem into its GraphQL representation
+fn to_service_item(item: ContractDraftItem) -> crate::types::DraftItem {
+    crate::types::DraftItem {
+        id: item.id,
+        name: item.name,
+        power: item.power,
+        quantity: item.quantity,
+        tags: item.tags,
+        cost: item.cost,
+        weight: item.weight,
+    }
+}
+
+/// Convert a GraphQL DraftItem back into the contract type, for shared rule checks
+fn from_service_item(item: &crate::types::DraftItem) -> ContractDraftItem {
+    ContractDraftItem {
+        id: item.id,
+        name: item.name.clone(),
+        power: item.power,
+        quantity: item.quantity,
+        tags: item.tags.clone(),
+        cost: item.cost,
+        weight: item.weight,
+    }
+}
+
+/// A room's settings as a template for `shareCode`
+///
+/// The creator's staged pool stands in for the custom pool, so edits made
+/// after `CreateRoom` travel too. Seeds are left out, so a shared room draws
+/// its own pool, and so are any password and captains, who are this room's
+/// players.
+fn room_template(room: &DraftRoomStateData) -> RoomTemplate {
+    RoomTemplate {
+        max_players: room.max_players,
+        options: RoomOptions {
+            reveal_after_round: room.reveal_after_round,
+            final_round_min_power: room.final_round_min_power,
+            round_weights: room.round_weights.clone(),
+            turn_duration_secs: room.turn_duration_secs,
+            budget: room.budget,
+            max_spectators: room.max_spectators,
+            preview: room.preview,
+            ban_rounds: room.ban_rounds,
+            pool_size: room.pool_size,
+            pool_seed: None,
+            seed_commitment: None,
+            custom_pool: room.staged_pool.iter().map(from_service_item).collect(),
+            handicap_first_pick: room.handicap_first_pick,
+            password_hash: None,
+            min_pick_interval_secs: room.min_pick_interval_secs,
+            captains: Vec::new(),
+            auto_close_after_secs: room.auto_close_after_secs,
+        },
+    }
+}
+
+/// Each player's visible picks among the first `revealed`, as item ids
+fn pick_id_board(room: &DraftRoomStateData, revealed: usize, viewer: Option<&Owner>) -> Vec<PlayerPickIds> {
+    let mut board: Vec<PlayerPickIds> = room.players.iter()
+        .map(|player| PlayerPickIds { player: player.clone(), item_ids: vec![] })
+        .collect();
+
+    for record in visible_picks(&room.pick_history[..revealed], room.round, viewer, room.reveal_after_round) {
+        let player = record.player.to_string();
+        if let Some(entry) = board.iter_mut().find(|entry| entry.player == player) {
+            entry.item_ids.push(record.item.id);
         }
     }
+    board
+}
 
-    /// Get current user's picks in a room
-    /// 
-    /// This queries the DraftRoom state and extracts the picks MapView<Owner, Vec<DraftItem>>
-    /// to return only the cards picked by the current player.
-    async fn my_picks(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<crate::types::DraftItem>> {
-        let context = get_context(ctx);
-        let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
-        
-        info!("Player {} querying their picks in DraftRoom {}", player_id, chain_id);
+/// One page of a room's pool, ordered by item id
+fn pool_page(room: &DraftRoomStateData, offset: usize, limit: usize) -> Vec<crate::types::DraftItem> {
+    let mut pool = room.pool.clone();
+    pool.sort_by_key(|item| item.id);
+    pool.into_iter().skip(offset).take(limit).collect()
+}
 
-        // Parse chain ID for the DraftRoom microchain
-        let chain_id = chain_id.parse::<ChainId>()
-            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+/// One page of the picks `viewer` may see among the first `revealed`, in draft order
+///
+/// Hidden picks take no place on a page but keep their pick number, so numbers
+/// stay the same once they are revealed.
+fn picks_page(room: &DraftRoomStateData, revealed: usize, viewer: Option<&Owner>, offset: usize, limit: usize) -> Vec<PickEntry> {
+    room.pick_history[..revealed]
+        .iter()
+        .zip(1..)
+        .filter(|(record, _)| is_visible(record, room.round, viewer, room.reveal_after_round))
+        .skip(offset)
+        .take(limit)
+        .map(|(record, pick_number)| PickEntry {
+            pick_number,
+            player: record.player.to_string(),
+            round: record.round,
+            item: to_service_item(record.item.clone()),
+        })
+        .collect()
+}
 
-        // Query the DraftRoom application state to access the picks MapView
-        match self.client.query_application(chain_id, self.app_id).await {
-            Ok(response) => {
-                info!("Player {} successfully queried DraftRoom for picks, extracting player data...", player_id);
-                
-                // Extract picks for the specific player from the MapView<Owner, Vec<DraftItem>>
-                match self.extract_player_picks(&response, player_owner).await {
-                    Ok(picks) => {
-                        info!("Player {} successfully retrieved {} picks from DraftRoom {}", player_id, picks.len(), chain_id);
-                        Ok(picks)
-                    }
-                    Err(e) => {
-                        error!("Player {} failed to extract picks from DraftRoom {}: {}", player_id, chain_id, e);
-                        Err(e)
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Player {} failed to query DraftRoom {} for picks: {}", player_id, chain_id, e);
-                Err(async_graphql::Error::new(format!("Failed to query picks: {}", e)))
-            }
+/// Pool items carrying `tag`
+fn items_with_tag(pool: Vec<crate::types::DraftItem>, tag: &str) -> Vec<crate::types::DraftItem> {
+    pool.into_iter()
+        .filter(|item| item.tags.iter().any(|item_tag| item_tag == tag))
+        .collect()
+}
+
+/// Convert contract room status to the GraphQL enum
+fn to_service_status(status: ContractRoomStatus) -> RoomStatus {
+    match status {
+        ContractRoomStatus::Waiting => RoomStatus::Waiting,
+        ContractRoomStatus::Drafting => RoomStatus::Drafting,
+        ContractRoomStatus::Finished => RoomStatus::Finished,
+    }
+}
+
+/// Pool items the caller can pick with their remaining budget
+/// 
+/// Empty unless it is the caller's turn; without a salary cap the whole pool
+/// is affordable.
+fn affordable_items(room: &DraftRoomStateData, caller: &Owner) -> Vec<crate::types::DraftItem> {
+    let caller = caller.to_string();
+    if current_player(room).as_deref() != Some(caller.as_str()) {
+        return Vec::new();
+    }
+
+    let remaining = room.remaining_budgets()
+        .into_iter()
+        .find(|budget| budget.player == caller)
+        .map_or(u32::MAX, |budget| budget.remaining);
+
+    room.pool.iter().filter(|item| item.cost <= remaining).cloned().collect()
+}
+
+/// Whether a room has played all its rounds but never had standings recorded
+pub(super) fn needs_finalizing(room: &DraftRoomStateData) -> bool {
+    room.status == RoomStatus::Finished && !room.finalized
+}
+
+/// Resolve the player whose turn it is, mirroring the contract's snake order
+pub(super) fn current_player(room: &DraftRoomStateData) -> Option<String> {
+    if room.status != RoomStatus::Drafting || room.players.is_empty() {
+        return None;
+    }
+
+    let index = snake_player_index(room.round, room.current_turn, room.players.len() as u8);
+    room.players.get(index as usize).cloned()
+}
+
+/// The current player, and whether it is `caller`
+fn current_turn(room: &DraftRoomStateData, caller: Option<&Owner>) -> CurrentTurn {
+    let player = current_player(room);
+    let is_my_turn = caller.is_some_and(|caller| player.as_deref() == Some(caller.to_string().as_str()));
+    CurrentTurn { player, is_my_turn }
+}
+
+/// Every pick slot of the draft in order, using the contract's snake order
+/// 
+/// Slots before the current turn are marked completed, as are all slots once
+/// the draft has finished; a room still waiting has none completed.
+fn turn_schedule(room: &DraftRoomStateData) -> Vec<TurnSlot> {
+    let players = room.effective_turn_order();
+    if players.is_empty() {
+        return Vec::new();
+    }
+    let player_count = players.len() as u8;
+
+    let mut slots = Vec::with_capacity(players.len() * room.max_rounds as usize);
+    for round in 1..=room.max_rounds {
+        for pick_in_round in 0..player_count {
+            let completed = match room.status {
+                RoomStatus::Finished => true,
+                RoomStatus::Drafting => (round, pick_in_round) < (room.round, room.current_turn),
+                _ => false,
+            };
+            let index = snake_player_index(round, pick_in_round, player_count);
+            slots.push(TurnSlot {
+                round,
+                pick_in_round,
+                player: players[index as usize].clone(),
+                completed,
+            });
         }
     }
+    slots
+}
 
-    /// Get player information (for debugging/display)
-    async fn player_info(&self, ctx: &Context<'_>) -> Result<String> {
-        let context = get_context(ctx);
-        let player_id = context.get_player_id();
-        let player_owner = context.get_player_owner();
-        
-        Ok(format!(
-            "Player ID: {} | Owner: {}",
-            player_id,
-            player_owner
-        ))
+/// The `limit` most-picked items, ties broken by name
+fn popular_items(counts: HashMap<String, u32>, limit: usize) -> Vec<PopularItem> {
+    let mut items: Vec<PopularItem> = counts.into_iter()
+        .map(|(name, pick_count)| PopularItem { name, pick_count })
+        .collect();
+    items.sort_by(|a, b| b.pick_count.cmp(&a.pick_count).then_with(|| a.name.cmp(&b.name)));
+    items.truncate(limit);
+    items
+}
+
+/// Build a room's share details from its Lobby metadata
+///
+/// Private rooms are the password-protected ones, so the two flags agree.
+fn share_info(chain_id: ChainId, metadata: DraftRoomMetadata) -> ShareInfo {
+    ShareInfo {
+        chain_id: chain_id.to_string(),
+        room_name: metadata.room_name,
+        requires_password: metadata.is_private,
+        is_private: metadata.is_private,
+        status: to_service_status(metadata.status),
     }
+}
 
-    /// Health check endpoint
-    async fn health(&self) -> Result<String> {
-        Ok("Service is running".to_string())
+/// A Lobby result as returned by `roomResult`
+fn room_result(chain_id: ChainId, result: ContractRoomResult) -> RoomResult {
+    let finalized_at = DateTime::from_timestamp_micros(result.finalized_at.micros() as i64).unwrap_or_default();
+    RoomResult {
+        chain_id: chain_id.to_string(),
+        room_name: result.room_name,
+        winner: result.winner.map(|winner| winner.to_string()),
+        finalized_at: to_rfc3339(finalized_at),
+    }
+}
+
+/// Lobby listing entry for a room, with the player count the room last reported
+fn room_data(chain_id: ChainId, metadata: DraftRoomMetadata) -> RoomData {
+    RoomData {
+        chain_id: chain_id.to_string(),
+        room_name: metadata.room_name,
+        max_players: metadata.max_players,
+        current_players: metadata.current_players, // As last reported by the room
+        status: to_service_status(metadata.status),
+        is_private: metadata.is_private,
+    }
+}
+
+/// Lobby listings in name order, ties broken by chain ID, optionally only those with `status`
+fn sorted_rooms(lobby_rooms: HashMap<ChainId, DraftRoomMetadata>, status: Option<RoomStatus>) -> Vec<RoomData> {
+    let mut rooms: Vec<RoomData> = lobby_rooms.into_iter()
+        .map(|(chain_id, metadata)| room_data(chain_id, metadata))
+        .filter(|room| status.map_or(true, |status| room.status == status))
+        .collect();
+    rooms.sort_by(|a, b| a.room_name.cmp(&b.room_name).then_with(|| a.chain_id.cmp(&b.chain_id)));
+    rooms
+}
+
+/// One page of the sorted, filtered Lobby listings
+fn page_rooms(
+    lobby_rooms: HashMap<ChainId, DraftRoomMetadata>,
+    status: Option<RoomStatus>,
+    offset: usize,
+    limit: usize,
+) -> Vec<RoomData> {
+    sorted_rooms(lobby_rooms, status).into_iter().skip(offset).take(limit).collect()
+}
+
+/// Rooms whose Lobby metadata names `creator`, in any status, sorted by name
+fn created_rooms(lobby_rooms: HashMap<ChainId, DraftRoomMetadata>, creator: &Owner) -> Vec<RoomData> {
+    let mut rooms: Vec<RoomData> = lobby_rooms.into_iter()
+        .filter(|(_, metadata)| metadata.creator.as_ref() == Some(creator))
+        .map(|(chain_id, metadata)| room_data(chain_id, metadata))
+        .collect();
+    rooms.sort_by(|a, b| a.room_name.cmp(&b.room_name).then_with(|| a.chain_id.cmp(&b.chain_id)));
+    rooms
+}
+
+/// Assemble the bootstrap payload, keeping at most `limit` rooms in name order
+fn bootstrap(
+    player: PlayerInfo,
+    lobby_rooms: HashMap<ChainId, DraftRoomMetadata>,
+    limit: Option<usize>,
+    server_time: String,
+) -> Bootstrap {
+    let mut rooms = sorted_rooms(lobby_rooms, None);
+    if let Some(limit) = limit {
+        rooms.truncate(limit);
+    }
+
+    Bootstrap {
+        player,
+        rooms,
+        server_time,
+    }
+}
+
+/// When the Lobby recorded a room's creation
+fn created_at(metadata: &DraftRoomMetadata) -> DateTime<Utc> {
+    DateTime::from_timestamp_micros(metadata.created_at.micros() as i64).unwrap_or_default()
+}
+
+/// Waiting rooms created more than `older_than` before `now`, oldest first
+fn stale_candidates(
+    lobby_rooms: HashMap<ChainId, DraftRoomMetadata>,
+    now: DateTime<Utc>,
+    older_than: Duration,
+) -> Vec<(ChainId, DraftRoomMetadata)> {
+    let mut candidates: Vec<_> = lobby_rooms.into_iter()
+        .filter(|(_, metadata)| {
+            matches!(metadata.status, ContractRoomStatus::Waiting) && now - created_at(metadata) > older_than
+        })
+        .collect();
+    candidates.sort_by_key(|(chain_id, metadata)| (created_at(metadata), chain_id.to_string()));
+    candidates
+}
+
+/// Report entry for a stale room with its live player count
+fn stale_room(chain_id: ChainId, metadata: DraftRoomMetadata, current_players: u8, now: DateTime<Utc>) -> StaleRoom {
+    let created = created_at(&metadata);
+    StaleRoom {
+        room: RoomData {
+            current_players,
+            ..room_data(chain_id, metadata)
+        },
+        created_at: to_rfc3339(created),
+        age_secs: (now - created).num_seconds().max(0) as u64,
+    }
+}
+
+/// Lobby export entry for a room, with its live state when the chain answered
+fn export_entry(chain_id: ChainId, metadata: DraftRoomMetadata, room: Option<&DraftRoomStateData>) -> LobbyExportEntry {
+    let created_at = to_rfc3339(created_at(&metadata));
+    LobbyExportEntry {
+        room: RoomData {
+            current_players: room.map_or(metadata.current_players, |room| room.players.len() as u8),
+            ..room_data(chain_id, metadata)
+        },
+        creator: room.and_then(|room| room.creator.clone()),
+        created_at,
     }
-}
\ No newline at end of file
+}
+
+/// Combine Lobby metadata with a room's live state for the lobby overview
+fn room_overview(chain_id: ChainId, metadata: DraftRoomMetadata, room: &DraftRoomStateData) -> RoomOverview {
+    RoomOverview {
+        room: RoomData {
+            chain_id: chain_id.to_string(),
+            room_name: metadata.room_name,
+            max_players: metadata.max_players,
+            current_players: room.players.len() as u8,
+            status: to_service_status(metadata.status),
+            is_private: metadata.is_private,
+        },
+        players: room.players.clone(),
+        current_player: current_player(room),
+    }
+}
+
+/// Work out whether a room can be started and whether the caller may start it
+fn start_requirements(room: &DraftRoomStateData, caller: &Owner) -> StartRequirements {
+    let current_players = room.players.len() as u8;
+    StartRequirements {
+        min_players: MIN_PLAYERS_TO_START,
+        current_players,
+        can_start: room.status == RoomStatus::Waiting
+            && current_players >= MIN_PLAYERS_TO_START
+            && room.ready_players.len() == room.players.len(),
+        is_creator: room.creator.as_deref() == Some(caller.to_string().as_str()),
+    }
+}
+
+/// Apply every `PickItem` guard, in the contract's order, to a pick by `caller`
+///
+/// Returns the error the contract would reject the pick with, if any.
+fn pick_error(room: &DraftRoomStateData, item_id: Option<u8>, caller: &str) -> Option<DraftRoomError> {
+    if room.status != RoomStatus::Drafting {
+        return Some(DraftRoomError::NotDrafting);
+    }
+    if room.spectators.iter().any(|spectator| spectator == caller) {
+        return Some(DraftRoomError::Spectating);
+    }
+    if current_player(room).as_deref() != Some(caller) {
+        return Some(DraftRoomError::NotYourTurn);
+    }
+
+    let Some(item) = item_id.and_then(|item_id| room.pool.iter().find(|item| item.id == item_id)) else {
+        return Some(DraftRoomError::ItemNotFound);
+    };
+    let item = from_service_item(item);
+    let pool: Vec<ContractDraftItem> = room.pool.iter().map(from_service_item).collect();
+    if let Err(error) = check_power_threshold(&pool, &item, room.round, room.max_rounds, room.final_round_min_power) {
+        return Some(error);
+    }
+    if let Err(error) = check_first_pick_handicap(&pool, &item, room.round, room.current_turn, room.handicap_first_pick) {
+        return Some(error);
+    }
+
+    let remaining = room.remaining_budgets()
+        .into_iter()
+        .find(|budget| budget.player == caller)
+        .map(|budget| budget.remaining);
+    budget_after_pick(remaining, item.cost).err()
+}
+
+/// Items suggested to `player` that are still in the pool, oldest suggestion first
+fn suggestions_for(room: &DraftRoomStateData, player: &Owner) -> Vec<crate::types::DraftItem> {
+    room.suggestions
+        .get(&player.to_string())
+        .into_iter()
+        .flatten()
+        .filter_map(|id| room.pool.iter().find(|item| item.id == *id).cloned())
+        .collect()
+}
+
+/// The pool seed and the creator's commitment to it, as recorded by the room
+fn randomness_info(room: &DraftRoomStateData) -> RandomnessInfo {
+    RandomnessInfo {
+        seed: room.pool_seed.map(|seed| seed.to_string()),
+        commitment: room.seed_commitment.clone(),
+    }
+}
+
+/// Predict whether `caller` picking `item_id` would succeed
+fn pick_validation(room: &DraftRoomStateData, item_id: u8, caller: &Owner) -> PickValidation {
+    match pick_error(room, Some(item_id), &caller.to_string()) {
+        Some(error) => PickValidation { valid: false, reason: Some(error.to_string()) },
+        None => PickValidation { valid: true, reason: None },
+    }
+}
+
+/// Apply the contract's guards to predict whether `operation` would be accepted
+///
+/// `room` is `None` for the Lobby chain. Reasons reuse the contract's own error
+/// messages so they match what a failed submission would report.
+fn check_operation(room: Option<&DraftRoomStateData>, operation: &OperationInput, caller: &Owner) -> ExecutionCheck {
+    let reject = |reason: String| ExecutionCheck { allowed: false, reason: Some(reason) };
+
+    let room = match (room, operation.kind) {
+        (None, OperationKind::CreateRoom) => return ExecutionCheck { allowed: true, reason: None },
+        (None, _) => return reject(LiveDraftArenaError::WrongContractType("Lobby").to_string()),
+        (Some(_), OperationKind::CreateRoom) => {
+            return reject(LiveDraftArenaError::WrongContractType("DraftRoom").to_string())
+        }
+        (Some(room), _) => room,
+    };
+
+    let caller = caller.to_string();
+    let error = match operation.kind {
+        OperationKind::CreateRoom => None,
+        OperationKind::JoinRoom => {
+            if room.status != RoomStatus::Waiting {
+                Some(DraftRoomError::NotWaiting)
+            } else if room.players.len() >= room.max_players as usize {
+                Some(DraftRoomError::RoomFull)
+            } else if room.players.contains(&caller) {
+                Some(DraftRoomError::AlreadyJoined)
+            } else if room.spectators.contains(&caller) {
+                Some(DraftRoomError::Spectating)
+            } else if room.password_hash.as_ref().is_some_and(|expected| {
+                operation.password.as_deref().map(password_hash).as_ref() != Some(expected)
+            }) {
+                Some(DraftRoomError::WrongPassword)
+            } else {
+                None
+            }
+        }
+        OperationKind::StartDraft => {
+            let total_picks = u32::from(room.max_players) * u32::from(room.max_rounds);
+            if room.creator.as_deref() != Some(caller.as_str()) {
+                Some(DraftRoomError::NotCreator)
+            } else if room.spectators.contains(&caller) {
+                Some(DraftRoomError::Spectating)
+            } else if room.status != RoomStatus::Waiting {
+                Some(DraftRoomError::NotWaiting)
+            } else if room.players.len() < MIN_PLAYERS_TO_START as usize {
+                Some(DraftRoomError::NotEnoughPlayers)
+            } else if !room.players.contains(&caller) {
+                Some(DraftRoomError::CreatorNotJoined)
+            } else if room.ready_players.len() < room.players.len() {
+                Some(DraftRoomError::PlayersNotReady)
+            } else if total_picks > MAX_TOTAL_PICKS {
+                Some(DraftRoomError::DraftTooLarge(total_picks))
+            } else {
+                None
+            }
+        }
+        OperationKind::PickItem => pick_error(room, operation.item_id, &caller),
+        OperationKind::FinalizeDraft => {
+            (room.status != RoomStatus::Finished).then_some(DraftRoomError::DraftNotFinished)
+        }
+    };
+
+    match error {
+        Some(error) => reject(error.to_string()),
+        None => ExecutionCheck { allowed: true, reason: None },
+    }
+}
+
+/// Apply the room's reveal rule to the pick history for a given viewer
+///
+/// With `reveal_after_round`, other players' picks only become visible once
+/// their round is complete (strictly less than the current round). The
+/// viewer's own picks are always visible; an anonymous viewer (`None`) only
+/// sees completed rounds.
+fn visible_picks<'a>(
+    pick_history: &'a [ContractPickRecord],
+    current_round: u8,
+    viewer: Option<&Owner>,
+    reveal_after_round: bool,
+) -> impl Iterator<Item = &'a ContractPickRecord> + 'a {
+    let viewer = viewer.copied();
+    pick_history.iter().filter(move |record| is_visible(record, current_round, viewer.as_ref(), reveal_after_round))
+}
+
+/// Whether `viewer` may see a pick: with `reveal_after_round`, others' picks stay hidden until the round ends
+fn is_visible(record: &ContractPickRecord, current_round: u8, viewer: Option<&Owner>, reveal_after_round: bool) -> bool {
+    !reveal_after_round || Some(&record.player) == viewer || record.round < current_round
+}
+
+/// How many picks, oldest first, were first seen at least `delay` before `now`
+/// 
+/// Sightings only grow over the history, so the revealed picks are a prefix.
+fn revealed_pick_count(seen_at: &[DateTime<Utc>], now: DateTime<Utc>, delay: Duration) -> usize {
+    let cutoff = now - delay;
+    seen_at.iter().take_while(|seen| **seen <= cutoff).count()
+}
+
+/// Head-to-head power of players `a` and `b`, overall and per round
+///
+/// Both must be seated in the room. Only picks visible to `viewer` among the
+/// first `revealed` are counted.
+fn compare_players(room: &DraftRoomStateData, revealed: usize, a: &str, b: &str, viewer: Option<&Owner>) -> Result<PlayerComparison> {
+    let seated = |player: &str| -> Result<Owner> {
+        let owner = Owner::from_str(player)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid player owner {}: {}", player, e)))?;
+        if !room.players.contains(&owner.to_string()) {
+            return Err(async_graphql::Error::new(format!("{} is not a player in this room", owner)));
+        }
+        Ok(owner)
+    };
+    let (a, b) = (seated(a)?, seated(b)?);
+
+    let mut a_score = PlayerScore { player: a.to_string(), total_power: 0, pick_count: 0 };
+    let mut b_score = PlayerScore { player: b.to_string(), total_power: 0, pick_count: 0 };
+    let mut rounds: Vec<RoundComparison> = (1..=room.max_rounds)
+        .map(|round| RoundComparison { round, a_power: 0, b_power: 0, delta: 0 })
+        .collect();
+
+    for record in visible_picks(&room.pick_history[..revealed], room.round, viewer, room.reveal_after_round) {
+        let power = u64::from(record.item.power);
+        let round = rounds.iter_mut().find(|round| round.round == record.round);
+        if record.player == a {
+            a_score.total_power += power;
+            a_score.pick_count += 1;
+            if let Some(round) = round {
+                round.a_power += power;
+            }
+        } else if record.player == b {
+            b_score.total_power += power;
+            b_score.pick_count += 1;
+            if let Some(round) = round {
+                round.b_power += power;
+            }
+        }
+    }
+    for round in &mut rounds {
+        round.delta = round.a_power as i64 - round.b_power as i64;
+    }
+
+    Ok(PlayerComparison {
+        delta: a_score.total_power as i64 - b_score.total_power as i64,
+        a: a_score,
+        b: b_score,
+        rounds,
+    })
+}
+
+/// Scores for the requested players, or every player in the room if none are requested
+///
+/// Requested owners that aren't in the room are skipped; malformed ones are an error.
+/// Only picks visible to `viewer` among the first `revealed` are counted.
+fn player_scores(room: &DraftRoomStateData, revealed: usize, requested: &[String], viewer: Option<&Owner>) -> Result<Vec<PlayerScore>> {
+    let players = if requested.is_empty() {
+        room.players.clone()
+    } else {
+        let mut players = Vec::new();
+        for player in requested {
+            let owner = Owner::from_str(player)
+                .map_err(|e| async_graphql::Error::new(format!("Invalid player owner {}: {}", player, e)))?
+                .to_string();
+            if room.players.contains(&owner) && !players.contains(&owner) {
+                players.push(owner);
+            }
+        }
+        players
+    };
+
+    let mut scores: Vec<PlayerScore> = players.into_iter()
+        .map(|player| PlayerScore { player, total_power: 0, pick_count: 0 })
+        .collect();
+
+    for record in visible_picks(&room.pick_history[..revealed], room.round, viewer, room.reveal_after_round) {
+        let player = record.player.to_string();
+        if let Some(score) = scores.iter_mut().find(|score| score.player == player) {
+            score.total_power += u64::from(record.item.power);
+            score.pick_count += 1;
+        }
+    }
+
+    Ok(scores)
+}
+
+/// Current standings with raw and round-weighted power, best first
+///
+/// Uses the contract's own scoring so live standings match what
+/// `FinalizeDraft` records.
+pub(super) fn room_standings(room: &DraftRoomStateData) -> Vec<StandingData> {
+    let players: Vec<Owner> = room.players.iter()
+        .filter_map(|player| Owner::from_str(player).ok())
+        .collect();
+
+    compute_standings(&players, &room.pick_history, &room.round_weights)
+        .into_iter()
+        .map(|standing| StandingData {
+            player: standing.player.to_string(),
+            raw_power: standing.raw_power,
+            weighted_power: standing.weighted_power,
+        })
+        .collect()
+}
+
+/// Standings scored from the picks `viewer` may see among the first `revealed`
+fn visible_standings(mut room: DraftRoomStateData, revealed: usize, viewer: Option<&Owner>) -> Vec<StandingData> {
+    room.pick_history = visible_picks(&room.pick_history[..revealed], room.round, viewer, room.reveal_after_round)
+        .cloned()
+        .collect();
+    room_standings(&room)
+}
+
+/// The items `player` picked that `viewer` may see among the first `revealed` picks
+fn visible_player_picks(room: &DraftRoomStateData, revealed: usize, player: &Owner, viewer: Option<&Owner>) -> Vec<crate::types::DraftItem> {
+    visible_picks(&room.pick_history[..revealed], room.round, viewer, room.reveal_after_round)
+        .filter(|record| record.player == *player)
+        .map(|record| to_service_item(record.item.clone()))
+        .collect()
+}
+
+/// Compare the owner a session acts as with the key its operations are signed by
+fn signing_info(player_owner: Option<&Owner>, signer: Option<String>) -> SigningInfo {
+    let player_owner = player_owner.map(ToString::to_string);
+    SigningInfo {
+        signs_as_player: player_owner.is_some() && player_owner == signer,
+        player_owner,
+        signer,
+    }
+}
+
+/// Each player's total picked power and the recorded winner
+fn draft_results(room: &DraftRoomStateData) -> DraftResults {
+    let totals = room.players
+        .iter()
+        .map(|player| PlayerTotal {
+            player: player.clone(),
+            total_power: room.pick_history
+                .iter()
+                .filter(|record| record.player.to_string() == *player)
+                .map(|record| u64::from(record.item.power))
+                .sum(),
+        })
+        .collect();
+    DraftResults { totals, winner: room.winner.clone(), conceded_by: room.conceded_by.clone() }
+}
+
+/// When a drafting room will finish if every remaining pick uses the full turn clock
+/// 
+/// Remaining picks are capped by what is left in the pool. `None` unless the
+/// room is drafting and has a turn duration.
+fn estimated_end_time(room: &DraftRoomStateData, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if room.status != RoomStatus::Drafting {
+        return None;
+    }
+    let turn_duration_secs = room.turn_duration_secs?;
+
+    let remaining_secs = remaining_picks(room).saturating_mul(turn_duration_secs);
+    now.checked_add_signed(Duration::seconds(i64::try_from(remaining_secs).ok()?))
+}
+
+/// Picks still to be made, capped by the items left in the pool
+fn remaining_picks(room: &DraftRoomStateData) -> u64 {
+    let total_picks = room.players.len() as u64 * room.max_rounds as u64;
+    let pool_remaining: u64 = room.pool.iter().map(|item| item.quantity as u64).sum();
+    total_picks
+        .saturating_sub(room.pick_history.len() as u64)
+        .min(pool_remaining)
+}
+
+/// How many auto-picks `simulateDraft` needs to finish a room started by `caller`
+/// 
+/// Simulation drives the draft with the creator's `ForceAutoPick`, so the
+/// caller must be the creator, the room must be drafting, and it must have
+/// no turn clock (forced picks would wait for each turn to expire).
+pub(super) fn simulation_picks(room: &DraftRoomStateData, caller: &Owner) -> Result<u64> {
+    if room.creator.as_deref() != Some(caller.to_string().as_str()) {
+        return Err(async_graphql::Error::new(DraftRoomError::NotCreator.to_string()));
+    }
+    if room.status != RoomStatus::Drafting {
+        return Err(async_graphql::Error::new(DraftRoomError::NotDrafting.to_string()));
+    }
+    if room.turn_duration_secs.is_some() {
+        return Err(async_graphql::Error::new("Rooms with a turn clock cannot be simulated"));
+    }
+    Ok(remaining_picks(room))
+}
+
+/// Intermediate struct for DraftRoom state data
+pub(super) struct DraftRoomStateData {
+    chain_id: ChainId,
+    players: Vec<String>,
+    max_players: u8,
+    current_turn: u8,
+    pub(super) round: u8,
+    max_rounds: u8,
+    pool: Vec<crate::types::DraftItem>,
+    staged_pool: Vec<crate::types::DraftItem>,
+    pub(super) status: RoomStatus,
+    creator: Option<String>,
+    turn_order: Option<Vec<String>>,
+    reserved_players: Vec<String>,
+    pick_history: Vec<ContractPickRecord>,
+    reveal_after_round: bool,
+    final_round_min_power: Option<u32>,
+    round_weights: Vec<u32>,
+    turn_duration_secs: Option<u64>,
+    min_pick_interval_secs: Option<u64>,
+    auto_close_after_secs: Option<u64>,
+    turn_deadline: Option<DateTime<Utc>>,
+    turn_started_at: Option<DateTime<Utc>>,
+    budget: Option<u32>,
+    spectators: Vec<String>,
+    max_spectators: u8,
+    time_requests_used: HashMap<String, u8>,
+    preview: bool,
+    ban_rounds: u8,
+    bans: Vec<ContractPickRecord>,
+    pool_size: Option<u8>,
+    pool_seed: Option<u64>,
+    seed_commitment: Option<String>,
+    handicap_first_pick: bool,
+    /// Checked by `canExecute` for private rooms; never returned to clients
+    password_hash: Option<String>,
+    /// Whether `FinalizeDraft` has recorded standings
+    finalized: bool,
+    /// Set by `FinalizeDraft`
+    winner: Option<String>,
+    /// Set by `Concede`; the other player is the winner
+    conceded_by: Option<String>,
+    /// Bumped by the contract after every accepted operation
+    pub(super) version: u64,
+    /// Advisory item ids suggested to each player, oldest first
+    suggestions: HashMap<String, Vec<u8>>,
+    /// Players who have marked themselves ready, in seat order
+    ready_players: Vec<String>,
+    /// The two captains in a captains room, first pick first
+    captains: Vec<String>,
+    /// Players each captain has drafted, keyed by captain
+    squads: HashMap<String, Vec<String>>,
+}
+
+impl DraftRoomStateData {
+    /// The order players will pick in: the creator's override until the draft
+    /// starts (when the contract folds it into `players`), otherwise join order
+    fn effective_turn_order(&self) -> Vec<String> {
+        match (&self.turn_order, self.status) {
+            (Some(order), RoomStatus::Waiting) => order.clone(),
+            _ => self.players.clone(),
+        }
+    }
+
+    /// Each player's remaining budget, or empty if the room has no salary cap
+    /// 
+    /// Derived from the pick history the same way the contract spends it.
+    fn remaining_budgets(&self) -> Vec<PlayerBudget> {
+        let Some(budget) = self.budget else {
+            return Vec::new();
+        };
+
+        self.players
+            .iter()
+            .map(|player| {
+                let spent: u32 = self.pick_history
+                    .iter()
+                    .filter(|record| record.player.to_string() == *player)
+                    .map(|record| record.item.cost)
+                    .sum();
+                PlayerBudget {
+                    player: player.clone(),
+                    remaining: budget.saturating_sub(spent),
+                }
+            })
+            .collect()
+    }
+
+    /// `RequestMoreTime` extensions each player has left
+    fn time_requests_remaining(&self) -> Vec<PlayerTimeRequests> {
+        self.players
+            .iter()
+            .map(|player| PlayerTimeRequests {
+                player: player.clone(),
+                remaining: MAX_TIME_REQUESTS.saturating_sub(self.time_requests_used.get(player).copied().unwrap_or(0)),
+            })
+            .collect()
+    }
+
+    /// Bans still to be made before the pick phase starts
+    fn bans_remaining(&self) -> u32 {
+        if matches!(self.status, RoomStatus::Drafting | RoomStatus::Finished) {
+            return 0;
+        }
+        (u32::from(self.ban_rounds) * self.players.len() as u32).saturating_sub(self.bans.len() as u32)
+    }
+
+    /// Whether the pick about to be made is the last of the draft
+    /// 
+    /// Counted from the round and turn rather than the pick history, so
+    /// preview rooms (which keep no picks) report it too.
+    fn almost_done(&self) -> bool {
+        if self.status != RoomStatus::Drafting {
+            return false;
+        }
+        let players = self.players.len() as u32;
+        let made = u32::from(self.round.saturating_sub(1)) * players + u32::from(self.current_turn);
+        (players * u32::from(self.max_rounds)).saturating_sub(made) == 1
+    }
+
+    /// The item the pick about to be made may not take, if the first-pick handicap applies
+    fn restricted_item(&self) -> Option<u8> {
+        let first_pick = self.status == RoomStatus::Drafting && self.round == 1 && self.current_turn == 0;
+        if !(self.handicap_first_pick && first_pick) {
+            return None;
+        }
+        let pool: Vec<ContractDraftItem> = self.pool.iter().map(from_service_item).collect();
+        handicapped_item(&pool)
+    }
+
+    /// The room as `viewer` may see it, given the first `revealed` picks
+    ///
+    /// Picks not revealed yet, and picks the reveal rule hides from `viewer`,
+    /// leave the pick history and go back into the pool, so the pool and
+    /// budgets don't give them away. Picks held back by the reveal delay also
+    /// rewind the turn to just after the last revealed pick.
+    pub(super) fn seen_by(mut self, revealed: usize, viewer: Option<&Owner>) -> Self {
+        let delayed = revealed < self.pick_history.len();
+        let (round, reveal_after_round) = (self.round, self.reveal_after_round);
+        let (shown, hidden): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pick_history)
+            .into_iter()
+            .enumerate()
+            .partition(|(index, record)| *index < revealed && is_visible(record, round, viewer, reveal_after_round));
+        self.pick_history = shown.into_iter().map(|(_, record)| record).collect();
+
+        // Put each hidden copy back where the item was, as undoing the pick does
+        for (_, record) in hidden {
+            match self.pool.iter_mut().find(|item| item.id == record.item.id) {
+                Some(item) => item.quantity += 1,
+                None => {
+                    let index = self.pool.iter().position(|item| item.id > record.item.id).unwrap_or(self.pool.len());
+                    self.pool.insert(index, to_service_item(record.item));
+                }
+            }
+        }
+        if !delayed {
+            return self;
+        }
+
+        // The live clock, result and later turns all belong to picks not revealed yet
+        if !self.players.is_empty() {
+            let players = self.players.len();
+            self.round = (revealed / players) as u8 + 1;
+            self.current_turn = (revealed % players) as u8;
+        }
+        if self.status == RoomStatus::Finished {
+            self.status = RoomStatus::Drafting;
+        }
+        self.turn_started_at = None;
+        self.turn_deadline = None;
+        self.finalized = false;
+        self.winner = None;
+        self
+    }
+
+    /// Convert to the GraphQL response type
+    pub(super) fn into_room_state(self) -> DraftRoomState {
+        let turn_order = self.effective_turn_order();
+        let budgets = self.remaining_budgets();
+        let time_requests_remaining = self.time_requests_remaining();
+        let bans_remaining = self.bans_remaining();
+        let almost_done = self.almost_done();
+        let restricted_item_id = self.restricted_item();
+        DraftRoomState {
+            chain_id: self.chain_id.to_string(),
+            players: self.players,
+            max_players: self.max_players,
+            current_turn: self.current_turn,
+            round: self.round,
+            max_rounds: self.max_rounds,
+            pool: self.pool,
+            staged_pool: self.staged_pool,
+            turn_order,
+            reserved_players: self.reserved_players,
+            status: self.status,
+            reveal_after_round: self.reveal_after_round,
+            final_round_min_power: self.final_round_min_power,
+            turn_duration_secs: self.turn_duration_secs,
+            min_pick_interval_secs: self.min_pick_interval_secs,
+            auto_close_after_secs: self.auto_close_after_secs,
+            turn_deadline: self.turn_deadline.map(to_rfc3339),
+            turn_started_at: self.turn_started_at.map(to_rfc3339),
+            budgets,
+            spectator_count: self.spectators.len() as u8,
+            max_spectators: self.max_spectators,
+            time_requests_remaining,
+            preview: self.preview,
+            bans_remaining,
+            almost_done,
+            banned_items: self.bans.into_iter().map(|ban| to_service_item(ban.item)).collect(),
+            pool_seed: self.pool_seed,
+            handicap_first_pick: self.handicap_first_pick,
+            restricted_item_id,
+            is_private: self.password_hash.is_some(),
+            ready_players: self.ready_players,
+            squads: self.captains.iter()
+                .map(|captain| Squad {
+                    captain: captain.clone(),
+                    players: self.squads.get(captain).cloned().unwrap_or_default(),
+                })
+                .collect(),
+            captains: self.captains,
+            version: self.version,
+        }
+    }
+}
+
+#[Object]
+impl QueryRoot {
+    /// Get draft rooms from the Lobby chain, a page at a time
+    /// 
+    /// This queries the Lobby contract state and deserializes the MapView<ChainId, DraftRoomMetadata>
+    /// to return created rooms with their metadata. Rooms are sorted by name,
+    /// optionally filtered by `status`, then `offset` are skipped and at most
+    /// `limit` returned. With no arguments every room is returned. Use
+    /// `totalCount` for the number of rooms across all pages.
+    async fn rooms(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        status: Option<RoomStatus>,
+    ) -> Result<Vec<RoomData>> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        
+        info!("Player {} querying rooms from Lobby on chain: {}", player_id, self.default_chain_id);
+
+        // Query the Lobby application state on the default chain
+        // This returns the serialized LiveDraftArena::Lobby state
+        match self.client.query_application(self.default_chain_id, self.app_id).await {
+            Ok(response) => {
+                info!("Player {} successfully queried Lobby state, deserializing rooms...", player_id);
+                
+                // Deserialize the Lobby state to extract the rooms MapView
+                match self.deserialize_lobby_state(&response).await {
+                    Ok(rooms_map) => {
+                        let offset = offset.unwrap_or(0) as usize;
+                        let limit = limit.unwrap_or(u32::MAX) as usize;
+                        let rooms = page_rooms(rooms_map, status, offset, limit);
+                        
+                        info!("Player {} successfully retrieved {} rooms from Lobby", player_id, rooms.len());
+                        Ok(rooms)
+                    }
+                    Err(e) => {
+                        error!("Player {} failed to deserialize Lobby state: {}", player_id, e);
+                        Err(e)
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Player {} failed to query Lobby state on chain {}: {}", player_id, self.default_chain_id, e);
+                Err(async_graphql::Error::new(format!("Failed to query Lobby: {}", e)))
+            }
+        }
+    }
+
+    /// Number of rooms `rooms` would list across all pages, optionally only those with `status`
+    async fn total_count(&self, status: Option<RoomStatus>) -> Result<u32> {
+        let lobby_rooms = self.load_lobby_rooms().await?;
+        Ok(sorted_rooms(lobby_rooms, status).len() as u32)
+    }
+
+    /// Get the details needed to build a shareable invite link for a room
+    async fn share_info(&self, chain_id: String) -> Result<ShareInfo> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let metadata = self.load_lobby_rooms().await?
+            .remove(&chain_id)
+            .ok_or_else(|| async_graphql::Error::new(format!("Room {} not found", chain_id)))?;
+
+        Ok(share_info(chain_id, metadata))
+    }
+
+    /// Encode a room's settings into a share code for `createFromShareCode`
+    ///
+    /// The code carries the room's capacity and options, not its players or
+    /// picks, so it can be posted as a reusable draft template.
+    async fn share_code(&self, chain_id: String) -> Result<String> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(encode_share_code(&room_template(&room)))
+    }
+
+    /// Get every room with its live roster and whose turn it is
+    ///
+    /// Fans out to each DraftRoom chain with bounded concurrency. Rooms whose
+    /// chain cannot be queried are left out rather than failing the listing.
+    async fn lobby_overview(&self, ctx: &Context<'_>) -> Result<Vec<RoomOverview>> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+
+        let mut rooms: Vec<_> = self.load_lobby_rooms().await?.into_iter().collect();
+        rooms.sort_by(|(_, a), (_, b)| a.room_name.cmp(&b.room_name));
+        rooms.truncate(LOBBY_OVERVIEW_MAX_ROOMS);
+
+        let overview: Vec<RoomOverview> = stream::iter(rooms)
+            .map(|(chain_id, metadata)| async move {
+                match self.load_draft_room(chain_id).await {
+                    Ok(room) => Some(room_overview(chain_id, metadata, &room)),
+                    Err(e) => {
+                        warn!("Skipping unreachable room {} in lobby overview: {}", chain_id, e.message);
+                        None
+                    }
+                }
+            })
+            .buffered(LOBBY_OVERVIEW_CONCURRENCY)
+            .filter_map(|entry| async move { entry })
+            .collect()
+            .await;
+
+        info!("Player {} retrieved lobby overview of {} rooms", player_id, overview.len());
+        Ok(overview)
+    }
+
+    /// Get every room the current player created, whatever its status
+    /// 
+    /// Read from the creator recorded in Lobby metadata, so it works across
+    /// service instances and for rooms the creator never joined.
+    async fn my_created_rooms(&self, ctx: &Context<'_>) -> Result<Vec<RoomData>> {
+        let context = get_context(ctx);
+        let player_owner = context.require_player_owner()?;
+
+        let lobby_rooms = self.load_lobby_rooms().await?;
+        Ok(created_rooms(lobby_rooms, player_owner))
+    }
+
+    /// Get the rooms the current player has joined
+    ///
+    /// Looks up the service's player room index and keeps only rooms where the
+    /// player is still in the on-chain roster.
+    async fn my_rooms(&self, ctx: &Context<'_>) -> Result<Vec<RoomData>> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        let owner_str = context.require_player_owner()?.to_string();
+
+        let indexed = self.room_index.rooms_for(player_id);
+        if indexed.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let lobby_rooms = self.load_lobby_rooms().await?;
+        let mut rooms = Vec::new();
+
+        for chain_id_str in indexed {
+            let Ok(chain_id) = ChainId::from_str(&chain_id_str) else {
+                warn!("Ignoring malformed chain ID {} in room index", chain_id_str);
+                continue;
+            };
+            let Some(metadata) = lobby_rooms.get(&chain_id) else {
+                continue;
+            };
+
+            match self.load_draft_room(chain_id).await {
+                Ok(room) if room.players.contains(&owner_str) => {
+                    rooms.push(room_overview(chain_id, metadata.clone(), &room).room);
+                }
+                Ok(_) => info!("Player {} is no longer in indexed room {}", player_id, chain_id),
+                Err(e) => warn!("Skipping unreachable indexed room {}: {}", chain_id, e.message),
+            }
+        }
+
+        Ok(rooms)
+    }
+
+    /// Get the state of a specific draft room
+    /// 
+    /// This queries a DraftRoom contract on its microchain and deserializes the complete
+    /// room state including players, turn order, card pool, and draft status.
+    /// Under `REVEAL_DELAY_SECS`, non-participants see the room as it stood
+    /// after the picks revealed to them so far, like `allPicks`.
+    async fn room_state(&self, ctx: &Context<'_>, chain_id: String) -> Result<Option<DraftRoomState>> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        let player_owner = context.get_player_owner();
+        
+        info!("Player {} querying DraftRoom state for chain: {}", player_id, chain_id);
+
+        // Parse chain ID for the DraftRoom microchain
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        // Query the DraftRoom application state on the specified microchain
+        // This returns the serialized LiveDraftArena::DraftRoom state
+        match self.client.query_application(chain_id, self.app_id).await {
+            Ok(response) => {
+                info!("Player {} successfully queried DraftRoom state, deserializing...", player_id);
+                
+                // Deserialize the DraftRoom state
+                match self.deserialize_draft_room_state(&response, chain_id).await {
+                    Ok(Some(room_data)) => {
+                        // Convert to GraphQL response type, holding back picks the caller may not see yet
+                        let revealed = self.revealed_picks(chain_id, &room_data, player_owner);
+                        let room_state = room_data.seen_by(revealed, player_owner).into_room_state();
+                        
+                        info!("Player {} successfully retrieved DraftRoom state for chain {}", player_id, chain_id);
+                        Ok(Some(room_state))
+                    }
+                    Ok(None) => {
+                        warn!("Player {} found no DraftRoom state for chain {}", player_id, chain_id);
+                        Ok(None)
+                    }
+                    Err(e) => {
+                        error!("Player {} failed to deserialize DraftRoom state for chain {}: {}", player_id, chain_id, e);
+                        Err(e)
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Player {} failed to query DraftRoom state for chain {}: {}", player_id, chain_id, e);
+                Err(async_graphql::Error::new(format!("Failed to query DraftRoom: {}", e)))
+            }
+        }
+    }
+
+    /// Get current user's picks in a room
+    /// 
+    /// This queries the DraftRoom state and extracts the picks MapView<Owner, Vec<DraftItem>>
+    /// to return only the cards picked by the current player.
+    async fn my_picks(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        let player_owner = context.require_player_owner()?;
+        
+        info!("Player {} querying their picks in DraftRoom {}", player_id, chain_id);
+
+        // Parse chain ID for the DraftRoom microchain
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        // Query the DraftRoom application state to access the picks MapView
+        match self.client.query_application(chain_id, self.app_id).await {
+            Ok(response) => {
+                info!("Player {} successfully queried DraftRoom for picks, extracting player data...", player_id);
+                
+                // Extract picks for the specific player from the MapView<Owner, Vec<DraftItem>>
+                match self.extract_player_picks(&response, player_owner).await {
+                    Ok(picks) => {
+                        info!("Player {} successfully retrieved {} picks from DraftRoom {}", player_id, picks.len(), chain_id);
+                        Ok(picks)
+                    }
+                    Err(e) => {
+                        error!("Player {} failed to extract picks from DraftRoom {}: {}", player_id, chain_id, e);
+                        Err(e)
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Player {} failed to query DraftRoom {} for picks: {}", player_id, chain_id, e);
+                Err(async_graphql::Error::new(format!("Failed to query picks: {}", e)))
+            }
+        }
+    }
+
+    /// Get every player's picks in a room, subject to the room's reveal rule
+    /// 
+    /// With `REVEAL_DELAY_SECS` set, non-participants only see picks older
+    /// than the delay, so a stream overlay can't be used to snipe.
+    async fn all_picks(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<PlayerPicks>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+
+        let mut all_picks: Vec<PlayerPicks> = room.players.iter()
+            .map(|player| PlayerPicks { player: player.clone(), items: vec![] })
+            .collect();
+
+        for record in visible_picks(&room.pick_history[..revealed], room.round, player_owner, room.reveal_after_round) {
+            let player = record.player.to_string();
+            if let Some(entry) = all_picks.iter_mut().find(|entry| entry.player == player) {
+                entry.items.push(to_service_item(record.item.clone()));
+            }
+        }
+
+        Ok(all_picks)
+    }
+
+    /// Get every player's picks as item ids only
+    /// 
+    /// A lightweight draft board for clients that resolve item details from a
+    /// cached pool. Follows the same reveal rules as `allPicks`.
+    async fn pick_ids(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<PlayerPickIds>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        Ok(pick_id_board(&room, revealed, player_owner))
+    }
+
+    /// Get one page of a room's pool, ordered by item id
+    /// 
+    /// For large pools that `roomState` would return in full. `offset` items
+    /// are skipped and at most `limit` returned; with neither, the whole pool.
+    async fn pool_page(&self, ctx: &Context<'_>, chain_id: String, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_room_seen_by(chain_id, context.get_player_owner()).await?;
+        Ok(pool_page(&room, offset.unwrap_or(0) as usize, limit.unwrap_or(u32::MAX) as usize))
+    }
+
+    /// Get one page of a room's picks in draft order, subject to the same reveal rules as `allPicks`
+    /// 
+    /// Each pick carries its number in the whole draft, so pages can be merged
+    /// even when some picks are still hidden from the caller.
+    async fn picks_page(&self, ctx: &Context<'_>, chain_id: String, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<PickEntry>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        Ok(picks_page(&room, revealed, player_owner, offset.unwrap_or(0) as usize, limit.unwrap_or(u32::MAX) as usize))
+    }
+
+    /// Get the items in a room's pool that carry a tag
+    async fn pool_by_tag(&self, ctx: &Context<'_>, chain_id: String, tag: String) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_room_seen_by(chain_id, context.get_player_owner()).await?;
+        Ok(items_with_tag(room.pool, &tag))
+    }
+
+    /// Get the pool items the caller can still afford on their turn
+    async fn affordable_items(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let player_owner = context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(affordable_items(&room, player_owner))
+    }
+
+    /// Get the room's standings, ranked by round-weighted power
+    /// 
+    /// Standings are scored from all picks once the draft has finished;
+    /// before then they only count picks visible to the caller.
+    async fn standings(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<StandingData>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        Ok(visible_standings(room, revealed, player_owner))
+    }
+
+    /// Get each player's total power and the winner once the draft is finalized
+    /// 
+    /// Totals count every pick, so they are only exposed once the draft has finished.
+    async fn draft_results(&self, chain_id: String) -> Result<DraftResults> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        if room.status != RoomStatus::Finished {
+            return Err(async_graphql::Error::new(DraftRoomError::DraftNotFinished.to_string()));
+        }
+        Ok(draft_results(&room))
+    }
+
+    /// Get total power and pick count for some or all players in a room
+    /// 
+    /// An empty `players` list returns the whole room. Scores only count picks
+    /// the caller can see under the room's reveal rule.
+    async fn scores(&self, ctx: &Context<'_>, chain_id: String, players: Vec<String>) -> Result<Vec<PlayerScore>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        player_scores(&room, revealed, &players, player_owner)
+    }
+
+    /// Compare two players' picked power, in total and round by round
+    /// 
+    /// `delta` is `a` minus `b`. Like `scores`, only picks the caller can see
+    /// under the room's reveal rule are counted.
+    async fn compare_players(&self, ctx: &Context<'_>, chain_id: String, a: String, b: String) -> Result<PlayerComparison> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        compare_players(&room, revealed, &a, &b, player_owner)
+    }
+
+    /// Get a single player's picks in a room, subject to the room's reveal rule
+    async fn player_picks(&self, ctx: &Context<'_>, chain_id: String, player: String) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let player_owner = context.get_player_owner();
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+        let player = Owner::from_str(&player)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid player owner: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        let revealed = self.revealed_picks(chain_id, &room, player_owner);
+        Ok(visible_player_picks(&room, revealed, &player, player_owner))
+    }
+
+    /// Get the items others in the room have suggested to the caller
+    /// 
+    /// Advisory only; items that have since left the pool are omitted.
+    async fn suggestions_for_me(&self, ctx: &Context<'_>, chain_id: String) -> Result<Vec<crate::types::DraftItem>> {
+        let context = get_context(ctx);
+        let player_owner = context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(suggestions_for(&room, player_owner))
+    }
+
+    /// Get the seed a room's pool is sampled with and the creator's commitment to it
+    /// 
+    /// Anyone can check that `sha256(seed as big-endian u64)` matches the
+    /// commitment and re-run the sampling to reproduce the pool.
+    async fn randomness_info(&self, chain_id: String) -> Result<RandomnessInfo> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(randomness_info(&room))
+    }
+
+    /// Projected finish time (RFC 3339, UTC) if every remaining pick uses the full turn clock
+    /// 
+    /// Returns `null` unless the room is drafting with a turn duration set.
+    async fn estimated_end_time(&self, chain_id: String) -> Result<Option<String>> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(estimated_end_time(&room, Utc::now()).map(to_rfc3339))
+    }
+
+    /// Get every pick slot of a room's draft in order, for rendering a draft board
+    /// 
+    /// Computed from the current players and format; slots already played are
+    /// marked completed.
+    async fn turn_schedule(&self, chain_id: String) -> Result<Vec<TurnSlot>> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(turn_schedule(&room))
+    }
+
+    /// Get whose turn it is in a room, resolved with the contract's snake order
+    /// 
+    /// `player` is null unless the room is drafting; `isMyTurn` is relative to
+    /// the requesting player.
+    async fn current_player(&self, ctx: &Context<'_>, chain_id: String) -> Result<CurrentTurn> {
+        let context = get_context(ctx);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(current_turn(&room, context.get_player_owner()))
+    }
+
+    /// Get what is still needed before the draft in a room can start
+    async fn start_requirements(&self, ctx: &Context<'_>, chain_id: String) -> Result<StartRequirements> {
+        let context = get_context(ctx);
+        let player_owner = context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(start_requirements(&room, player_owner))
+    }
+
+    /// Check whether the caller's operation would be accepted on a chain, without submitting it
+    /// 
+    /// The Lobby chain only accepts `CreateRoom`; on DraftRoom chains the
+    /// room's current state is checked with the same guards the contract uses.
+    async fn can_execute(&self, ctx: &Context<'_>, chain_id: String, operation: OperationInput) -> Result<ExecutionCheck> {
+        let context = get_context(ctx);
+        let player_owner = context.require_player_owner()?;
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        if chain_id == self.default_chain_id {
+            return Ok(check_operation(None, &operation, player_owner));
+        }
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(check_operation(Some(&room), &operation, player_owner))
+    }
+
+    /// Check whether the caller picking `itemId` would succeed right now
+    /// 
+    /// Applies every contract `PickItem` guard (status, turn, item in pool,
+    /// final-round power threshold, budget) to live state, so the UI can warn
+    /// before a pick is submitted and fails.
+    async fn validate_pick(&self, ctx: &Context<'_>, chain_id: String, item_id: u8) -> Result<PickValidation> {
+        let context = get_context(ctx);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let room = self.load_draft_room(chain_id).await?;
+        Ok(pick_validation(&room, item_id, context.require_player_owner()?))
+    }
+
+    /// Get a resume token linking the caller back into a room
+    async fn resume_token(&self, ctx: &Context<'_>, chain_id: String) -> Result<String> {
+        let context = get_context(ctx);
+
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        Ok(encode_resume_token(&chain_id, context.get_player_id()))
+    }
+
+    /// Resume a session from a resume token
+    ///
+    /// Decodes the token back into its room and player, and returns the room
+    /// state together with that player's picks in a single call.
+    async fn resume(&self, token: String) -> Result<ResumeData> {
+        let (chain_id, player_id) = decode_resume_token(&token)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid resume token: {}", e)))?;
+        let player_owner = player_id_to_owner(&player_id);
+
+        info!("Player {} resuming DraftRoom {}", player_id, chain_id);
+
+        let room = self.load_room_seen_by(chain_id, Some(&player_owner)).await?;
+        let picks = room.pick_history.iter()
+            .filter(|record| record.player == player_owner)
+            .map(|record| to_service_item(record.item.clone()))
+            .collect();
+
+        Ok(ResumeData {
+            player_id,
+            room: room.into_room_state(),
+            picks,
+        })
+    }
+
+    /// Get the caller's identity, the lobby rooms and the server time in one request
+    /// 
+    /// Saves a fresh client separate `playerInfo`, `rooms` and `serverTime`
+    /// round trips on page load. `limit` caps the number of rooms returned.
+    async fn bootstrap(&self, ctx: &Context<'_>, limit: Option<u32>) -> Result<Bootstrap> {
+        let context = get_context(ctx);
+        let player = PlayerInfo {
+            player_id: context.get_player_id().to_string(),
+            owner: context.get_player_owner().map(ToString::to_string),
+        };
+
+        let lobby_rooms = self.load_lobby_rooms().await?;
+        info!("Player {} bootstrapping with {} lobby rooms", player.player_id, lobby_rooms.len());
+
+        Ok(bootstrap(player, lobby_rooms, limit.map(|limit| limit as usize), now_rfc3339()))
+    }
+
+    /// Get player information (for debugging/display)
+    async fn player_info(&self, ctx: &Context<'_>) -> Result<String> {
+        let context = get_context(ctx);
+        let player_id = context.get_player_id();
+        let player_owner = context.get_player_owner()
+            .map_or_else(|| "anonymous".to_string(), ToString::to_string);
+        
+        Ok(format!(
+            "Player ID: {} | Owner: {}",
+            player_id,
+            player_owner
+        ))
+    }
+
+    /// Get the Owner address a player ID maps to
+    /// 
+    /// Lets tooling pre-seed rooms or verify identities without reimplementing
+    /// the derivation. The ID must be in the format the service issues.
+    async fn derive_owner(&self, player_id: String) -> Result<String> {
+        let owner = derive_owner(&player_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid player ID: {}", e)))?;
+        Ok(owner.to_string())
+    }
+
+    /// Show which owner the service signs this session's mutations with
+    /// 
+    /// Operations are signed with the session's own key, so `signer` is the
+    /// owner the contract's `authenticated_signer()` sees. `signsAsPlayer`
+    /// reports whether it matches the session's derived `playerOwner`.
+    async fn signing_info(&self, ctx: &Context<'_>) -> SigningInfo {
+        let context = get_context(ctx);
+        let signer = Owner::from(context.signing_key().public()).to_string();
+        signing_info(context.get_player_owner(), Some(signer))
+    }
+
+    /// Check a custom pool against the contract's rules without creating a room
+    /// 
+    /// Uses the same validation the contract applies, so a pool that passes
+    /// here will not be rejected on-chain for its contents.
+    async fn validate_pool(&self, items: Vec<DraftItemInput>) -> Result<PoolValidation> {
+        Ok(pool_validation(items))
+    }
+
+    /// The most-drafted items across every finalized room, most picked first
+    ///
+    /// Counts are kept by the Lobby as rooms finalize, so rooms drafted
+    /// before the Lobby tracked popularity are not included.
+    async fn popular_items(&self, limit: u32) -> Result<Vec<PopularItem>> {
+        let response = self.client.query_application(self.default_chain_id, self.app_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to query Lobby: {}", e)))?;
+
+        Ok(popular_items(Self::extract_item_popularity(&response), limit as usize))
+    }
+
+    /// A finalized room's result, kept by the Lobby after `cleanupFinished` delists the room
+    async fn room_result(&self, chain_id: String) -> Result<Option<RoomResult>> {
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        let response = self.client.query_application(self.default_chain_id, self.app_id).await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to query Lobby: {}", e)))?;
+
+        Ok(Self::extract_room_results(&response).remove(&chain_id).map(|result| room_result(chain_id, result)))
+    }
+
+    /// Rooms players have reported, most reported first (admin only)
+    async fn reported_rooms(&self, ctx: &Context<'_>) -> Result<Vec<ReportedRoom>> {
+        require_admin(get_context(ctx))?;
+        Ok(self.reports.reported_rooms())
+    }
+
+    /// Waiting rooms older than `olderThanSecs` that never filled, oldest first (admin only)
+    /// 
+    /// Lets operators find abandoned rooms to prune. Full rooms that simply
+    /// haven't been started are left out; rooms whose chain can't be queried
+    /// are included with the player count the Lobby last recorded.
+    async fn stale_rooms(&self, ctx: &Context<'_>, older_than_secs: u64) -> Result<Vec<StaleRoom>> {
+        require_admin(get_context(ctx))?;
+
+        let now = Utc::now();
+        let older_than = Duration::seconds(older_than_secs.min(i64::MAX as u64) as i64);
+        let candidates = stale_candidates(self.load_lobby_rooms().await?, now, older_than);
+
+        let stale: Vec<StaleRoom> = stream::iter(candidates)
+            .map(|(chain_id, metadata)| async move {
+                match self.load_draft_room(chain_id).await {
+                    Ok(room) if room.players.len() >= usize::from(metadata.max_players) => None,
+                    Ok(room) => Some(stale_room(chain_id, metadata, room.players.len() as u8, now)),
+                    Err(e) => {
+                        warn!("Reporting unreachable room {} as stale: {}", chain_id, e.message);
+                        let reported_players = metadata.current_players;
+                        Some(stale_room(chain_id, metadata, reported_players, now))
+                    }
+                }
+            })
+            .buffered(LOBBY_OVERVIEW_CONCURRENCY)
+            .filter_map(|entry| async move { entry })
+            .collect()
+            .await;
+
+        Ok(stale)
+    }
+
+    /// Every Lobby room as a JSON array, for backups and analytics (admin only)
+    /// 
+    /// Each entry has the room's listing fields plus its creator and creation
+    /// time. Rooms whose chain can't be queried are exported without a creator.
+    async fn export_lobby(&self, ctx: &Context<'_>) -> Result<Json<Vec<LobbyExportEntry>>> {
+        require_admin(get_context(ctx))?;
+
+        let mut rooms: Vec<_> = self.load_lobby_rooms().await?.into_iter().collect();
+        rooms.sort_by_key(|(chain_id, metadata)| (created_at(metadata), chain_id.to_string()));
+
+        let entries: Vec<LobbyExportEntry> = stream::iter(rooms)
+            .map(|(chain_id, metadata)| async move {
+                match self.load_draft_room(chain_id).await {
+                    Ok(room) => export_entry(chain_id, metadata, Some(&room)),
+                    Err(e) => {
+                        warn!("Exporting unreachable room {} without live state: {}", chain_id, e.message);
+                        export_entry(chain_id, metadata, None)
+                    }
+                }
+            })
+            .buffered(LOBBY_OVERVIEW_CONCURRENCY)
+            .collect()
+            .await;
+
+        info!("Exported {} lobby rooms", entries.len());
+        Ok(Json(entries))
+    }
+
+    /// Status of an operation queued with `enqueueOperation`
+    /// 
+    /// Returns `null` for unknown job IDs and for jobs past their TTL.
+    async fn job_status(&self, job_id: String) -> Option<JobStatus> {
+        self.jobs.status(&job_id)
+    }
+
+    /// Current server time (RFC 3339, UTC) for reconciling turn deadlines with the local clock
+    async fn server_time(&self) -> String {
+        now_rfc3339()
+    }
+
+    /// Health check endpoint
+    async fn health(&self) -> Result<String> {
+        Ok("Service is running".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner(n: u8) -> Owner {
+        Owner::from_str(&format!("{:064x}", n)).unwrap()
+    }
+
+    fn room_with_players(players: &[Owner], creator: Owner) -> DraftRoomStateData {
+        DraftRoomStateData {
+            chain_id: ChainId::root(0),
+            players: players.iter().map(|p| p.to_string()).collect(),
+            max_players: 4,
+            current_turn: 0,
+            round: 1,
+            max_rounds: 3,
+            pool: vec![],
+            staged_pool: vec![],
+            status: RoomStatus::Waiting,
+            creator: Some(creator.to_string()),
+            turn_order: None,
+            reserved_players: vec![],
+            pick_history: vec![],
+            reveal_after_round: false,
+            final_round_min_power: None,
+            round_weights: vec![],
+            turn_duration_secs: None,
+            min_pick_interval_secs: None,
+            auto_close_after_secs: None,
+            turn_deadline: None,
+            turn_started_at: None,
+            budget: None,
+            spectators: vec![],
+            max_spectators: DEFAULT_MAX_SPECTATORS,
+            time_requests_used: HashMap::new(),
+            preview: false,
+            ban_rounds: 0,
+            bans: vec![],
+            pool_size: None,
+            pool_seed: None,
+            seed_commitment: None,
+            handicap_first_pick: false,
+            password_hash: None,
+            finalized: false,
+            winner: None,
+            conceded_by: None,
+            version: 0,
+            suggestions: HashMap::new(),
+            ready_players: vec![],
+            captains: vec![],
+            squads: HashMap::new(),
+        }
+    }
+
+    fn pick(player: Owner, id: u8, round: u8) -> ContractPickRecord {
+        ContractPickRecord {
+            player,
+            item: ContractDraftItem::new(id, &format!("Item {}", id), 50),
+            round,
+        }
+    }
+
+    #[test]
+    fn test_reveal_after_round_hides_current_round_picks() {
+        let (me, other) = (owner(1), owner(2));
+        // Round 1 complete, round 2 in progress with one pick each
+        let history = vec![pick(me, 1, 1), pick(other, 2, 1), pick(other, 3, 2), pick(me, 4, 2)];
+
+        let visible: Vec<u8> = visible_picks(&history, 2, Some(&me), true)
+            .map(|record| record.item.id)
+            .collect();
+
+        assert_eq!(visible, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_open_draft_shows_all_picks() {
+        let (me, other) = (owner(1), owner(2));
+        let history = vec![pick(other, 1, 1), pick(other, 2, 2)];
+
+        assert_eq!(visible_picks(&history, 2, Some(&me), false).count(), 2);
+    }
+
+    #[test]
+    fn test_start_requirements_below_threshold() {
+        let room = room_with_players(&[owner(1)], owner(1));
+        let requirements = start_requirements(&room, &owner(1));
+        assert_eq!(requirements.current_players, 1);
+        assert!(!requirements.can_start);
+        assert!(requirements.is_creator);
+    }
+
+    #[test]
+    fn test_start_requirements_at_threshold() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        assert!(!start_requirements(&room, &owner(1)).can_start);
+        room.ready_players = room.players.clone();
+        assert!(start_requirements(&room, &owner(1)).can_start);
+        assert!(!start_requirements(&room, &owner(2)).is_creator);
+    }
+
+    #[test]
+    fn test_room_overview_includes_roster_and_turn() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.round = 2;
+        let metadata = DraftRoomMetadata {
+            room_name: "Friday Cube".to_string(),
+            max_players: 4,
+            status: ContractRoomStatus::Drafting,
+            created_at: Default::default(),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+
+        let overview = room_overview(ChainId::root(0), metadata, &room);
+
+        assert_eq!(overview.players, vec![owner(1).to_string(), owner(2).to_string()]);
+        assert_eq!(overview.room.current_players, 2);
+        // Round 2 runs backwards, so the last joiner picks first
+        assert_eq!(overview.current_player, Some(owner(2).to_string()));
+    }
+
+    #[test]
+    fn test_old_schema_response_falls_back_to_legacy() {
+        // Old contracts reject the unknown field instead of answering it
+        let old = serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Unknown field \"schemaVersion\" on type \"QueryRoot\"" }]
+        });
+        assert!(schema_draft_room(&old).is_none());
+
+        let unversioned = serde_json::json!({ "data": { "draftRoom": { "players": [] } } });
+        assert!(schema_draft_room(&unversioned).is_none());
+
+        let current = serde_json::json!({
+            "data": { "schemaVersion": SCHEMA_VERSION, "draftRoom": { "players": [] } }
+        });
+        assert!(schema_draft_room(&current).is_some());
+    }
+
+    fn pool_input(id: u8, name: &str, power: u32) -> DraftItemInput {
+        DraftItemInput {
+            id,
+            name: name.to_string(),
+            power,
+            quantity: None,
+            tags: None,
+            cost: None,
+            weight: None,
+        }
+    }
+
+    fn assert_invalid(items: Vec<DraftItemInput>) {
+        let result = pool_validation(items);
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+    }
+
+    #[test]
+    fn test_validate_pool_accepts_valid_pool() {
+        let result = pool_validation(vec![pool_input(1, "Lightning Bolt", 100), pool_input(2, "Counterspell", 90)]);
+        assert!(result.valid);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_validate_pool_rejects_duplicate_ids() {
+        assert_invalid(vec![pool_input(1, "Lightning Bolt", 100), pool_input(1, "Counterspell", 90)]);
+    }
+
+    #[test]
+    fn test_validate_pool_rejects_zero_power() {
+        assert_invalid(vec![pool_input(1, "Lightning Bolt", 0)]);
+    }
+
+    #[test]
+    fn test_validate_pool_rejects_bad_names() {
+        assert_invalid(vec![pool_input(1, "   ", 100)]);
+        assert_invalid(vec![pool_input(1, &"x".repeat(65), 100)]);
+        assert_invalid(vec![pool_input(1, "Lightning\nBolt", 100)]);
+    }
+
+    #[test]
+    fn test_validate_pool_rejects_bad_size() {
+        assert_invalid(vec![]);
+        assert_invalid((1..=101).map(|id| pool_input(id, "Card", 10)).collect());
+    }
+
+    #[test]
+    fn test_effective_turn_order_prefers_override_while_waiting() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        assert_eq!(room.effective_turn_order(), room.players);
+
+        room.turn_order = Some(vec![owner(2).to_string(), owner(1).to_string()]);
+        assert_eq!(room.effective_turn_order(), vec![owner(2).to_string(), owner(1).to_string()]);
+
+        room.status = RoomStatus::Drafting;
+        assert_eq!(room.effective_turn_order(), room.players);
+    }
+
+    #[test]
+    fn test_scores_for_subset_and_full_room() {
+        let mut room = room_with_players(&[owner(1), owner(2), owner(3)], owner(1));
+        room.pick_history = vec![pick(owner(1), 1, 1), pick(owner(2), 2, 1), pick(owner(1), 3, 2)];
+
+        let subset = player_scores(&room, 3, &[owner(1).to_string(), owner(9).to_string()], Some(&owner(1))).unwrap();
+        assert_eq!(subset.len(), 1);
+        assert_eq!(subset[0].player, owner(1).to_string());
+        assert_eq!((subset[0].total_power, subset[0].pick_count), (100, 2));
+
+        let full = player_scores(&room, 3, &[], Some(&owner(1))).unwrap();
+        let totals: Vec<_> = full.iter().map(|score| (score.total_power, score.pick_count)).collect();
+        assert_eq!(totals, vec![(100, 2), (50, 1), (0, 0)]);
+
+        assert!(player_scores(&room, 3, &["not an owner".to_string()], Some(&owner(1))).is_err());
+    }
+
+    #[test]
+    fn test_share_info_from_metadata() {
+        let metadata = DraftRoomMetadata {
+            room_name: "Friday Draft".to_string(),
+            max_players: 4,
+            status: ContractRoomStatus::Drafting,
+            created_at: Default::default(),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+
+        let info = share_info(ChainId::root(0), metadata.clone());
+        assert_eq!(info.chain_id, ChainId::root(0).to_string());
+        assert_eq!(info.room_name, "Friday Draft");
+        assert_eq!(info.status, RoomStatus::Drafting);
+        assert!(!info.requires_password && !info.is_private);
+
+        let private = share_info(ChainId::root(0), DraftRoomMetadata { is_private: true, ..metadata });
+        assert!(private.requires_password && private.is_private);
+    }
+
+    fn check(room: Option<&DraftRoomStateData>, kind: OperationKind, item_id: Option<u8>, caller: Owner) -> Option<String> {
+        let result = check_operation(room, &OperationInput { kind, item_id, password: None }, &caller);
+        assert_eq!(result.allowed, result.reason.is_none());
+        result.reason
+    }
+
+    fn drafting_room_with_pool() -> DraftRoomStateData {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.pool = vec![to_service_item(ContractDraftItem::new(1, "Lightning Bolt", 100))];
+        room
+    }
+
+    #[test]
+    fn test_can_execute_create_room_only_on_lobby() {
+        let room = room_with_players(&[], owner(1));
+        assert_eq!(check(None, OperationKind::CreateRoom, None, owner(1)), None);
+        assert!(check(Some(&room), OperationKind::CreateRoom, None, owner(1)).is_some());
+        assert!(check(None, OperationKind::JoinRoom, None, owner(1)).is_some());
+    }
+
+    #[test]
+    fn test_can_execute_join_room() {
+        let mut room = room_with_players(&[owner(1)], owner(1));
+        assert_eq!(check(Some(&room), OperationKind::JoinRoom, None, owner(2)), None);
+        assert_eq!(check(Some(&room), OperationKind::JoinRoom, None, owner(1)), Some(DraftRoomError::AlreadyJoined.to_string()));
+
+        room.spectators = vec![owner(3).to_string()];
+        assert_eq!(check(Some(&room), OperationKind::JoinRoom, None, owner(3)), Some(DraftRoomError::Spectating.to_string()));
+
+        room.password_hash = Some(password_hash("hunter2"));
+        assert_eq!(check(Some(&room), OperationKind::JoinRoom, None, owner(2)), Some(DraftRoomError::WrongPassword.to_string()));
+        let join = |password: &str| OperationInput { kind: OperationKind::JoinRoom, item_id: None, password: Some(password.to_string()) };
+        assert!(!check_operation(Some(&room), &join("hunter3"), &owner(2)).allowed);
+        assert!(check_operation(Some(&room), &join("hunter2"), &owner(2)).allowed);
+        room.password_hash = None;
+
+        room.max_players = 1;
+        assert_eq!(check(Some(&room), OperationKind::JoinRoom, None, owner(2)), Some(DraftRoomError::RoomFull.to_string()));
+    }
+
+    #[test]
+    fn test_can_execute_start_draft() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.ready_players = vec![owner(1).to_string()];
+        assert_eq!(check(Some(&room), OperationKind::StartDraft, None, owner(1)), Some(DraftRoomError::PlayersNotReady.to_string()));
+
+        room.ready_players = room.players.clone();
+        assert_eq!(check(Some(&room), OperationKind::StartDraft, None, owner(1)), None);
+        assert_eq!(check(Some(&room), OperationKind::StartDraft, None, owner(2)), Some(DraftRoomError::NotCreator.to_string()));
+
+        room.status = RoomStatus::Drafting;
+        assert_eq!(check(Some(&room), OperationKind::StartDraft, None, owner(1)), Some(DraftRoomError::NotWaiting.to_string()));
+
+        let alone = room_with_players(&[owner(1)], owner(1));
+        assert_eq!(check(Some(&alone), OperationKind::StartDraft, None, owner(1)), Some(DraftRoomError::NotEnoughPlayers.to_string()));
+        let absent = room_with_players(&[owner(2), owner(3)], owner(1));
+        assert_eq!(check(Some(&absent), OperationKind::StartDraft, None, owner(1)), Some(DraftRoomError::CreatorNotJoined.to_string()));
+    }
+
+    #[test]
+    fn test_can_execute_pick_item() {
+        let mut room = drafting_room_with_pool();
+        assert_eq!(check(Some(&room), OperationKind::PickItem, Some(1), owner(1)), None);
+        assert_eq!(check(Some(&room), OperationKind::PickItem, Some(1), owner(2)), Some(DraftRoomError::NotYourTurn.to_string()));
+        assert_eq!(check(Some(&room), OperationKind::PickItem, Some(9), owner(1)), Some(DraftRoomError::ItemNotFound.to_string()));
+
+        room.final_round_min_power = Some(90);
+        room.round = room.max_rounds;
+        room.pool.push(to_service_item(ContractDraftItem::new(2, "Healing Salve", 70)));
+        assert_eq!(
+            check(Some(&room), OperationKind::PickItem, Some(2), owner(1)),
+            Some(DraftRoomError::BelowPowerThreshold(90).to_string())
+        );
+
+        room.status = RoomStatus::Waiting;
+        assert_eq!(check(Some(&room), OperationKind::PickItem, Some(1), owner(1)), Some(DraftRoomError::NotDrafting.to_string()));
+    }
+
+    #[test]
+    fn test_can_execute_finalize_draft() {
+        let mut room = drafting_room_with_pool();
+        assert_eq!(check(Some(&room), OperationKind::FinalizeDraft, None, owner(1)), Some(DraftRoomError::DraftNotFinished.to_string()));
+
+        room.status = RoomStatus::Finished;
+        assert_eq!(check(Some(&room), OperationKind::FinalizeDraft, None, owner(2)), None);
+    }
+
+    #[test]
+    fn test_pool_filtered_by_tag() {
+        let tagged = |id, tags: &[&str]| to_service_item(ContractDraftItem {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..ContractDraftItem::new(id, "Card", 50)
+        });
+        let pool = vec![tagged(1, &["aggro"]), tagged(2, &["control", "aggro"]), tagged(3, &["control"]), tagged(4, &[])];
+
+        let ids = |items: Vec<crate::types::DraftItem>| items.iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(ids(items_with_tag(pool.clone(), "aggro")), vec![1, 2]);
+        assert_eq!(ids(items_with_tag(pool.clone(), "control")), vec![2, 3]);
+        assert!(items_with_tag(pool, "Aggro").is_empty());
+    }
+
+    #[test]
+    fn test_standings_apply_round_weights() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.pick_history = vec![pick(owner(1), 1, 1), pick(owner(2), 2, 2), pick(owner(2), 3, 2)];
+
+        let summary = |room: &DraftRoomStateData| room_standings(room).iter()
+            .map(|standing| (standing.player.clone(), standing.raw_power, standing.weighted_power))
+            .collect::<Vec<_>>();
+
+        assert_eq!(summary(&room), vec![(owner(2).to_string(), 100, 100), (owner(1).to_string(), 50, 50)]);
+
+        room.round_weights = vec![3, 1];
+        assert_eq!(summary(&room), vec![(owner(1).to_string(), 50, 150), (owner(2).to_string(), 100, 100)]);
+    }
+
+    #[test]
+    fn test_estimated_end_time_for_partial_draft() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.turn_duration_secs = Some(30);
+        room.pool = (1..=10).map(|id| to_service_item(ContractDraftItem::new(id, "Card", 50))).collect();
+        // 2 players x 3 rounds, 2 picks made: 4 remaining at 30s each
+        room.pick_history = vec![pick(owner(1), 11, 1), pick(owner(2), 12, 1)];
+
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(to_rfc3339(estimated_end_time(&room, now).unwrap()), "2024-01-01T12:02:00.000Z");
+
+        room.turn_duration_secs = None;
+        assert!(estimated_end_time(&room, now).is_none());
+
+        room.turn_duration_secs = Some(30);
+        room.status = RoomStatus::Waiting;
+        assert!(estimated_end_time(&room, now).is_none());
+    }
+
+    #[test]
+    fn test_affordable_items_respect_remaining_budget() {
+        let (me, other) = (owner(1), owner(2));
+        let mut room = room_with_players(&[me, other], me);
+        room.status = RoomStatus::Drafting;
+        room.budget = Some(100);
+        let costed = |id, cost| to_service_item(ContractDraftItem { cost, ..ContractDraftItem::new(id, "Card", 50) });
+        room.pool = vec![costed(1, 70), costed(2, 40), costed(3, 30), costed(4, 0)];
+
+        // One round done: I spent 60, so 40 remains; watch the snake bring it back to me
+        let spent = ContractPickRecord {
+            player: me,
+            item: ContractDraftItem { cost: 60, ..ContractDraftItem::new(5, "Card", 50) },
+            round: 1,
+        };
+        room.pick_history = vec![spent, pick(other, 6, 1)];
+        room.round = 2;
+        room.current_turn = 1;
+
+        let ids: Vec<u8> = affordable_items(&room, &me).iter().map(|item| item.id).collect();
+        assert_eq!(ids, vec![2, 3, 4]);
+
+        assert!(affordable_items(&room, &other).is_empty());
+    }
+
+    fn parse_fixture(fixture: &livedraft_arena::fixtures::DraftRoomFixture) -> DraftRoomStateData {
+        let state: serde_json::Value = serde_json::from_str(&fixture.state_json()).unwrap();
+        futures::executor::block_on(QueryRoot::extract_draft_room_from_json(&state["DraftRoom"], ChainId::root(0)))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mid_draft_fixture_parses_through_extractor() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let room = parse_fixture(&DraftRoomFixture::mid_draft());
+
+        assert_eq!(room.players, vec![owner(1).to_string(), owner(2).to_string()]);
+        assert_eq!(room.creator, Some(owner(1).to_string()));
+        assert_eq!(room.status, RoomStatus::Drafting);
+        assert_eq!((room.round, room.max_rounds, room.max_players), (2, 3, 4));
+        assert_eq!(room.pool.len(), 13);
+        assert_eq!(room.pick_history.len(), 2);
+        assert!(room.reveal_after_round);
+        assert_eq!(room.final_round_min_power, Some(80));
+        assert_eq!(room.round_weights, vec![1, 2, 3]);
+        assert_eq!(room.turn_duration_secs, Some(30));
+        assert_eq!((room.spectators.len(), room.max_spectators), (1, 10));
+        let remaining: Vec<u8> = room.time_requests_remaining().iter().map(|requests| requests.remaining).collect();
+        assert_eq!(remaining, vec![MAX_TIME_REQUESTS, MAX_TIME_REQUESTS - 1]);
+        assert_eq!(current_player(&room), Some(owner(2).to_string()));
+        assert_eq!(room.version, 7);
+    }
+
+    #[test]
+    fn test_fixture_overrides_parse_through_extractor() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+        use livedraft_arena::RoomOptions;
+
+        let fixture = DraftRoomFixture::mid_draft()
+            .with_status(ContractDraftStatus::Finished)
+            .with_options(RoomOptions { budget: Some(200), ..RoomOptions::default() });
+        let room = parse_fixture(&fixture);
+
+        assert_eq!(room.status, RoomStatus::Finished);
+        assert!(!room.reveal_after_round);
+        assert_eq!(room.turn_duration_secs, None);
+        let remaining: Vec<u32> = room.remaining_budgets().iter().map(|budget| budget.remaining).collect();
+        assert_eq!(remaining, vec![200, 200]);
+        assert!(current_player(&room).is_none());
+    }
+
+    #[test]
+    fn test_reveal_delay_withholds_recent_picks() {
+        let sightings = PickSightings::default();
+        let chain_id = ChainId::root(0);
+        let start = DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+
+        sightings.observe(chain_id, 2, start);
+        let seen_at = sightings.observe(chain_id, 3, start + Duration::seconds(40));
+        assert_eq!(seen_at, vec![start, start, start + Duration::seconds(40)]);
+
+        let delay = Duration::seconds(30);
+        let now = start + Duration::seconds(50);
+        assert_eq!(revealed_pick_count(&seen_at, now, delay), 2);
+        assert_eq!(revealed_pick_count(&seen_at, now + Duration::seconds(20), delay), 3);
+        assert_eq!(revealed_pick_count(&seen_at, start, delay), 0);
+
+        // An undone pick drops its sighting
+        assert_eq!(sightings.observe(chain_id, 1, now).len(), 1);
+
+        // Every pick-derived read only counts the revealed picks
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.pick_history = vec![pick(owner(1), 1, 1), pick(owner(2), 2, 1), pick(owner(2), 3, 2)];
+        let revealed = 2;
+        let ids = |items: Vec<crate::types::DraftItem>| items.iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(ids(visible_player_picks(&room, revealed, &owner(2), None)), vec![2]);
+        let scores = player_scores(&room, revealed, &[], None).unwrap();
+        assert_eq!(scores.iter().map(|score| score.pick_count).collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(pick_id_board(&room, revealed, None)[1].item_ids, vec![2]);
+        assert_eq!(picks_page(&room, revealed, None, 0, 10).len(), 2);
+        let standings = visible_standings(room, revealed, None);
+        assert_eq!(standings.iter().map(|standing| standing.raw_power).collect::<Vec<_>>(), vec![50, 50]);
+    }
+
+    #[test]
+    fn test_spectator_room_state_lags_behind_the_reveal_delay() {
+        let finished_room = || {
+            let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+            room.status = RoomStatus::Finished;
+            room.round = 3;
+            room.max_rounds = 2;
+            room.budget = Some(200);
+            room.winner = Some(owner(2).to_string());
+            room.turn_deadline = Some(Utc::now());
+            room.pool = vec![to_service_item(ContractDraftItem::new(1, "Item 1", 50))];
+            room.pick_history = vec![pick(owner(1), 2, 1), pick(owner(2), 3, 1), pick(owner(2), 1, 2), pick(owner(1), 4, 2)];
+            for record in &mut room.pick_history {
+                record.item.cost = 50;
+            }
+            room
+        };
+
+        // A spectator has only seen the first three picks
+        let state = finished_room().seen_by(3, None).into_room_state();
+        assert_eq!((state.status, state.round, state.current_turn), (RoomStatus::Drafting, 2, 1));
+        assert_eq!(state.turn_deadline, None);
+        assert_eq!(state.pool.iter().map(|item| (item.id, item.quantity)).collect::<Vec<_>>(), vec![(1, 1), (4, 1)]);
+        let remaining: Vec<u32> = state.budgets.iter().map(|budget| budget.remaining).collect();
+        assert_eq!(remaining, vec![150, 100]);
+
+        // Once every pick is revealed the room is shown as it is
+        let state = finished_room().seen_by(4, None).into_room_state();
+        assert_eq!((state.status, state.round), (RoomStatus::Finished, 3));
+        assert_eq!(state.pool.len(), 1);
+    }
+
+    #[test]
+    fn test_reveal_after_round_room_state_hides_current_round_picks() {
+        let (me, other) = (owner(1), owner(2));
+        let round_two = || {
+            let mut room = room_with_players(&[me, other], me);
+            room.status = RoomStatus::Drafting;
+            room.reveal_after_round = true;
+            room.round = 2;
+            room.current_turn = 1;
+            room.budget = Some(200);
+            room.pool = vec![to_service_item(ContractDraftItem::new(4, "Item 4", 50))];
+            room.pick_history = vec![pick(me, 1, 1), pick(other, 2, 1), pick(other, 3, 2)];
+            for record in &mut room.pick_history {
+                record.item.cost = 50;
+            }
+            room
+        };
+        let pool_ids = |state: &DraftRoomState| state.pool.iter().map(|item| item.id).collect::<Vec<_>>();
+        let remaining = |state: &DraftRoomState| state.budgets.iter().map(|budget| budget.remaining).collect::<Vec<_>>();
+
+        // The opponent's round 2 pick stays in the pool and unspent for me
+        let state = round_two().seen_by(3, Some(&me)).into_room_state();
+        assert_eq!(pool_ids(&state), vec![3, 4]);
+        assert_eq!(remaining(&state), vec![150, 150]);
+        assert_eq!((state.round, state.current_turn), (2, 1));
+
+        // The picker sees their own pick
+        let state = round_two().seen_by(3, Some(&other)).into_room_state();
+        assert_eq!(pool_ids(&state), vec![4]);
+        assert_eq!(remaining(&state), vec![150, 100]);
+    }
+
+    #[test]
+    fn test_ban_phase_state() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.ban_rounds = 2;
+        room.bans = vec![pick(owner(1), 7, 1)];
+        assert_eq!(room.bans_remaining(), 0);
+
+        room.status = RoomStatus::Banning;
+        let state = room.into_room_state();
+        assert_eq!(state.status, RoomStatus::Banning);
+        assert_eq!(state.bans_remaining, 3);
+        assert_eq!(state.banned_items.iter().map(|item| item.id).collect::<Vec<_>>(), vec![7]);
+    }
+
+    #[test]
+    fn test_bootstrap_populates_all_sections() {
+        let metadata = |name: &str| DraftRoomMetadata {
+            room_name: name.to_string(),
+            max_players: 4,
+            status: ContractRoomStatus::Waiting,
+            created_at: Default::default(),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+        let lobby_rooms: HashMap<ChainId, DraftRoomMetadata> = [
+            (ChainId::root(0), metadata("Zephyr")),
+            (ChainId::root(1), metadata("Arcane")),
+            (ChainId::root(2), metadata("Moxen")),
+        ].into_iter().collect();
+        let player = PlayerInfo {
+            player_id: "1234567890abcdef".to_string(),
+            owner: Some(owner(1).to_string()),
+        };
+
+        let data = bootstrap(player, lobby_rooms.clone(), None, now_rfc3339());
+        assert_eq!(data.player.owner, Some(owner(1).to_string()));
+        let names: Vec<&str> = data.rooms.iter().map(|room| room.room_name.as_str()).collect();
+        assert_eq!(names, vec!["Arcane", "Moxen", "Zephyr"]);
+        assert!(DateTime::parse_from_rfc3339(&data.server_time).is_ok());
+
+        let limited = bootstrap(data.player, lobby_rooms, Some(2), now_rfc3339());
+        assert_eq!(limited.rooms.len(), 2);
+        assert_eq!(limited.rooms[1].room_name, "Moxen");
+    }
+
+    #[test]
+    fn test_stale_rooms_are_old_waiting_rooms() {
+        let metadata = |name: &str, status, created_secs: u64| DraftRoomMetadata {
+            room_name: name.to_string(),
+            max_players: 4,
+            status,
+            created_at: linera_sdk::base::Timestamp::from(created_secs * 1_000_000),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+        let lobby_rooms: HashMap<ChainId, DraftRoomMetadata> = [
+            (ChainId::root(0), metadata("Fresh", ContractRoomStatus::Waiting, 3_500)),
+            (ChainId::root(1), metadata("Abandoned", ContractRoomStatus::Waiting, 100)),
+            (ChainId::root(2), metadata("Older", ContractRoomStatus::Waiting, 50)),
+            (ChainId::root(3), metadata("Underway", ContractRoomStatus::Drafting, 100)),
+        ].into_iter().collect();
+        let now = DateTime::from_timestamp(3_600, 0).unwrap();
+
+        let candidates = stale_candidates(lobby_rooms, now, Duration::seconds(600));
+        let names: Vec<&str> = candidates.iter().map(|(_, metadata)| metadata.room_name.as_str()).collect();
+        assert_eq!(names, vec!["Older", "Abandoned"]);
+
+        let (chain_id, metadata) = candidates.into_iter().nth(1).unwrap();
+        let report = stale_room(chain_id, metadata, 1, now);
+        assert_eq!(report.age_secs, 3_500);
+        assert_eq!(report.room.current_players, 1);
+        assert_eq!(report.created_at, "1970-01-01T00:01:40.000Z");
+    }
+
+    #[test]
+    fn test_validate_pick_reports_each_rejection() {
+        let reason = |room: &DraftRoomStateData, item_id, caller| pick_validation(room, item_id, &caller).reason;
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.pool = vec![
+            to_service_item(ContractDraftItem { cost: 60, ..ContractDraftItem::new(1, "Lightning Bolt", 100) }),
+            to_service_item(ContractDraftItem::new(2, "Healing Salve", 40)),
+        ];
+
+        assert_eq!(reason(&room, 1, owner(1)), Some(DraftRoomError::NotDrafting.to_string()));
+
+        room.status = RoomStatus::Drafting;
+        assert!(pick_validation(&room, 1, &owner(1)).valid);
+        assert_eq!(reason(&room, 1, owner(2)), Some(DraftRoomError::NotYourTurn.to_string()));
+        assert_eq!(reason(&room, 9, owner(1)), Some(DraftRoomError::ItemNotFound.to_string()));
+
+        room.final_round_min_power = Some(80);
+        room.round = room.max_rounds;
+        // Round three runs forward again, so owner 1 is still up
+        assert_eq!(reason(&room, 2, owner(1)), Some(DraftRoomError::BelowPowerThreshold(80).to_string()));
+
+        room.final_round_min_power = None;
+        room.budget = Some(50);
+        assert_eq!(
+            reason(&room, 1, owner(1)),
+            Some(DraftRoomError::OverBudget { cost: 60, remaining: 50 }.to_string())
+        );
+        assert!(pick_validation(&room, 2, &owner(1)).valid);
+    }
+
+    #[test]
+    fn test_lobby_export_round_trips_as_room_data() {
+        let metadata = DraftRoomMetadata {
+            room_name: "Friday Cube".to_string(),
+            max_players: 4,
+            status: ContractRoomStatus::Waiting,
+            created_at: linera_sdk::base::Timestamp::from(100_000_000),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+        let room = room_with_players(&[owner(1), owner(2)], owner(1));
+        let entries = vec![
+            export_entry(ChainId::root(0), metadata.clone(), Some(&room)),
+            export_entry(ChainId::root(1), metadata, None),
+        ];
+        assert_eq!(entries[0].creator, Some(owner(1).to_string()));
+        assert_eq!(entries[1].creator, None);
+
+        let json = serde_json::to_value(&entries).unwrap();
+        assert_eq!(json[0]["created_at"], "1970-01-01T00:01:40.000Z");
+
+        let rooms: Vec<RoomData> = serde_json::from_value(json).unwrap();
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[0].chain_id, ChainId::root(0).to_string());
+        assert_eq!(rooms[0].room_name, "Friday Cube");
+        assert_eq!(rooms[0].current_players, 2);
+        assert_eq!(rooms[1].status, RoomStatus::Waiting);
+    }
+
+    #[test]
+    fn test_pick_ids_omit_item_metadata() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.round = 2;
+        room.pick_history = vec![pick(owner(1), 7, 1), pick(owner(2), 3, 1), pick(owner(2), 9, 2)];
+
+        let board = pick_id_board(&room, room.pick_history.len(), Some(&owner(1)));
+        assert_eq!(board[0].item_ids, vec![7]);
+        assert_eq!(board[1].item_ids, vec![3, 9]);
+
+        let json = serde_json::to_value(&board).unwrap();
+        assert_eq!(json[1], serde_json::json!({ "player": owner(2).to_string(), "item_ids": [3, 9] }));
+
+        assert!(pick_id_board(&room, 1, Some(&owner(1)))[1].item_ids.is_empty());
+    }
+
+    #[test]
+    fn test_anonymous_viewer_sees_only_completed_rounds() {
+        let (first, second) = (owner(1), owner(2));
+        let history = vec![pick(first, 1, 1), pick(second, 2, 1), pick(second, 3, 2), pick(first, 4, 2)];
+
+        let visible: Vec<u8> = visible_picks(&history, 2, None, true)
+            .map(|record| record.item.id)
+            .collect();
+
+        assert_eq!(visible, vec![1, 2]);
+        assert_eq!(visible_picks(&history, 2, None, false).count(), 4);
+    }
+
+    #[test]
+    fn test_randomness_info_seed_matches_commitment() {
+        use livedraft_arena::{fixtures::DraftRoomFixture, seed_commitment};
+
+        let mut fixture = DraftRoomFixture::mid_draft();
+        fixture.checkpoint.pool_seed = Some(1234);
+        fixture.checkpoint.seed_commitment = Some(seed_commitment(1234));
+
+        let info = randomness_info(&parse_fixture(&fixture));
+        let seed: u64 = info.seed.unwrap().parse().unwrap();
+        assert_eq!(info.commitment, Some(seed_commitment(seed)));
+
+        let unsampled = randomness_info(&parse_fixture(&DraftRoomFixture::mid_draft()));
+        assert!(unsampled.seed.is_none() && unsampled.commitment.is_none());
+    }
+
+    #[test]
+    fn test_finished_room_needs_finalizing_until_standings_exist() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let draft = DraftRoomFixture::mid_draft();
+        assert!(!needs_finalizing(&parse_fixture(&draft)));
+
+        let mut finished = draft.with_status(ContractDraftStatus::Finished);
+        assert!(needs_finalizing(&parse_fixture(&finished)));
+
+        let checkpoint = &mut finished.checkpoint;
+        checkpoint.standings = compute_standings(&checkpoint.players, &checkpoint.pick_history, &[]);
+        assert!(!needs_finalizing(&parse_fixture(&finished)));
+    }
+
+    #[test]
+    fn test_suggestions_for_skip_items_no_longer_in_pool() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.pool = (1..=3).map(|id| to_service_item(ContractDraftItem::new(id, "Card", 50))).collect();
+        room.suggestions.insert(owner(1).to_string(), vec![3, 9, 1]);
+
+        let ids: Vec<u8> = suggestions_for(&room, &owner(1)).iter().map(|item| item.id).collect();
+        assert_eq!(ids, vec![3, 1]);
+        assert!(suggestions_for(&room, &owner(2)).is_empty());
+    }
+
+    #[test]
+    fn test_created_rooms_only_lists_the_callers_rooms() {
+        let metadata = |name: &str, creator: Owner, status| DraftRoomMetadata {
+            room_name: name.to_string(),
+            max_players: 4,
+            status,
+            created_at: Default::default(),
+            current_players: 0,
+            creator: Some(creator),
+            is_private: false,
+        };
+        let lobby_rooms: HashMap<ChainId, DraftRoomMetadata> = [
+            (ChainId::root(0), metadata("Zephyr", owner(1), ContractRoomStatus::Finished)),
+            (ChainId::root(1), metadata("Arcane", owner(2), ContractRoomStatus::Waiting)),
+            (ChainId::root(2), metadata("Moxen", owner(1), ContractRoomStatus::Drafting)),
+        ].into_iter().collect();
+
+        let names = |creator: Owner| created_rooms(lobby_rooms.clone(), &creator)
+            .into_iter()
+            .map(|room| room.room_name)
+            .collect::<Vec<_>>();
+        assert_eq!(names(owner(1)), vec!["Moxen", "Zephyr"]);
+        assert_eq!(names(owner(2)), vec!["Arcane"]);
+        assert!(names(owner(3)).is_empty());
+    }
+
+    #[test]
+    fn test_almost_done_only_at_the_final_pick() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        assert!(!room.almost_done());
+
+        room.status = RoomStatus::Drafting;
+        // Two players, three rounds: the sixth pick is round 3, turn 1
+        for round in 1..=3 {
+            for turn in 0..2 {
+                room.round = round;
+                room.current_turn = turn;
+                assert_eq!(room.almost_done(), (round, turn) == (3, 1), "round {} turn {}", round, turn);
+            }
+        }
+
+        room.status = RoomStatus::Finished;
+        assert!(!room.almost_done());
+    }
+
+    #[test]
+    fn test_first_pick_handicap_restricts_only_the_opening_pick() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.handicap_first_pick = true;
+        room.pool = vec![
+            to_service_item(ContractDraftItem::new(1, "Lightning Bolt", 100)),
+            to_service_item(ContractDraftItem::new(2, "Healing Salve", 70)),
+        ];
+
+        assert_eq!(room.restricted_item(), Some(1));
+        assert_eq!(
+            pick_validation(&room, 1, &owner(1)).reason,
+            Some(DraftRoomError::HandicapRestricted.to_string())
+        );
+        assert!(pick_validation(&room, 2, &owner(1)).valid);
+
+        room.current_turn = 1;
+        assert_eq!(room.restricted_item(), None);
+        assert!(pick_validation(&room, 1, &owner(2)).valid);
+    }
+
+    #[test]
+    fn test_draft_results_total_power_and_parse_winner() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let mut fixture = DraftRoomFixture::mid_draft().with_status(ContractDraftStatus::Finished);
+        let room = parse_fixture(&fixture);
+        let totals: Vec<_> = draft_results(&room).totals.into_iter().map(|total| total.total_power).collect();
+        assert_eq!(totals, vec![100, 100]);
+        assert_eq!(draft_results(&room).winner, None);
+
+        fixture.checkpoint.winner = Some(owner(1));
+        assert_eq!(draft_results(&parse_fixture(&fixture)).winner, Some(owner(1).to_string()));
+    }
+
+    #[test]
+    fn test_turn_deadline_is_exposed_as_rfc3339() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let mut fixture = DraftRoomFixture::mid_draft();
+        assert_eq!(parse_fixture(&fixture).into_room_state().turn_deadline, None);
+
+        fixture.checkpoint.turn_started_at = Some(linera_sdk::base::Timestamp::from(60_000_000));
+        fixture.checkpoint.turn_deadline = Some(linera_sdk::base::Timestamp::from(90_000_000));
+        let state = parse_fixture(&fixture).into_room_state();
+        assert_eq!(state.turn_started_at.as_deref(), Some("1970-01-01T00:01:00.000Z"));
+        assert_eq!(state.turn_deadline.as_deref(), Some("1970-01-01T00:01:30.000Z"));
+    }
+
+    #[test]
+    fn test_signing_info_flags_signer_mismatch() {
+        let player = crate::identity::player_id_to_owner("1234567890abcdef");
+        let wallet = owner(9).to_string();
+
+        // A signer other than the derived owner is reported as a mismatch
+        let info = signing_info(Some(&player), Some(wallet.clone()));
+        assert_eq!(info.player_owner, Some(player.to_string()));
+        assert_eq!(info.signer, Some(wallet));
+        assert!(!info.signs_as_player);
+
+        // Each session signs with its own key, whose owner is the derived one
+        let key = crate::keys::player_keys().key_pair("1234567890abcdef");
+        assert!(signing_info(Some(&player), Some(Owner::from(key.public()).to_string())).signs_as_player);
+        assert!(!signing_info(None, None).signs_as_player);
+    }
+
+    #[test]
+    fn test_share_code_recreates_room_settings() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let custom_pool = vec![
+            ContractDraftItem { quantity: 6, ..ContractDraftItem::new(1, "Rook", 50) },
+            ContractDraftItem { quantity: 6, ..ContractDraftItem::new(2, "Bishop", 30) },
+        ];
+        let mut fixture = DraftRoomFixture::mid_draft();
+        fixture.checkpoint.staged_pool = custom_pool.clone();
+        fixture.options.custom_pool = custom_pool;
+
+        let template = room_template(&parse_fixture(&fixture));
+        assert_eq!(template.max_players, 4);
+        assert_eq!(template.options, fixture.options);
+
+        let decoded = crate::share_code::decode_share_code(&encode_share_code(&template)).unwrap();
+        assert_eq!(decoded, template);
+    }
+
+    #[test]
+    fn test_popular_items_ranked_from_lobby_state() {
+        let lobby = serde_json::json!({
+            "Lobby": {
+                "rooms": {},
+                "item_popularity": { "Lightning Bolt": 3, "Black Lotus": 5, "Counterspell": 3, "Time Walk": 1 }
+            }
+        });
+        let counts = QueryRoot::extract_item_popularity(lobby.to_string().as_bytes());
+
+        let ranked = popular_items(counts, 3);
+        let names: Vec<_> = ranked.iter().map(|item| (item.name.as_str(), item.pick_count)).collect();
+        assert_eq!(names, vec![("Black Lotus", 5), ("Counterspell", 3), ("Lightning Bolt", 3)]);
+
+        let pairs = serde_json::json!({ "item_popularity": [["Lightning Bolt", 2]] });
+        let counts = QueryRoot::extract_item_popularity(pairs.to_string().as_bytes());
+        assert_eq!(counts.get("Lightning Bolt"), Some(&2));
+        assert!(QueryRoot::extract_item_popularity(b"{\"Lobby\": {\"rooms\": {}}}").is_empty());
+    }
+
+    #[test]
+    fn test_turn_schedule_snakes_across_rounds() {
+        let (a, b, c) = (owner(1), owner(2), owner(3));
+        let mut room = room_with_players(&[a, b, c], a);
+        room.max_rounds = 2;
+        room.status = RoomStatus::Drafting;
+        room.round = 2;
+        room.current_turn = 1;
+
+        let schedule = turn_schedule(&room);
+        let order: Vec<(u8, u8, String)> = schedule.iter()
+            .map(|slot| (slot.round, slot.pick_in_round, slot.player.clone()))
+            .collect();
+        assert_eq!(order, vec![
+            (1, 0, a.to_string()),
+            (1, 1, b.to_string()),
+            (1, 2, c.to_string()),
+            (2, 0, c.to_string()),
+            (2, 1, b.to_string()),
+            (2, 2, a.to_string()),
+        ]);
+
+        let completed: Vec<bool> = schedule.iter().map(|slot| slot.completed).collect();
+        assert_eq!(completed, vec![true, true, true, true, false, false]);
+
+        room.status = RoomStatus::Finished;
+        assert!(turn_schedule(&room).iter().all(|slot| slot.completed));
+    }
+
+    #[test]
+    fn test_simulation_picks_cover_rest_of_draft() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.pool = (1..=10).map(|id| to_service_item(ContractDraftItem::new(id, "Card", 50))).collect();
+        room.pick_history = vec![pick(owner(1), 11, 1)];
+
+        assert_eq!(simulation_picks(&room, &owner(1)).unwrap(), 5);
+        assert!(simulation_picks(&room, &owner(2)).is_err());
+
+        room.turn_duration_secs = Some(30);
+        assert!(simulation_picks(&room, &owner(1)).is_err());
+
+        room.turn_duration_secs = None;
+        room.status = RoomStatus::Waiting;
+        assert!(simulation_picks(&room, &owner(1)).is_err());
+    }
+
+    #[test]
+    fn test_page_rooms_filters_sorts_and_slices() {
+        let metadata = |name: &str, status| DraftRoomMetadata {
+            room_name: name.to_string(),
+            max_players: 4,
+            status,
+            created_at: Default::default(),
+            current_players: 0,
+            creator: None,
+            is_private: false,
+        };
+        let lobby_rooms: HashMap<ChainId, DraftRoomMetadata> = [
+            (ChainId::root(0), metadata("Zephyr", ContractRoomStatus::Waiting)),
+            (ChainId::root(1), metadata("Arcane", ContractRoomStatus::Drafting)),
+            (ChainId::root(2), metadata("Moxen", ContractRoomStatus::Waiting)),
+            (ChainId::root(3), metadata("Bolt", ContractRoomStatus::Waiting)),
+        ].into_iter().collect();
+        let names = |rooms: Vec<RoomData>| rooms.into_iter().map(|room| room.room_name).collect::<Vec<_>>();
+
+        assert_eq!(names(page_rooms(lobby_rooms.clone(), None, 0, usize::MAX)), vec!["Arcane", "Bolt", "Moxen", "Zephyr"]);
+        assert_eq!(names(page_rooms(lobby_rooms.clone(), None, 1, 2)), vec!["Bolt", "Moxen"]);
+        assert_eq!(names(page_rooms(lobby_rooms.clone(), Some(RoomStatus::Waiting), 1, 10)), vec!["Moxen", "Zephyr"]);
+        assert!(page_rooms(lobby_rooms.clone(), None, 10, 10).is_empty());
+        assert_eq!(sorted_rooms(lobby_rooms, Some(RoomStatus::Drafting)).len(), 1);
+    }
+
+    #[test]
+    fn test_lobby_room_keys_round_trip_through_json() {
+        let chain_id = ChainId::root(7);
+        let metadata = DraftRoomMetadata {
+            room_name: "Friday Draft".to_string(),
+            max_players: 4,
+            status: ContractRoomStatus::Waiting,
+            created_at: Default::default(),
+            current_players: 1,
+            creator: Some(owner(1)),
+            is_private: false,
+        };
+        let stored: HashMap<String, DraftRoomMetadata> = [(chain_id.to_string(), metadata)].into_iter().collect();
+        let json = serde_json::to_value(&stored).unwrap();
+
+        let rooms = QueryRoot::extract_rooms_from_json_object(&json).unwrap();
+        let reparsed = ChainId::from_str(&chain_id.to_string()).unwrap();
+        assert_eq!(reparsed, chain_id);
+        assert_eq!(rooms.get(&reparsed).map(|room| room.room_name.as_str()), Some("Friday Draft"));
+
+        // The same chain spelled in upper case is the same key, not a second room
+        let mut both = json.as_object().unwrap().clone();
+        both.insert(chain_id.to_string().to_uppercase(), both[&chain_id.to_string()].clone());
+        let rooms = QueryRoot::extract_rooms_from_json_object(&serde_json::Value::Object(both)).unwrap();
+        assert_eq!(rooms.len(), 1);
+        assert!(rooms.contains_key(&chain_id));
+    }
+
+    #[test]
+    fn test_max_players_change_reaches_listings() {
+        let chain_id = ChainId::root(7);
+        // The Lobby metadata after `SetMaxPlayers` raised a four-player room to six
+        let metadata = DraftRoomMetadata {
+            room_name: "Friday Draft".to_string(),
+            max_players: 6,
+            status: ContractRoomStatus::Waiting,
+            created_at: Default::default(),
+            current_players: 2,
+            creator: None,
+            is_private: false,
+        };
+        let stored: HashMap<String, DraftRoomMetadata> = [(chain_id.to_string(), metadata.clone())].into_iter().collect();
+        let rooms = QueryRoot::extract_rooms_from_json_object(&serde_json::to_value(&stored).unwrap()).unwrap();
+
+        let page = page_rooms(rooms, None, 0, usize::MAX);
+        assert_eq!(page.len(), 1);
+        assert_eq!((page[0].max_players, page[0].current_players), (6, 2));
+        assert_eq!(room_data(chain_id, metadata).max_players, 6);
+    }
+
+    #[test]
+    fn test_captains_and_squads_state() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+        use livedraft_arena::{DraftRoomStatus, RoomOptions};
+
+        let fixture = DraftRoomFixture::mid_draft()
+            .with_status(DraftRoomStatus::CaptainPicking)
+            .with_options(RoomOptions { captains: vec![owner(2), owner(1)], ..RoomOptions::default() });
+        let mut room = parse_fixture(&fixture);
+        assert_eq!(room.status, RoomStatus::CaptainPicking);
+        assert_eq!(room.captains, vec![owner(2).to_string(), owner(1).to_string()]);
+
+        room.squads.insert(owner(1).to_string(), vec![owner(3).to_string()]);
+        let squads = room.into_room_state().squads;
+        assert_eq!(squads.len(), 2);
+        assert_eq!(squads[0].captain, owner(2).to_string());
+        assert!(squads[0].players.is_empty());
+        assert_eq!(squads[1].players, vec![owner(3).to_string()]);
+    }
+
+    #[test]
+    fn test_current_turn_is_relative_to_caller() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        let waiting = current_turn(&room, Some(&owner(1)));
+        assert!(waiting.player.is_none() && !waiting.is_my_turn);
+
+        room.status = RoomStatus::Drafting;
+        room.round = 2;
+        let turn = current_turn(&room, Some(&owner(2)));
+        assert_eq!(turn.player, Some(owner(2).to_string()));
+        assert!(turn.is_my_turn);
+        assert!(!current_turn(&room, Some(&owner(1))).is_my_turn);
+        assert!(!current_turn(&room, None).is_my_turn);
+    }
+
+    #[test]
+    fn test_compare_players_by_round() {
+        let mut room = room_with_players(&[owner(1), owner(2), owner(3)], owner(1));
+        room.status = RoomStatus::Drafting;
+        room.max_rounds = 2;
+        room.round = 2;
+        room.pick_history = vec![pick(owner(1), 1, 1), pick(owner(2), 2, 1), pick(owner(3), 3, 1), pick(owner(2), 4, 2)];
+
+        let comparison = compare_players(&room, 4, &owner(1).to_string(), &owner(2).to_string(), None).unwrap();
+        assert_eq!((comparison.a.total_power, comparison.a.pick_count), (50, 1));
+        assert_eq!((comparison.b.total_power, comparison.b.pick_count), (100, 2));
+        assert_eq!(comparison.delta, -50);
+        let rounds: Vec<_> = comparison.rounds.iter().map(|round| (round.round, round.a_power, round.b_power, round.delta)).collect();
+        assert_eq!(rounds, vec![(1, 50, 50, 0), (2, 0, 50, -50)]);
+
+        // Picks still inside the reveal delay are left out
+        let delayed = compare_players(&room, 3, &owner(1).to_string(), &owner(2).to_string(), None).unwrap();
+        assert_eq!((delayed.b.total_power, delayed.b.pick_count), (50, 1));
+        assert_eq!(delayed.delta, 0);
+
+        assert!(compare_players(&room, 4, "not an owner", &owner(2).to_string(), None).is_err());
+        assert!(compare_players(&room, 4, &owner(1).to_string(), &owner(4).to_string(), None).is_err());
+    }
+
+    #[test]
+    fn test_draft_results_report_concession() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let mut fixture = DraftRoomFixture::mid_draft().with_status(ContractDraftStatus::Finished);
+        assert_eq!(draft_results(&parse_fixture(&fixture)).conceded_by, None);
+
+        fixture.checkpoint.conceded_by = Some(owner(2));
+        fixture.checkpoint.winner = Some(owner(1));
+        let results = draft_results(&parse_fixture(&fixture));
+        assert_eq!(results.conceded_by, Some(owner(2).to_string()));
+        assert_eq!(results.winner, Some(owner(1).to_string()));
+    }
+
+    #[test]
+    fn test_room_results_outlive_the_listing() {
+        let lobby = serde_json::json!({
+            "Lobby": {
+                "rooms": {},
+                "results": {
+                    (ChainId::root(1).to_string()): {
+                        "room_name": "Friday Draft",
+                        "winner": owner(2).to_string(),
+                        "finalized_at": 90_000_000
+                    }
+                }
+            }
+        });
+        let bytes = lobby.to_string();
+        assert!(QueryRoot::extract_room_results(bytes.as_bytes()).get(&ChainId::root(2)).is_none());
+
+        let result = QueryRoot::extract_room_results(bytes.as_bytes()).remove(&ChainId::root(1)).unwrap();
+        let result = room_result(ChainId::root(1), result);
+        assert_eq!(result.room_name, "Friday Draft");
+        assert_eq!(result.winner, Some(owner(2).to_string()));
+        assert_eq!(result.finalized_at, "1970-01-01T00:01:30.000Z");
+        assert!(QueryRoot::extract_room_results(b"not json").is_empty());
+    }
+
+    #[test]
+    fn test_pool_and_picks_pages_are_stable() {
+        let mut room = room_with_players(&[owner(1), owner(2)], owner(1));
+        room.pool = [9, 2, 14, 5, 1].iter().map(|id| to_service_item(ContractDraftItem::new(*id, "Card", 50))).collect();
+        room.round = 2;
+        room.pick_history = vec![pick(owner(1), 3, 1), pick(owner(2), 7, 1), pick(owner(2), 8, 2), pick(owner(1), 4, 2)];
+
+        let pool_ids = |offset, limit| pool_page(&room, offset, limit).iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(pool_ids(0, 2), vec![1, 2]);
+        assert_eq!(pool_ids(2, 2), vec![5, 9]);
+        assert_eq!(pool_ids(4, 2), vec![14]);
+        assert_eq!(pool_ids(2, 2), pool_ids(2, 2));
+        assert!(pool_ids(5, 2).is_empty());
+
+        let pick_numbers = |room: &DraftRoomStateData, viewer, offset, limit| {
+            picks_page(room, room.pick_history.len(), viewer, offset, limit).iter().map(|entry| entry.pick_number).collect::<Vec<_>>()
+        };
+        assert_eq!(pick_numbers(&room, None, 0, 3), vec![1, 2, 3]);
+        assert_eq!(pick_numbers(&room, None, 3, 3), vec![4]);
+        assert_eq!(pick_numbers(&room, None, 1, 2), pick_numbers(&room, None, 1, 2));
+
+        // Hidden round-2 picks drop out of the caller's pages but keep their numbers
+        room.reveal_after_round = true;
+        assert_eq!(pick_numbers(&room, Some(&owner(1)), 0, 10), vec![1, 2, 4]);
+        let page = picks_page(&room, room.pick_history.len(), Some(&owner(1)), 2, 1);
+        assert_eq!((page[0].pick_number, page[0].item.id, page[0].round), (4, 4, 2));
+    }
+}
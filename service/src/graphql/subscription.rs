@@ -0,0 +1,272 @@
+use async_graphql::{Context, Result, SimpleObject, Subscription};
+use futures::stream::{self, Stream};
+use linera_core::data_types::ChainId;
+use std::future::Future;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::types::{DraftRoomState, RoomStatus};
+use super::get_context;
+use super::query::{current_player, QueryRoot};
+
+/// How often subscriptions re-read DraftRoom state
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Emitted when it becomes the subscriber's turn to pick
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TurnNotification {
+    pub chain_id: String,
+    pub round: u8,
+}
+
+/// GraphQL Subscription root
+///
+/// Linera has no push channel for application state, so subscriptions poll
+/// DraftRoom state and emit only when something the client cares about changes.
+pub struct SubscriptionRoot {
+    query: QueryRoot,
+}
+
+impl SubscriptionRoot {
+    pub fn new(query: QueryRoot) -> Self {
+        Self { query }
+    }
+}
+
+/// Whether a poll moves the turn onto `me`, given whether it was already theirs
+fn became_my_turn(was_my_turn: bool, current_player: Option<&str>, me: &str) -> bool {
+    !was_my_turn && current_player == Some(me)
+}
+
+/// Polling state carried between turn notifications
+struct TurnWatch {
+    query: QueryRoot,
+    chain_id: ChainId,
+    me: String,
+    was_my_turn: bool,
+}
+
+/// The parts of a room whose change triggers a `roomUpdates` snapshot
+#[derive(Debug, PartialEq)]
+struct RoomKey {
+    current_turn: u8,
+    round: u8,
+    status: RoomStatus,
+    players: Vec<String>,
+}
+
+impl RoomKey {
+    fn of(state: &DraftRoomState) -> Self {
+        Self {
+            current_turn: state.current_turn,
+            round: state.round,
+            status: state.status,
+            players: state.players.clone(),
+        }
+    }
+}
+
+/// Polling state carried between room snapshots
+struct RoomWatch<L> {
+    load: L,
+    chain_id: ChainId,
+    last: Option<RoomKey>,
+}
+
+/// Snapshots of a room, starting with its current state
+///
+/// The first state `load` returns is emitted straight away, so a client that
+/// subscribes after missing updates is in sync before any further change.
+/// Later snapshots are emitted only when the room's `RoomKey` changes.
+fn room_snapshots<L, F>(chain_id: ChainId, load: L) -> impl Stream<Item = DraftRoomState>
+where
+    L: FnMut() -> F,
+    F: Future<Output = Result<DraftRoomState>>,
+{
+    let watch = RoomWatch {
+        load,
+        chain_id,
+        last: None,
+    };
+
+    stream::unfold(watch, |mut watch| async move {
+        loop {
+            match &watch.last {
+                Some(last) if last.status == RoomStatus::Finished => return None,
+                Some(_) => tokio::time::sleep(POLL_INTERVAL).await,
+                None => {}
+            }
+
+            let state = match (watch.load)().await {
+                Ok(state) => state,
+                Err(e) => {
+                    warn!("Room update poll failed for chain {}: {}", watch.chain_id, e.message);
+                    if watch.last.is_none() {
+                        tokio::time::sleep(POLL_INTERVAL).await;
+                    }
+                    continue;
+                }
+            };
+
+            let key = RoomKey::of(&state);
+            if watch.last.as_ref() != Some(&key) {
+                watch.last = Some(key);
+                return Some((state, watch));
+            }
+        }
+    })
+}
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Stream the room's state each time its turn, round, status or players change
+    ///
+    /// The current full state is sent straight away, so reconnecting clients
+    /// catch up on anything they missed. The stream ends after the snapshot
+    /// showing the draft finished. Snapshots hold back picks the caller may
+    /// not see yet, as `roomState` does.
+    async fn room_updates(
+        &self,
+        ctx: &Context<'_>,
+        chain_id: String,
+    ) -> Result<impl Stream<Item = DraftRoomState>> {
+        let context = get_context(ctx);
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        info!("Player {} subscribing to room updates on chain: {}", context.get_player_id(), chain_id);
+
+        let query = self.query.clone();
+        let viewer = context.get_player_owner().copied();
+        Ok(room_snapshots(chain_id, move || {
+            let query = query.clone();
+            async move { query.load_room_seen_by(chain_id, viewer.as_ref()).await.map(|room| room.into_room_state()) }
+        }))
+    }
+
+    /// Notify the caller each time it becomes their turn in a room
+    ///
+    /// Emits exactly once per transition to the caller's turn, so clients can
+    /// alert the player without diffing full room state. The stream ends when
+    /// the draft finishes.
+    async fn my_turn_notifications(
+        &self,
+        ctx: &Context<'_>,
+        chain_id: String,
+    ) -> Result<impl Stream<Item = TurnNotification>> {
+        let context = get_context(ctx);
+        let chain_id = chain_id.parse::<ChainId>()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid chain ID: {}", e)))?;
+
+        info!("Player {} subscribing to turn notifications on chain: {}", context.get_player_id(), chain_id);
+
+        let watch = TurnWatch {
+            query: self.query.clone(),
+            chain_id,
+            me: context.require_player_owner()?.to_string(),
+            was_my_turn: false,
+        };
+
+        Ok(stream::unfold(watch, |mut watch| async move {
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+
+                let room = match watch.query.load_draft_room(watch.chain_id).await {
+                    Ok(room) => room,
+                    Err(e) => {
+                        warn!("Turn notification poll failed for chain {}: {}", watch.chain_id, e.message);
+                        continue;
+                    }
+                };
+
+                if room.status == RoomStatus::Finished {
+                    return None;
+                }
+
+                let current = current_player(&room);
+                let notify = became_my_turn(watch.was_my_turn, current.as_deref(), &watch.me);
+                watch.was_my_turn = current.as_deref() == Some(watch.me.as_str());
+
+                if notify {
+                    let notification = TurnNotification {
+                        chain_id: watch.chain_id.to_string(),
+                        round: room.round,
+                    };
+                    return Some((notification, watch));
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifies_once_per_turn_transition() {
+        let polls = [None, Some("other"), Some("me"), Some("me"), Some("other"), Some("me")];
+
+        let mut was_my_turn = false;
+        let mut notified_at = Vec::new();
+        for (index, current) in polls.iter().enumerate() {
+            if became_my_turn(was_my_turn, *current, "me") {
+                notified_at.push(index);
+            }
+            was_my_turn = *current == Some("me");
+        }
+
+        assert_eq!(notified_at, vec![2, 5]);
+    }
+
+    #[test]
+    fn test_room_key_ignores_changes_outside_turn_round_status_players() {
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let fixture = DraftRoomFixture::mid_draft();
+        let state: serde_json::Value = serde_json::from_str(&fixture.state_json()).unwrap();
+        let room = futures::executor::block_on(QueryRoot::extract_draft_room_from_json(&state["DraftRoom"], ChainId::root(0)))
+            .unwrap()
+            .unwrap();
+        let base = room.into_room_state();
+
+        let mut repriced = base.clone();
+        repriced.pool.pop();
+        repriced.version += 1;
+        assert_eq!(RoomKey::of(&repriced), RoomKey::of(&base));
+
+        let mut next_turn = base.clone();
+        next_turn.current_turn += 1;
+        assert_ne!(RoomKey::of(&next_turn), RoomKey::of(&base));
+
+        let mut joined = base.clone();
+        joined.players.push("someone".to_string());
+        assert_ne!(RoomKey::of(&joined), RoomKey::of(&base));
+    }
+
+    #[tokio::test]
+    async fn test_room_snapshots_start_with_current_state() {
+        use futures::StreamExt;
+        use livedraft_arena::fixtures::DraftRoomFixture;
+
+        let state: serde_json::Value = serde_json::from_str(&DraftRoomFixture::mid_draft().state_json()).unwrap();
+        let current = QueryRoot::extract_draft_room_from_json(&state["DraftRoom"], ChainId::root(0))
+            .await
+            .unwrap()
+            .unwrap()
+            .into_room_state();
+
+        // The first read fails, as on a flaky reconnect; the next one is emitted as is
+        let mut reads = vec![Ok(current.clone()), Err(async_graphql::Error::new("node unavailable"))];
+        let snapshots = room_snapshots(ChainId::root(0), move || {
+            let read = reads.pop().expect("Only two reads before the first snapshot");
+            async move { read }
+        });
+        futures::pin_mut!(snapshots);
+
+        let first = snapshots.next().await.unwrap();
+        assert_eq!(first.version, current.version);
+        assert_eq!(RoomKey::of(&first), RoomKey::of(&current));
+        assert_eq!(first.pool.len(), current.pool.len());
+    }
+}
@@ -1,9 +1,12 @@
 use anyhow::Result;
-use linera_core::data_types::Owner;
+use linera_core::data_types::{ChainId, Owner};
 use sha2::{Digest, Sha256};
 use std::str::FromStr;
+use tracing::warn;
 use warp::http::HeaderMap;
 
+use crate::keys::player_keys;
+
 /// Player identity management for multi-user sessions
 /// 
 /// Each browser session gets a deterministic player ID that maps to a Linera Owner.
@@ -12,31 +15,27 @@ use warp::http::HeaderMap;
 const PLAYER_ID_HEADER: &str = "x-player-id";
 const PLAYER_ID_COOKIE: &str = "livedraft_player_id";
 
+/// Length of the checksum suffix appended to resume tokens
+const RESUME_CHECKSUM_LEN: usize = 8;
+
 /// Generate a deterministic Linera Owner from a player ID
 /// 
-/// This creates a consistent Owner address for each player session.
+/// This is the Owner of the player's signing key (see `keys`), so the
+/// address a session acts as is exactly the signer the contract sees.
 /// The same player ID will always generate the same Owner address.
+pub fn player_id_to_owner(player_id: &str) -> Owner {
+    player_keys().owner(player_id)
+}
+
+/// Derive the Owner for an externally supplied player ID
 /// 
-/// Process:
-/// 1. Hash "livedraft_player_" + player_id with SHA256
-/// 2. Convert hash to hex string (64 characters)
-/// 3. Parse as Linera Owner address
-/// 
-/// This ensures each browser session has a unique, persistent Linera identity
-/// that can sign transactions and own assets on the Linera network.
-pub fn player_id_to_owner(player_id: &str) -> Result<Owner> {
-    // Create a deterministic hash from the player ID
-    let mut hasher = Sha256::new();
-    hasher.update(b"livedraft_player_");
-    hasher.update(player_id.as_bytes());
-    let hash = hasher.finalize();
-    
-    // Convert hash to hex string (64 characters for Owner)
-    let owner_str = format!("{:x}", hash);
-    
-    // Parse as Owner (this creates a valid Linera Owner address)
-    Owner::from_str(&owner_str)
-        .map_err(|e| anyhow::anyhow!("Failed to create Owner from player ID: {}", e))
+/// Same mapping as `player_id_to_owner`, but rejects IDs the service would
+/// never issue, so tooling can't precompute owners no session can use.
+pub fn derive_owner(player_id: &str) -> Result<Owner> {
+    if !is_valid_player_id(player_id) {
+        anyhow::bail!("Player ID must be 16 hex characters");
+    }
+    Ok(player_id_to_owner(player_id))
 }
 
 /// Extract player ID from HTTP request headers or cookies
@@ -45,32 +44,67 @@ pub fn player_id_to_owner(player_id: &str) -> Result<Owner> {
 /// 1. x-player-id header (for explicit player identification)
 /// 2. livedraft_player_id cookie (for browser persistence)
 /// 3. Generate new player ID if none found
+/// 
+/// Any header value is honored: one that isn't already a player ID is
+/// normalized into one (see `normalize_player_id`), so a client's own stable
+/// ID always maps to the same Owner. A malformed cookie, or a header that
+/// isn't text, is logged and skipped rather than treated as "no identity".
 pub fn extract_player_id(headers: &HeaderMap) -> String {
-    // Try to get player ID from header first
-    if let Some(header_value) = headers.get(PLAYER_ID_HEADER) {
-        if let Ok(player_id) = header_value.to_str() {
-            if !player_id.is_empty() && is_valid_player_id(player_id) {
-                return player_id.to_string();
-            }
-        }
+    header_player_id(headers)
+        .or_else(|| cookie_player_id(headers))
+        .unwrap_or_else(generate_player_id)
+}
+
+/// Player ID from the x-player-id header, normalized, if present and non-empty
+fn header_player_id(headers: &HeaderMap) -> Option<String> {
+    let header_value = headers.get(PLAYER_ID_HEADER)?;
+    let Ok(player_id) = header_value.to_str() else {
+        warn!("Ignoring {} header that is not valid UTF-8", PLAYER_ID_HEADER);
+        return None;
+    };
+    let player_id = player_id.trim();
+    if player_id.is_empty() {
+        return None;
     }
-    
-    // Try to get player ID from cookie
-    if let Some(cookie_header) = headers.get("cookie") {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            for cookie in cookie_str.split(';') {
-                let cookie = cookie.trim();
-                if let Some(value) = cookie.strip_prefix(&format!("{}=", PLAYER_ID_COOKIE)) {
-                    if !value.is_empty() && is_valid_player_id(value) {
-                        return value.to_string();
-                    }
+    Some(normalize_player_id(player_id))
+}
+
+/// Map an externally chosen ID onto a player ID
+/// 
+/// IDs that are already 16 hex characters are kept; anything else is hashed
+/// into one, so the same external ID always yields the same player.
+fn normalize_player_id(external_id: &str) -> String {
+    if is_valid_player_id(external_id) {
+        return external_id.to_string();
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"livedraft_external_id_");
+    hasher.update(external_id.as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// Player ID from the livedraft_player_id cookie, if present and well-formed
+/// 
+/// Cookies may arrive split across several `cookie` headers (e.g. over HTTP/2),
+/// so every one is searched.
+fn cookie_player_id(headers: &HeaderMap) -> Option<String> {
+    let prefix = format!("{}=", PLAYER_ID_COOKIE);
+    for cookie_header in headers.get_all("cookie") {
+        let Ok(cookie_str) = cookie_header.to_str() else {
+            warn!("Ignoring cookie header that is not valid UTF-8");
+            continue;
+        };
+        for cookie in cookie_str.split(';') {
+            if let Some(value) = cookie.trim().strip_prefix(&prefix) {
+                if is_valid_player_id(value) {
+                    return Some(value.to_string());
                 }
+                warn!("Ignoring malformed {} cookie", PLAYER_ID_COOKIE);
             }
         }
     }
-    
-    // Generate new player ID if none found
-    generate_player_id()
+    None
 }
 
 /// Generate a new random player ID
@@ -117,22 +151,64 @@ pub fn create_player_id_cookie(player_id: &str) -> String {
     )
 }
 
+/// Encode a room and player into an opaque, URL-safe resume token
+/// 
+/// The token is the chain ID and player ID in hex followed by a short
+/// checksum, so a mistyped or truncated link is rejected instead of
+/// resolving to the wrong room or player.
+pub fn encode_resume_token(chain_id: &ChainId, player_id: &str) -> String {
+    let payload = format!("{}{}", chain_id, player_id);
+    let checksum = resume_checksum(&payload);
+    format!("{}{}", payload, checksum)
+}
+
+/// Decode a resume token back into its room and player
+pub fn decode_resume_token(token: &str) -> Result<(ChainId, String)> {
+    if !token.is_ascii() || token.len() <= RESUME_CHECKSUM_LEN + 16 {
+        anyhow::bail!("Resume token is too short");
+    }
+
+    let (payload, checksum) = token.split_at(token.len() - RESUME_CHECKSUM_LEN);
+    if resume_checksum(payload) != checksum {
+        anyhow::bail!("Resume token checksum mismatch");
+    }
+
+    let (chain_id_str, player_id) = payload.split_at(payload.len() - 16);
+    if !is_valid_player_id(player_id) {
+        anyhow::bail!("Resume token contains an invalid player ID");
+    }
+
+    let chain_id = ChainId::from_str(chain_id_str)
+        .map_err(|e| anyhow::anyhow!("Resume token contains an invalid chain ID: {}", e))?;
+
+    Ok((chain_id, player_id.to_string()))
+}
+
+/// Short checksum guarding resume tokens against corruption
+fn resume_checksum(payload: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"livedraft_resume_");
+    hasher.update(payload.as_bytes());
+    format!("{:x}", hasher.finalize())[..RESUME_CHECKSUM_LEN].to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use warp::http::HeaderValue;
     
     #[test]
     fn test_player_id_to_owner_deterministic() {
         let player_id = "1234567890abcdef";
-        let owner1 = player_id_to_owner(player_id).unwrap();
-        let owner2 = player_id_to_owner(player_id).unwrap();
+        let owner1 = player_id_to_owner(player_id);
+        let owner2 = player_id_to_owner(player_id);
         assert_eq!(owner1, owner2);
     }
     
     #[test]
     fn test_different_player_ids_different_owners() {
-        let owner1 = player_id_to_owner("1234567890abcdef").unwrap();
-        let owner2 = player_id_to_owner("fedcba0987654321").unwrap();
+        let owner1 = player_id_to_owner("1234567890abcdef");
+        let owner2 = player_id_to_owner("fedcba0987654321");
         assert_ne!(owner1, owner2);
     }
     
@@ -142,4 +218,88 @@ mod tests {
         assert!(!is_valid_player_id("invalid"));
         assert!(!is_valid_player_id("123")); // too short
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_valid_cookie_survives_garbage_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(PLAYER_ID_HEADER, HeaderValue::from_bytes(b"\xff\xfe").unwrap());
+        headers.insert("cookie", HeaderValue::from_static("theme=dark; livedraft_player_id=1234567890abcdef"));
+        assert_eq!(extract_player_id(&headers), "1234567890abcdef");
+
+        headers.insert(PLAYER_ID_HEADER, HeaderValue::from_static(""));
+        assert_eq!(extract_player_id(&headers), "1234567890abcdef");
+    }
+
+    #[test]
+    fn test_valid_header_wins_over_cookie_and_bad_cookies_skipped() {
+        let mut headers = HeaderMap::new();
+        headers.append("cookie", HeaderValue::from_bytes(b"livedraft_player_id=\xff").unwrap());
+        headers.append("cookie", HeaderValue::from_static("livedraft_player_id=short"));
+        headers.append("cookie", HeaderValue::from_static("livedraft_player_id=fedcba0987654321"));
+        assert_eq!(extract_player_id(&headers), "fedcba0987654321");
+
+        headers.insert(PLAYER_ID_HEADER, HeaderValue::from_static("1234567890abcdef"));
+        assert_eq!(extract_player_id(&headers), "1234567890abcdef");
+    }
+
+    #[test]
+    fn test_derive_owner_matches_player_id_to_owner() {
+        let player_id = "1234567890abcdef";
+        assert_eq!(derive_owner(player_id).unwrap(), player_id_to_owner(player_id));
+        assert_ne!(derive_owner(player_id).unwrap(), derive_owner("fedcba0987654321").unwrap());
+        assert!(derive_owner("invalid").is_err());
+        assert!(derive_owner("1234567890abcdeg").is_err()); // not hex
+    }
+    
+    #[test]
+    fn test_resume_token_round_trip() {
+        let chain_id = ChainId::root(0);
+        let token = encode_resume_token(&chain_id, "1234567890abcdef");
+        let (decoded_chain, decoded_player) = decode_resume_token(&token).unwrap();
+        assert_eq!(decoded_chain, chain_id);
+        assert_eq!(decoded_player, "1234567890abcdef");
+    }
+    
+    #[test]
+    fn test_malformed_resume_tokens_rejected() {
+        let token = encode_resume_token(&ChainId::root(0), "1234567890abcdef");
+        assert!(decode_resume_token("").is_err());
+        assert!(decode_resume_token(&token[..token.len() - 1]).is_err()); // truncated
+        assert!(decode_resume_token(&token.replacen('0', "1", 1)).is_err()); // corrupted
+        assert!(decode_resume_token("not a resume token at all, clearly").is_err());
+    }
+
+    #[test]
+    fn test_players_sign_as_their_derived_owners() {
+        let (alice, bob) = ("1234567890abcdef", "fedcba0987654321");
+        let signer = |player_id| Owner::from(player_keys().key_pair(player_id).public());
+
+        assert_ne!(signer(alice), signer(bob));
+        assert_eq!(signer(alice), player_id_to_owner(alice));
+    }
+
+    #[test]
+    fn test_uuid_header_is_normalized_stably() {
+        let mut headers = HeaderMap::new();
+        headers.insert(PLAYER_ID_HEADER, HeaderValue::from_static("550e8400e29b41d4a716446655440000"));
+        headers.insert("cookie", HeaderValue::from_static("livedraft_player_id=fedcba0987654321"));
+
+        let player_id = extract_player_id(&headers);
+        assert!(is_valid_player_id(&player_id));
+        assert_ne!(player_id, "fedcba0987654321");
+        assert_eq!(extract_player_id(&headers), player_id);
+        assert_ne!(normalize_player_id("550e8400e29b41d4a716446655440001"), player_id);
+    }
+
+    #[test]
+    fn test_short_alphanumeric_header_is_normalized_stably() {
+        let mut headers = HeaderMap::new();
+        headers.insert(PLAYER_ID_HEADER, HeaderValue::from_static("alice42"));
+
+        let player_id = extract_player_id(&headers);
+        assert!(is_valid_player_id(&player_id));
+        assert_eq!(player_id, normalize_player_id("alice42"));
+        assert_eq!(player_id_to_owner(&player_id), player_id_to_owner(&extract_player_id(&headers)));
+        assert_eq!(normalize_player_id("1234567890abcdef"), "1234567890abcdef");
+    }
+}
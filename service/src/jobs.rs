@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::types::{JobState, JobStatus, OperationResult};
+
+/// In-memory store of queued operations and their outcomes
+///
+/// `enqueueOperation` returns as soon as a job is recorded here; a background
+/// task submits the operation and reports back via `complete`, and clients
+/// poll `jobStatus` for the result. Jobs are dropped once they are older than
+/// the TTL, whether or not anyone collected them.
+
+/// How long a job is kept after it was enqueued
+const JOB_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct Job {
+    created_at: Instant,
+    result: Option<OperationResult>,
+}
+
+pub struct JobStore {
+    ttl: Duration,
+    jobs: RwLock<HashMap<String, Job>>,
+}
+
+impl Default for JobStore {
+    fn default() -> Self {
+        Self::with_ttl(JOB_TTL)
+    }
+}
+
+impl JobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            jobs: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a new pending job and return its ID
+    pub fn enqueue(&self) -> String {
+        let job_id = uuid::Uuid::new_v4().to_string();
+        let mut jobs = self.jobs.write().expect("Job store lock poisoned");
+        jobs.retain(|_, job| job.created_at.elapsed() < self.ttl);
+        jobs.insert(job_id.clone(), Job {
+            created_at: Instant::now(),
+            result: None,
+        });
+        job_id
+    }
+
+    /// Store the outcome of a job; ignored if the job has already expired
+    pub fn complete(&self, job_id: &str, result: OperationResult) {
+        let mut jobs = self.jobs.write().expect("Job store lock poisoned");
+        if let Some(job) = jobs.get_mut(job_id) {
+            job.result = Some(result);
+        }
+    }
+
+    /// Current status of a job, or `None` if it is unknown or expired
+    pub fn status(&self, job_id: &str) -> Option<JobStatus> {
+        let jobs = self.jobs.read().expect("Job store lock poisoned");
+        let job = jobs.get(job_id).filter(|job| job.created_at.elapsed() < self.ttl)?;
+
+        let state = match &job.result {
+            None => JobState::Pending,
+            Some(result) if result.success => JobState::Done,
+            Some(_) => JobState::Failed,
+        };
+        Some(JobStatus {
+            state,
+            result: job.result.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(success: bool) -> OperationResult {
+        OperationResult {
+            success,
+            message: "Item picked successfully".to_string(),
+            transaction_hash: None,
+            request_id: "request".to_string(),
+            server_time: crate::clock::now_rfc3339(),
+            state_version: None,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_poll_done() {
+        let jobs = JobStore::new();
+        let job_id = jobs.enqueue();
+
+        let pending = jobs.status(&job_id).unwrap();
+        assert_eq!(pending.state, JobState::Pending);
+        assert!(pending.result.is_none());
+
+        jobs.complete(&job_id, result(true));
+        let done = jobs.status(&job_id).unwrap();
+        assert_eq!(done.state, JobState::Done);
+        assert_eq!(done.result.unwrap().message, "Item picked successfully");
+
+        let failed_id = jobs.enqueue();
+        jobs.complete(&failed_id, result(false));
+        assert_eq!(jobs.status(&failed_id).unwrap().state, JobState::Failed);
+        assert!(jobs.status("unknown").is_none());
+    }
+
+    #[test]
+    fn test_jobs_expire_after_ttl() {
+        let jobs = JobStore::with_ttl(Duration::ZERO);
+        let job_id = jobs.enqueue();
+        assert!(jobs.status(&job_id).is_none());
+    }
+}
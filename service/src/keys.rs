@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use linera_core::data_types::Owner;
+use linera_sdk::base::crypto::KeyPair;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+/// Per-player signing keys
+///
+/// Every player signs their operations with a key derived from the
+/// service's secret and their player ID, so the contract's
+/// `authenticated_signer()` is the player's own Owner. Keys are derived on
+/// first use and cached for the life of the process. Set the secret with
+/// `PLAYER_KEY_SECRET`; the service refuses to start without it unless
+/// `ALLOW_DEV_PLAYER_KEYS=1` opts into a public development secret.
+
+/// Public secret used only with `ALLOW_DEV_PLAYER_KEYS=1`
+const DEV_SECRET: &[u8] = b"livedraft_player_";
+
+static PLAYER_KEYS: OnceLock<PlayerKeys> = OnceLock::new();
+
+pub struct PlayerKeys {
+    secret: Vec<u8>,
+    cache: RwLock<HashMap<String, Arc<KeyPair>>>,
+}
+
+impl PlayerKeys {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Read the secret from `PLAYER_KEY_SECRET`
+    ///
+    /// Without it, anyone who knows a player ID could derive that player's
+    /// key, so this fails unless `ALLOW_DEV_PLAYER_KEYS=1` is set.
+    pub fn from_env() -> anyhow::Result<Self> {
+        match std::env::var("PLAYER_KEY_SECRET") {
+            Ok(secret) if !secret.is_empty() => Ok(Self::new(secret)),
+            _ if std::env::var("ALLOW_DEV_PLAYER_KEYS").is_ok_and(|value| value == "1") => {
+                warn!("PLAYER_KEY_SECRET not set; using the public development secret, so player keys can be derived from player IDs alone");
+                Ok(Self::new(DEV_SECRET))
+            }
+            _ => Err(anyhow::anyhow!("PLAYER_KEY_SECRET must be set (or ALLOW_DEV_PLAYER_KEYS=1 for local development)")),
+        }
+    }
+
+    /// The player's signing key, derived on first use
+    pub fn key_pair(&self, player_id: &str) -> Arc<KeyPair> {
+        if let Some(key_pair) = self.cache.read().expect("Player key cache poisoned").get(player_id) {
+            return key_pair.clone();
+        }
+
+        let key_pair = Arc::new(derive_key_pair(&self.secret, player_id));
+        self.cache
+            .write()
+            .expect("Player key cache poisoned")
+            .entry(player_id.to_string())
+            .or_insert(key_pair)
+            .clone()
+    }
+
+    /// The Owner the player signs as
+    pub fn owner(&self, player_id: &str) -> Owner {
+        Owner::from(self.key_pair(player_id).public())
+    }
+}
+
+/// Derive a signing key from SHA256(secret + player_id)
+pub fn derive_key_pair(secret: &[u8], player_id: &str) -> KeyPair {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update(player_id.as_bytes());
+    let seed: [u8; 32] = hasher.finalize().into();
+
+    KeyPair::generate_from(&mut ChaCha20Rng::from_seed(seed))
+}
+
+/// Use `keys` for every player in this process
+///
+/// Call once at startup, before any request is served; later calls are ignored.
+pub fn init(keys: PlayerKeys) {
+    if PLAYER_KEYS.set(keys).is_err() {
+        info!("Player keys already initialized; keeping the existing secret");
+    }
+}
+
+/// The process-wide player keys set by `init`
+///
+/// Tests never call `init` and share keys derived from the development secret.
+pub fn player_keys() -> &'static PlayerKeys {
+    PLAYER_KEYS.get_or_init(|| {
+        if cfg!(test) {
+            PlayerKeys::new(DEV_SECRET)
+        } else {
+            panic!("Player keys used before keys::init")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_public_key_maps_to_owner() {
+        let keys = PlayerKeys::new("s3cret");
+        let expected = Owner::from(derive_key_pair(b"s3cret", "1234567890abcdef").public());
+
+        assert_eq!(keys.owner("1234567890abcdef"), expected);
+        assert_ne!(keys.owner("fedcba0987654321"), expected);
+        assert_ne!(PlayerKeys::new("other").owner("1234567890abcdef"), expected);
+    }
+
+    #[test]
+    fn test_keys_are_cached_per_player() {
+        let keys = PlayerKeys::new("s3cret");
+        assert!(Arc::ptr_eq(&keys.key_pair("1234567890abcdef"), &keys.key_pair("1234567890abcdef")));
+    }
+}
@@ -1,20 +1,46 @@
 use anyhow::{Context, Result};
-use async_graphql::{EmptySubscription, Schema};
-use async_graphql_warp::GraphQLBadRequest;
+use async_graphql::{Data, ObjectType, Schema, SubscriptionType};
+use async_graphql_warp::{GraphQLBadRequest, GraphQLProtocol, GraphQLWebSocket};
 use linera_client::{ClientContext, Options as ClientOptions};
 use linera_core::data_types::{ApplicationId, ChainId};
 use std::convert::Infallible;
 use std::path::PathBuf;
 use std::str::FromStr;
-use tracing::{info, warn};
+use std::sync::Arc;
+use tracing::{debug, info, warn, Instrument};
 use warp::{http::Response as HttpResponse, Filter, Rejection, Reply};
 
 mod graphql;
 mod types;
 mod identity;
-
-use graphql::{MutationRoot, QueryRoot, GraphQLContext};
+mod room_index;
+mod query_log;
+mod clock;
+mod reports;
+mod admin;
+mod jobs;
+mod op_limit;
+mod keys;
+mod share_code;
+
+use graphql::{MutationRoot, QueryRoot, SubscriptionRoot, GraphQLContext};
 use identity::{extract_player_id, create_player_id_cookie};
+use room_index::RoomIndex;
+use reports::ReportStore;
+use jobs::JobStore;
+use op_limit::OperationLimiter;
+
+/// The service's GraphQL schema
+type AppSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+/// Response header carrying the request's correlation ID
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Cache-Control for the playground page, which only changes with the service build
+const DEFAULT_PLAYGROUND_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Cache-Control for health checks, which must always reach the service
+const HEALTH_CACHE_CONTROL: &str = "no-cache";
 
 /// Conway testnet configuration
 const CONWAY_TESTNET_ENDPOINT: &str = "https://conway-testnet.linera.net:8080";
@@ -151,6 +177,41 @@ async fn get_default_chain_id(client: &ClientContext) -> Result<ChainId> {
     Ok(default_chain)
 }
 
+/// Cache-Control for the playground, from `PLAYGROUND_CACHE_CONTROL` if set
+/// 
+/// Deployments behind a CDN can shorten or lengthen how long the page is kept.
+fn playground_cache_control() -> String {
+    std::env::var("PLAYGROUND_CACHE_CONTROL")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PLAYGROUND_CACHE_CONTROL.to_string())
+}
+
+/// GraphQL playground page (for development)
+fn playground_route(cache_control: String) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    warp::path("playground")
+        .and(warp::get())
+        .map(move || {
+            HttpResponse::builder()
+                .header("content-type", "text/html")
+                .header("cache-control", cache_control.as_str())
+                .body(async_graphql::http::playground_source(
+                    async_graphql::http::GraphQLPlaygroundConfig::new("/graphql")
+                        .subscription_endpoint("/ws"),
+                ))
+        })
+}
+
+/// Health check endpoint, never cached
+fn health_route() -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    warp::path("health")
+        .and(warp::get())
+        .map(|| {
+            let reply = warp::reply::json(&serde_json::json!({"status": "ok"}));
+            warp::reply::with_header(reply, "cache-control", HEALTH_CACHE_CONTROL)
+        })
+}
+
 /// Handle GraphQL requests with player identity context
 /// 
 /// This is the core request handler that:
@@ -158,35 +219,71 @@ async fn get_default_chain_id(client: &ClientContext) -> Result<ChainId> {
 /// 2. Creates GraphQL context with player's Linera Owner
 /// 3. Executes GraphQL operations with proper authentication
 /// 4. Returns response with Set-Cookie for session persistence
-async fn graphql_handler(
-    schema: Schema<QueryRoot, MutationRoot, EmptySubscription>,
+/// 
+/// Every request gets a fresh `request_id`, attached to its tracing span and
+/// returned in the `X-Request-Id` header and in mutation results, so a user
+/// can quote it in bug reports.
+/// 
+/// With `log_queries` set, the query text and variables are also logged at
+/// debug level (passwords redacted) to help reproduce client issues.
+/// 
+/// Requests whose `X-Admin-Token` header matches `admin_token` may call
+/// admin-only queries.
+async fn graphql_handler<Query, Mutation, Subscription>(
+    schema: Schema<Query, Mutation, Subscription>,
+    log_queries: bool,
+    admin_token: Option<String>,
     headers: warp::http::HeaderMap,
     request: async_graphql::Request,
-) -> Result<impl Reply, Rejection> {
+) -> Result<impl Reply, Rejection>
+where
+    Query: ObjectType + 'static,
+    Mutation: ObjectType + 'static,
+    Subscription: SubscriptionType + 'static,
+{
     // Extract or generate player ID from request headers/cookies
     // This creates a deterministic Linera Owner address for the player
     let player_id = extract_player_id(&headers);
+    let request_id = new_request_id();
+    let span = tracing::info_span!("graphql_request", request_id = %request_id);
+
+    let response = async {
+        info!("Processing GraphQL request for player: {} (Owner will be derived)", player_id);
+
+        if log_queries && tracing::enabled!(tracing::Level::DEBUG) {
+            let variables = serde_json::to_value(&request.variables).unwrap_or_default();
+            debug!("GraphQL request for player {}: {}", player_id, query_log::describe_request(&request.query, &variables));
+        }
+        
+        // Create GraphQL context with player identity
+        // The context contains both the player ID and the derived Linera Owner
+        let context = GraphQLContext::new(player_id.clone(), request_id.clone())
+            .with_admin_token(&headers, admin_token.as_deref());
+        
+        // Execute GraphQL request with player context
+        // All mutations will use the player's Owner for signing operations
+        // All queries will have access to the player's identity for filtering
+        schema.execute(request.data(context)).await
+    }
+    .instrument(span)
+    .await;
     
-    info!("Processing GraphQL request for player: {} (Owner will be derived)", player_id);
-    
-    // Create GraphQL context with player identity
-    // The context contains both the player ID and the derived Linera Owner
-    let context = GraphQLContext::new(player_id.clone());
-    
-    // Execute GraphQL request with player context
-    // All mutations will use the player's Owner for signing operations
-    // All queries will have access to the player's identity for filtering
-    let response = schema.execute(request.data(context)).await;
-    
+    Ok(with_session_headers(async_graphql_warp::Response::from(response), &player_id, &request_id))
+}
+
+/// Generate a correlation ID for a new request
+fn new_request_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Attach the player ID cookie and request ID header to a reply
+fn with_session_headers(reply: impl Reply, player_id: &str, request_id: &str) -> impl Reply {
     // Create response with Set-Cookie header for player ID persistence
     // This ensures the same browser maintains the same Linera identity
-    let cookie_header = create_player_id_cookie(&player_id);
+    let cookie_header = create_player_id_cookie(player_id);
     
-    Ok(warp::reply::with_header(
-        async_graphql_warp::Response::from(response),
-        "Set-Cookie",
-        cookie_header,
-    ))
+    let reply = warp::reply::with_header(reply, "Set-Cookie", cookie_header);
+    warp::reply::with_header(reply, REQUEST_ID_HEADER, request_id)
 }
 
 /// Handle GraphQL errors
@@ -237,38 +334,87 @@ async fn main() -> Result<()> {
     info!("🏛️  Lobby operations will execute on chain: {}", default_chain_id);
     info!("🏠 DraftRoom operations will execute on individual microchains");
 
+    // Player -> room index backing myRooms (persisted when STATE_DIR is set)
+    let room_index = Arc::new(RoomIndex::from_env()?);
+
+    // Player reports against rooms, reviewed via reportedRooms
+    let reports = Arc::new(ReportStore::new());
+
+    // Operations queued by enqueueOperation, polled via jobStatus
+    let jobs = Arc::new(JobStore::new());
+
+    // Cap on operations submitted to the node at once; excess mutations queue
+    let limiter = Arc::new(OperationLimiter::from_env()?);
+
+    // Per-player signing keys, derived from PLAYER_KEY_SECRET
+    keys::init(keys::PlayerKeys::from_env()?);
+
+    // Optional lag on picks shown to non-participants, for streamed drafts
+    let reveal_delay = std::env::var("REVEAL_DELAY_SECS")
+        .ok()
+        .map(|secs| secs.parse::<i64>().context("Invalid REVEAL_DELAY_SECS environment variable"))
+        .transpose()?
+        .filter(|secs| *secs > 0)
+        .map(chrono::Duration::seconds);
+    if let Some(delay) = reveal_delay {
+        info!("Picks revealed to non-participants after {}s", delay.num_seconds());
+    }
+
     // Create GraphQL schema
-    let schema = Schema::build(
-        QueryRoot::new(client.clone(), app_id, default_chain_id),
-        MutationRoot::new(client, app_id, default_chain_id),
-        EmptySubscription,
+    let query = QueryRoot::new(client.clone(), app_id, default_chain_id, room_index.clone(), reports.clone(), jobs.clone())
+        .with_reveal_delay(reveal_delay);
+    let schema: AppSchema = Schema::build(
+        query.clone(),
+        MutationRoot::new(client, app_id, default_chain_id, room_index, reports, jobs, limiter, query.clone()),
+        SubscriptionRoot::new(query),
     )
     .finish();
 
+    // Optional debug logging of query text (never at info level)
+    let log_queries = query_log::enabled_from_env();
+    if log_queries {
+        info!("GraphQL query logging enabled at debug level");
+    }
+
+    // Admin-only queries are disabled unless ADMIN_TOKEN is set
+    let admin_token = admin::admin_token_from_env();
+    if admin_token.is_some() {
+        info!("Admin queries enabled via X-Admin-Token");
+    }
+
     // Create GraphQL endpoint with player identity handling
     let graphql_route = warp::path("graphql")
         .and(warp::post())
         .and(warp::headers_cloned()) // Extract headers for player ID
         .and(async_graphql_warp::graphql(schema.clone()))
         .and_then(move |headers, request| {
-            graphql_handler(schema.clone(), headers, request)
+            graphql_handler(schema.clone(), log_queries, admin_token.clone(), headers, request)
         });
 
-    // Create GraphQL playground (for development)
-    let playground_route = warp::path("playground")
-        .and(warp::get())
-        .map(|| {
-            HttpResponse::builder()
-                .header("content-type", "text/html")
-                .body(async_graphql::http::playground_source(
-                    async_graphql::http::GraphQLPlaygroundConfig::new("/graphql"),
-                ))
+    // GraphQL subscriptions over WebSocket, with the same player identity as HTTP
+    let subscription_schema = schema.clone();
+    let subscription_route = warp::path("ws")
+        .and(warp::ws())
+        .and(warp::headers_cloned())
+        .and(async_graphql_warp::graphql_protocol())
+        .map(move |ws: warp::ws::Ws, headers: warp::http::HeaderMap, protocol: GraphQLProtocol| {
+            let schema = subscription_schema.clone();
+            let player_id = extract_player_id(&headers);
+            let reply = ws.on_upgrade(move |socket| {
+                let mut data = Data::default();
+                data.insert(GraphQLContext::new(player_id, new_request_id()));
+                GraphQLWebSocket::new(socket, schema, protocol)
+                    .with_data(data)
+                    .serve()
+            });
+            warp::reply::with_header(reply, "Sec-WebSocket-Protocol", protocol.sec_websocket_protocol())
         });
 
+    // Create GraphQL playground (for development)
+    let playground_route = playground_route(playground_cache_control());
+
     // Health check endpoint
-    let health_route = warp::path("health")
-        .and(warp::get())
-        .map(|| warp::reply::json(&serde_json::json!({"status": "ok"})));
+    let health_route = health_route();
 
     // Production CORS configuration
     // Allow specific origins in production, any origin in development
@@ -279,19 +425,22 @@ async fn main() -> Result<()> {
         info!("🌐 CORS: Allowing all origins (development mode)");
         warp::cors()
             .allow_any_origin()
-            .allow_headers(vec!["content-type", "x-player-id", "cookie"])
+            .allow_headers(vec!["content-type", "x-player-id", "x-admin-token", "cookie"])
             .allow_methods(vec!["GET", "POST", "OPTIONS"])
+            .expose_headers(vec!["x-request-id"])
     } else {
         info!("🌐 CORS: Allowing specific origins: {}", cors_origins);
         let origins: Vec<&str> = cors_origins.split(',').map(|s| s.trim()).collect();
         warp::cors()
             .allow_origins(origins)
-            .allow_headers(vec!["content-type", "x-player-id", "cookie"])
+            .allow_headers(vec!["content-type", "x-player-id", "x-admin-token", "cookie"])
             .allow_methods(vec!["GET", "POST", "OPTIONS"])
+            .expose_headers(vec!["x-request-id"])
     };
 
     // Combine all routes
     let routes = graphql_route
+        .or(subscription_route)
         .or(playground_route)
         .or(health_route)
         .with(cors)
@@ -310,6 +459,7 @@ async fn main() -> Result<()> {
     info!("🚀 LiveDraft Arena service ready!");
     info!("🌐 Binding to: {}:{}", bind_address, port);
     info!("GraphQL endpoint: http://{}:{}/graphql", bind_address, port);
+    info!("GraphQL subscriptions: ws://{}:{}/ws", bind_address, port);
     info!("GraphQL playground: http://{}:{}/playground", bind_address, port);
     info!("Health check: http://{}:{}/health", bind_address, port);
     info!("🔐 Multi-user identity: Cookie + header based");
@@ -326,4 +476,60 @@ async fn main() -> Result<()> {
         .await;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{Context as GraphQLResolverContext, EmptyMutation, EmptySubscription, Object};
+
+    /// The real roots need a Linera client, so this one only echoes the request context
+    struct RequestIdQuery;
+
+    #[Object]
+    impl RequestIdQuery {
+        async fn request_id(&self, ctx: &GraphQLResolverContext<'_>) -> String {
+            graphql::get_context(ctx).request_id.clone()
+        }
+    }
+
+    /// Send `{ requestId }` through `graphql_handler`, returning the header and resolved IDs
+    async fn request_ids(schema: &Schema<RequestIdQuery, EmptyMutation, EmptySubscription>) -> (String, String) {
+        let request = async_graphql::Request::new("{ requestId }");
+        let response = graphql_handler(schema.clone(), false, None, warp::http::HeaderMap::new(), request)
+            .await
+            .unwrap()
+            .into_response();
+
+        let header = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+        let body = warp::hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (header, body["data"]["requestId"].as_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_matches_header_and_result() {
+        let schema = Schema::build(RequestIdQuery, EmptyMutation, EmptySubscription).finish();
+
+        let (header, resolved) = request_ids(&schema).await;
+        assert_eq!(header, resolved);
+
+        // Every request gets its own ID
+        let (next_header, _) = request_ids(&schema).await;
+        assert_ne!(next_header, header);
+    }
+
+    #[tokio::test]
+    async fn test_playground_and_health_cache_headers() {
+        let playground = warp::test::request()
+            .path("/playground")
+            .reply(&playground_route(DEFAULT_PLAYGROUND_CACHE_CONTROL.to_string()))
+            .await;
+        assert_eq!(playground.status(), 200);
+        assert_eq!(playground.headers()["cache-control"], DEFAULT_PLAYGROUND_CACHE_CONTROL);
+
+        let health = warp::test::request().path("/health").reply(&health_route()).await;
+        assert_eq!(health.status(), 200);
+        assert_eq!(health.headers()["cache-control"], HEALTH_CACHE_CONTROL);
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use linera_core::data_types::ChainId;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard, Semaphore, SemaphorePermit};
+use tracing::{debug, info};
+
+/// Per-instance cap on operations submitted to the node at once
+///
+/// Every mutation holds a permit while its operation is being executed, so a
+/// burst of requests queues here instead of flooding the node. Set the limit
+/// with `MAX_CONCURRENT_OPS`.
+///
+/// Operations are also serialized per (player, chain): one player's requests
+/// against a room run in order, while the same player acting in another room
+/// never waits on them.
+
+/// Permits available when `MAX_CONCURRENT_OPS` is unset
+pub const DEFAULT_MAX_CONCURRENT_OPS: usize = 16;
+
+/// Queue waits at least this long are logged at info level
+const SLOW_WAIT: Duration = Duration::from_millis(500);
+
+type RoomLocks = HashMap<(String, ChainId), Arc<AsyncMutex<()>>>;
+
+pub struct OperationLimiter {
+    permits: Semaphore,
+    room_locks: Mutex<RoomLocks>,
+}
+
+/// Held while an operation executes; releases its slot and room lock on drop
+pub struct OperationPermit<'a> {
+    _slot: SemaphorePermit<'a>,
+    _room: OwnedMutexGuard<()>,
+}
+
+impl Default for OperationLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_OPS)
+    }
+}
+
+impl OperationLimiter {
+    /// A limiter allowing `max_concurrent` operations at once (at least one)
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            permits: Semaphore::new(max_concurrent.max(1)),
+            room_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Read the limit from `MAX_CONCURRENT_OPS`, falling back to the default
+    pub fn from_env() -> anyhow::Result<Self> {
+        match std::env::var("MAX_CONCURRENT_OPS") {
+            Ok(value) => {
+                let max_concurrent = value.parse::<usize>()
+                    .map_err(|e| anyhow::anyhow!("Invalid MAX_CONCURRENT_OPS environment variable: {}", e))?;
+                Ok(Self::new(max_concurrent))
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Wait for the player's turn on the chain and a free slot, logging how long the operation queued
+    ///
+    /// Both are released when the returned permit is dropped.
+    pub async fn acquire(&self, action: &str, player_id: &str, chain_id: ChainId) -> OperationPermit<'_> {
+        let queued_at = Instant::now();
+        // Take the room lock first, so operations queued behind it don't hold a slot
+        let room = self.room_lock(player_id, chain_id).lock_owned().await;
+        let slot = self.permits.acquire().await.expect("Operation limiter is never closed");
+
+        let waited = queued_at.elapsed();
+        if waited >= SLOW_WAIT {
+            info!("Operation to {} queued for {}ms behind the concurrency limit", action, waited.as_millis());
+        } else {
+            debug!("Operation to {} queued for {}ms", action, waited.as_millis());
+        }
+        OperationPermit { _slot: slot, _room: room }
+    }
+
+    /// The lock for one player's operations on one chain
+    fn room_lock(&self, player_id: &str, chain_id: ChainId) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.room_locks.lock().expect("Room lock table poisoned");
+        // Forget locks that nobody holds or is waiting on
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks.entry((player_id.to_string(), chain_id)).or_default().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_operations_beyond_limit_wait_for_a_permit() {
+        let limiter = Arc::new(OperationLimiter::new(2));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..6)
+            .map(|n| {
+                let (limiter, running, peak) = (limiter.clone(), running.clone(), peak.clone());
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire("pick item", &format!("player-{}", n), ChainId::root(0)).await;
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now_running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_same_player_in_two_rooms_does_not_block() {
+        let limiter = OperationLimiter::new(4);
+        let wait = Duration::from_millis(50);
+        let (room_a, room_b) = (ChainId::root(1), ChainId::root(2));
+
+        let _in_room_a = limiter.acquire("pick item", "player", room_a).await;
+
+        // The same player's operation in another room goes straight through
+        let in_room_b = tokio::time::timeout(wait, limiter.acquire("pick item", "player", room_b)).await;
+        assert!(in_room_b.is_ok());
+
+        // A second operation in the first room waits for the one in flight
+        let again_in_room_a = tokio::time::timeout(wait, limiter.acquire("pick item", "player", room_a)).await;
+        assert!(again_in_room_a.is_err());
+
+        // Another player in the first room is unaffected
+        let other_player = tokio::time::timeout(wait, limiter.acquire("pick item", "other", room_a)).await;
+        assert!(other_player.is_ok());
+    }
+}
@@ -0,0 +1,173 @@
+use serde_json::Value;
+
+/// Debug logging of incoming GraphQL requests
+///
+/// Query text and variables are only logged when `LOG_GRAPHQL_QUERIES` is set
+/// and the log level includes debug. Any argument or variable whose name
+/// contains "password" is replaced before the text reaches the log.
+
+const REDACTED: &str = "\"[REDACTED]\"";
+
+/// Whether query logging was requested via `LOG_GRAPHQL_QUERIES`
+pub fn enabled_from_env() -> bool {
+    std::env::var("LOG_GRAPHQL_QUERIES")
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Render a request's query and variables for the log with passwords scrubbed
+pub fn describe_request(query: &str, variables: &Value) -> String {
+    let mut variables = variables.clone();
+    redact_variables(&mut variables);
+    format!("query: {} | variables: {}", redact_query(query), variables)
+}
+
+fn is_password_key(name: &str) -> bool {
+    name.to_ascii_lowercase().contains("password")
+}
+
+/// Replace inline `password: <value>` arguments in GraphQL query text
+///
+/// Variable references (`password: $pw`) and variable definitions
+/// (`$password: String`) carry no secret and are left alone.
+fn redact_query(query: &str) -> String {
+    let bytes = query.as_bytes();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut output = String::with_capacity(query.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !is_ident(bytes[i]) {
+            // Skip string literals so their contents are never read as names
+            if bytes[i] == b'"' {
+                i = string_end(bytes, i);
+            } else {
+                i += 1;
+            }
+            continue;
+        }
+
+        let name_start = i;
+        while i < bytes.len() && is_ident(bytes[i]) {
+            i += 1;
+        }
+        let is_variable = name_start > 0 && bytes[name_start - 1] == b'$';
+        if is_variable || !is_password_key(&query[name_start..i]) {
+            continue;
+        }
+
+        let mut value_start = i;
+        while value_start < bytes.len() && bytes[value_start].is_ascii_whitespace() {
+            value_start += 1;
+        }
+        if bytes.get(value_start) != Some(&b':') {
+            continue;
+        }
+        value_start += 1;
+        while value_start < bytes.len() && bytes[value_start].is_ascii_whitespace() {
+            value_start += 1;
+        }
+        if value_start >= bytes.len() || bytes[value_start] == b'$' {
+            continue;
+        }
+
+        let value_end = if bytes[value_start] == b'"' {
+            string_end(bytes, value_start)
+        } else {
+            let mut end = value_start;
+            while end < bytes.len() && !b",)} \t\r\n".contains(&bytes[end]) {
+                end += 1;
+            }
+            end
+        };
+
+        output.push_str(&query[copied..value_start]);
+        output.push_str(REDACTED);
+        copied = value_end;
+        i = value_end;
+    }
+
+    output.push_str(&query[copied..]);
+    output
+}
+
+/// Index just past the string literal starting at `start`
+///
+/// Block strings (`"""..."""`) run to the next unescaped `"""`.
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    if bytes[start..].starts_with(b"\"\"\"") {
+        let mut i = start + 3;
+        while i < bytes.len() {
+            if bytes[i..].starts_with(b"\\\"\"\"") {
+                i += 4;
+            } else if bytes[i..].starts_with(b"\"\"\"") {
+                return i + 3;
+            } else {
+                i += 1;
+            }
+        }
+        return bytes.len();
+    }
+
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Replace the value of every password-like key in a variables object
+fn redact_variables(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if is_password_key(key) {
+                    *entry = Value::String("[REDACTED]".to_string());
+                } else {
+                    redact_variables(entry);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_variables),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_password_fields_redacted() {
+        let query = r#"mutation($roomPassword: String) { joinRoom(chainId: "abc", password: "hunter2") { success } other: joinRoom(chainId: "def", password: $roomPassword) { success } }"#;
+        let variables = json!({ "roomPassword": "s3cret", "input": { "roomName": "Friday", "password": "p@ss" } });
+
+        let logged = describe_request(query, &variables);
+
+        assert!(!logged.contains("hunter2"));
+        assert!(!logged.contains("s3cret"));
+        assert!(!logged.contains("p@ss"));
+        assert!(logged.contains(r#"password: "[REDACTED]""#));
+        assert!(logged.contains("$roomPassword: String"));
+        assert!(logged.contains("password: $roomPassword"));
+        assert!(logged.contains("Friday"));
+
+        let block = r#"mutation { joinRoom(chainId: "abc", password: """hunter2 \""" still secret""") { success } }"#;
+        let logged = redact_query(block);
+        assert!(!logged.contains("hunter2"));
+        assert!(!logged.contains("still secret"));
+        assert_eq!(logged, r#"mutation { joinRoom(chainId: "abc", password: "[REDACTED]") { success } }"#);
+    }
+
+    #[test]
+    fn test_queries_without_passwords_unchanged() {
+        let query = r#"{ roomState(chainId: "password") { round } }"#;
+        assert_eq!(redact_query(query), query);
+    }
+}
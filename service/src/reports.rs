@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::types::ReportedRoom;
+
+/// Service-side store of player reports against rooms, for moderation
+///
+/// Each player counts at most once per room, so a single player can't inflate
+/// a room's report count. Reports are kept in memory only; they are a review
+/// queue for operators, not a record of truth.
+
+/// Longest report reason kept, in characters
+const MAX_REASON_LEN: usize = 200;
+
+/// A room's reports, keyed by reporting player ID
+type RoomReports = HashMap<String, String>;
+
+#[derive(Default)]
+pub struct ReportStore {
+    reports: RwLock<HashMap<String, RoomReports>>,
+}
+
+impl ReportStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a player's report against a room
+    ///
+    /// Returns `false` if this player had already reported the room; their
+    /// reason is updated but the count is unchanged.
+    pub fn report(&self, chain_id: &str, player_id: &str, reason: &str) -> bool {
+        let reason: String = reason.trim().chars().take(MAX_REASON_LEN).collect();
+        let mut reports = self.reports.write().expect("Report store lock poisoned");
+        reports
+            .entry(chain_id.to_string())
+            .or_default()
+            .insert(player_id.to_string(), reason)
+            .is_none()
+    }
+
+    /// Reported rooms, most reported first
+    pub fn reported_rooms(&self) -> Vec<ReportedRoom> {
+        let reports = self.reports.read().expect("Report store lock poisoned");
+        let mut rooms: Vec<ReportedRoom> = reports
+            .iter()
+            .map(|(chain_id, room_reports)| {
+                let mut reasons: Vec<String> = room_reports
+                    .values()
+                    .filter(|reason| !reason.is_empty())
+                    .cloned()
+                    .collect();
+                reasons.sort();
+                ReportedRoom {
+                    chain_id: chain_id.clone(),
+                    report_count: room_reports.len() as u32,
+                    reasons,
+                }
+            })
+            .collect();
+
+        rooms.sort_by(|a, b| {
+            b.report_count.cmp(&a.report_count).then_with(|| a.chain_id.cmp(&b.chain_id))
+        });
+        rooms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_reports_count_once() {
+        let store = ReportStore::new();
+        assert!(store.report("chain-a", "1234567890abcdef", "spam"));
+        assert!(!store.report("chain-a", "1234567890abcdef", "spam again"));
+        assert!(store.report("chain-a", "fedcba0987654321", "abusive name"));
+        assert!(store.report("chain-b", "1234567890abcdef", ""));
+
+        let rooms = store.reported_rooms();
+        assert_eq!(rooms[0].chain_id, "chain-a");
+        assert_eq!(rooms[0].report_count, 2);
+        assert_eq!(rooms[0].reasons, vec!["abusive name", "spam again"]);
+        assert_eq!(rooms[1].report_count, 1);
+        assert!(rooms[1].reasons.is_empty());
+    }
+}
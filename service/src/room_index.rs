@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+/// Service-side index of the rooms each player has joined
+///
+/// This lets `myRooms` look up a player's rooms directly instead of scanning
+/// every DraftRoom chain. Entries are hints only: callers verify membership
+/// against on-chain state before trusting them.
+///
+/// When `STATE_DIR` is set the index is persisted there as JSON so it
+/// survives restarts; otherwise it lives in memory only.
+
+const INDEX_FILE_NAME: &str = "player_rooms.json";
+
+pub struct RoomIndex {
+    path: Option<PathBuf>,
+    entries: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl RoomIndex {
+    /// Create an index persisted under `STATE_DIR`, or in-memory if unset
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("STATE_DIR") {
+            Ok(dir) => Self::load(Some(PathBuf::from(dir).join(INDEX_FILE_NAME))),
+            Err(_) => {
+                info!("STATE_DIR not set, player room index will not be persisted");
+                Self::load(None)
+            }
+        }
+    }
+
+    /// Load an index from `path`, starting empty if the file doesn't exist yet
+    pub fn load(path: Option<PathBuf>) -> Result<Self> {
+        let entries = match &path {
+            Some(path) if path.exists() => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read room index {}", path.display()))?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Invalid room index {}", path.display()))?
+            }
+            _ => HashMap::new(),
+        };
+
+        Ok(Self {
+            path,
+            entries: RwLock::new(entries),
+        })
+    }
+
+    /// Record that a player is in a room
+    pub fn record(&self, player_id: &str, chain_id: &str) {
+        let mut entries = self.entries.write().expect("Room index lock poisoned");
+        let rooms = entries.entry(player_id.to_string()).or_default();
+        if rooms.iter().any(|room| room == chain_id) {
+            return;
+        }
+        rooms.push(chain_id.to_string());
+        self.persist(&entries);
+    }
+
+    /// Forget that a player is in a room
+    pub fn remove(&self, player_id: &str, chain_id: &str) {
+        let mut entries = self.entries.write().expect("Room index lock poisoned");
+        if let Some(rooms) = entries.get_mut(player_id) {
+            rooms.retain(|room| room != chain_id);
+            self.persist(&entries);
+        }
+    }
+
+    /// Rooms recorded for a player, in the order they were joined
+    pub fn rooms_for(&self, player_id: &str) -> Vec<String> {
+        self.entries
+            .read()
+            .expect("Room index lock poisoned")
+            .get(player_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Write the index to disk; failures are logged since the index is only a cache
+    fn persist(&self, entries: &HashMap<String, Vec<String>>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let result = (|| -> Result<()> {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            // Write then rename so a crash never leaves a truncated index
+            let tmp_path = path.with_extension("json.tmp");
+            std::fs::write(&tmp_path, serde_json::to_string(entries)?)?;
+            std::fs::rename(&tmp_path, path)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            warn!("Failed to persist room index to {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_index_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("livedraft_{}_{}", name, std::process::id()))
+            .join(INDEX_FILE_NAME)
+    }
+
+    #[test]
+    fn test_joining_updates_index() {
+        let index = RoomIndex::load(None).unwrap();
+        index.record("1234567890abcdef", "chain-a");
+        index.record("1234567890abcdef", "chain-a"); // duplicate join is a no-op
+        index.record("1234567890abcdef", "chain-b");
+
+        assert_eq!(index.rooms_for("1234567890abcdef"), vec!["chain-a", "chain-b"]);
+        assert!(index.rooms_for("fedcba0987654321").is_empty());
+    }
+
+    #[test]
+    fn test_index_persists_across_reload() {
+        let path = temp_index_path("room_index");
+        let _ = std::fs::remove_file(&path);
+
+        let index = RoomIndex::load(Some(path.clone())).unwrap();
+        index.record("1234567890abcdef", "chain-a");
+        drop(index);
+
+        let reloaded = RoomIndex::load(Some(path.clone())).unwrap();
+        assert_eq!(reloaded.rooms_for("1234567890abcdef"), vec!["chain-a"]);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use livedraft_arena::draft_room::{validate_captains, validate_draft_config, validate_pool, DEFAULT_MAX_ROUNDS};
+use livedraft_arena::{DraftRoom, RoomOptions, MAX_ROOM_CAPACITY, MIN_PLAYERS_TO_START};
+use serde::{Deserialize, Serialize};
+
+/// Portable room templates
+///
+/// A share code is a room's settings as JSON in URL-safe base64, so a
+/// community can pass a draft format around and anyone can open a room just
+/// like it. Only settings travel in the code: players, picks and the pool
+/// seed stay with the original room. JSON keeps codes readable by newer
+/// services, since options added later decode to their defaults.
+
+/// A room's settings, without any of its draft state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoomTemplate {
+    pub max_players: u8,
+    pub options: RoomOptions,
+}
+
+/// Encode a template into a share code
+pub fn encode_share_code(template: &RoomTemplate) -> String {
+    let json = serde_json::to_vec(template).expect("Room templates always serialize");
+    URL_SAFE_NO_PAD.encode(json)
+}
+
+/// Decode a share code, rejecting settings the Lobby would refuse
+pub fn decode_share_code(code: &str) -> Result<RoomTemplate> {
+    let json = URL_SAFE_NO_PAD
+        .decode(code.trim())
+        .map_err(|e| anyhow::anyhow!("Share code is not valid base64: {}", e))?;
+    let template: RoomTemplate = serde_json::from_slice(&json)
+        .map_err(|e| anyhow::anyhow!("Share code does not describe a room: {}", e))?;
+
+    validate_template(&template)?;
+    Ok(template)
+}
+
+/// Check a template against the same rules `CreateRoom` applies
+pub fn validate_template(template: &RoomTemplate) -> Result<()> {
+    if !(MIN_PLAYERS_TO_START..=MAX_ROOM_CAPACITY).contains(&template.max_players) {
+        anyhow::bail!("Max players must be between {} and {}", MIN_PLAYERS_TO_START, MAX_ROOM_CAPACITY);
+    }
+
+    let candidates = if template.options.custom_pool.is_empty() {
+        DraftRoom::initialize_pool()
+    } else {
+        if let Some(error) = validate_pool(&template.options.custom_pool).into_iter().next() {
+            anyhow::bail!("{}", error);
+        }
+        template.options.custom_pool.clone()
+    };
+    validate_draft_config(template.max_players, DEFAULT_MAX_ROUNDS, template.options.ban_rounds, &candidates)?;
+    validate_captains(&template.options.captains)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use livedraft_arena::DraftItem;
+
+    fn template() -> RoomTemplate {
+        RoomTemplate {
+            max_players: 2,
+            options: RoomOptions {
+                reveal_after_round: true,
+                final_round_min_power: Some(80),
+                round_weights: vec![1, 2, 3],
+                turn_duration_secs: Some(30),
+                budget: Some(150),
+                ban_rounds: 1,
+                custom_pool: vec![
+                    DraftItem { quantity: 4, cost: 60, ..DraftItem::new(1, "Rook", 50) },
+                    DraftItem { quantity: 4, tags: vec!["control".to_string()], ..DraftItem::new(2, "Bishop", 30) },
+                ],
+                handicap_first_pick: true,
+                ..RoomOptions::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_share_code_round_trips_settings() {
+        let code = encode_share_code(&template());
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decode_share_code(&code).unwrap(), template());
+
+        let default_room = RoomTemplate { max_players: 4, options: RoomOptions::default() };
+        assert_eq!(decode_share_code(&encode_share_code(&default_room)).unwrap(), default_room);
+    }
+
+    #[test]
+    fn test_share_code_rejects_garbage_and_invalid_settings() {
+        assert!(decode_share_code("not a share code!").is_err());
+        assert!(decode_share_code(&URL_SAFE_NO_PAD.encode(b"{\"max_players\":2}")).is_err());
+
+        let too_many = RoomTemplate { max_players: MAX_ROOM_CAPACITY + 1, ..template() };
+        assert!(decode_share_code(&encode_share_code(&too_many)).is_err());
+
+        let mut small_pool = template();
+        small_pool.options.custom_pool.truncate(1);
+        assert!(decode_share_code(&encode_share_code(&small_pool)).is_err());
+
+        // Two stacks of 4 cover 6 picks but not the 2 bans on top
+        let mut banned_out = template();
+        banned_out.options.custom_pool = vec![
+            DraftItem { quantity: 3, ..DraftItem::new(1, "Rook", 50) },
+            DraftItem { quantity: 4, ..DraftItem::new(2, "Bishop", 30) },
+        ];
+        assert!(decode_share_code(&encode_share_code(&banned_out)).is_err());
+    }
+}
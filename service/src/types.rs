@@ -1,4 +1,4 @@
-use async_graphql::{Enum, SimpleObject};
+use async_graphql::{Enum, InputObject, SimpleObject};
 use linera_core::data_types::ChainId;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, Enum, Copy, PartialEq, Eq)]
 pub enum RoomStatus {
     Waiting,
+    CaptainPicking,
+    Banning,
     Drafting,
     Finished,
 }
@@ -16,6 +18,10 @@ pub struct DraftItem {
     pub id: u8,
     pub name: String,
     pub power: u32,
+    pub quantity: u8, // Copies remaining in the pool
+    pub tags: Vec<String>,
+    pub cost: u32, // Budget spent by picking it in salary-cap rooms
+    pub weight: u32, // Relative chance of being drawn when the pool is sampled
 }
 
 /// Draft room metadata matching the contract struct
@@ -34,6 +40,68 @@ pub struct RoomData {
     pub max_players: u8,
     pub current_players: u8,
     pub status: RoomStatus,
+    pub is_private: bool, // Joining needs the room password
+}
+
+/// One room in a lobby export: its listing plus who created it and when
+///
+/// Flattens `RoomData`, so an export also reads back as `Vec<RoomData>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LobbyExportEntry {
+    #[serde(flatten)]
+    pub room: RoomData,
+    pub creator: Option<String>, // None if the room's chain couldn't be queried
+    pub created_at: String, // RFC 3339, UTC
+}
+
+/// A room that has sat in Waiting without filling, reported to operators
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct StaleRoom {
+    pub room: RoomData,
+    pub created_at: String, // RFC 3339, UTC
+    pub age_secs: u64,
+}
+
+/// What the frontend needs to build and present a room's invite link
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ShareInfo {
+    pub chain_id: String, // ChainId as string for GraphQL
+    pub room_name: String,
+    pub requires_password: bool,
+    pub is_private: bool,
+    pub status: RoomStatus,
+}
+
+/// A room players have reported, for moderator review
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ReportedRoom {
+    pub chain_id: String, // ChainId as string for GraphQL
+    pub report_count: u32,
+    pub reasons: Vec<String>,
+}
+
+/// How often an item has been drafted across finalized rooms
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject, PartialEq)]
+pub struct PopularItem {
+    pub name: String,
+    pub pick_count: u32,
+}
+
+/// One pick slot in a room's draft
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject, PartialEq)]
+pub struct TurnSlot {
+    pub round: u8,
+    pub pick_in_round: u8, // 0-based, like the contract's current_turn
+    pub player: String, // Owner as string for GraphQL
+    pub completed: bool,
+}
+
+/// Lobby room listing with its live roster
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RoomOverview {
+    pub room: RoomData,
+    pub players: Vec<String>, // Owner addresses as strings
+    pub current_player: Option<String>, // None unless drafting
 }
 
 /// Draft room state for individual room queries
@@ -46,7 +114,54 @@ pub struct DraftRoomState {
     pub round: u8,
     pub max_rounds: u8,
     pub pool: Vec<DraftItem>,
+    pub staged_pool: Vec<DraftItem>, // Creator's custom pool, used when the draft starts
+    pub turn_order: Vec<String>, // Effective pick order for round one
+    pub reserved_players: Vec<String>, // Seated by the creator rather than self-joined
     pub status: RoomStatus,
+    pub reveal_after_round: bool,
+    pub final_round_min_power: Option<u32>, // Minimum power for final-round picks
+    pub turn_duration_secs: Option<u64>, // Seconds per pick, if the room has a turn clock
+    pub min_pick_interval_secs: Option<u64>, // Seconds into a turn before a pick is accepted
+    pub auto_close_after_secs: Option<u64>, // Seconds after finalizing before the Lobby may delist the room
+    pub turn_deadline: Option<String>, // RFC 3339; when the current turn's clock runs out
+    pub turn_started_at: Option<String>, // RFC 3339; when the current turn began
+    pub budgets: Vec<PlayerBudget>, // Empty unless the room has a salary cap
+    pub spectator_count: u8,
+    pub max_spectators: u8,
+    pub time_requests_remaining: Vec<PlayerTimeRequests>, // Self-extensions left per player
+    pub preview: bool, // Rehearsal room: picks are not kept
+    pub bans_remaining: u32, // Bans still to be made before picking starts
+    pub almost_done: bool, // Only the final pick of the draft remains
+    pub banned_items: Vec<DraftItem>, // In the order they were banned
+    pub pool_seed: Option<u64>, // Seed the pool was sampled with, if the room samples
+    pub handicap_first_pick: bool, // The first pick may not take the highest-power item
+    pub restricted_item_id: Option<u8>, // Item the current pick may not take under the handicap
+    pub is_private: bool, // Joining needs the room password
+    pub ready_players: Vec<String>, // Players who have confirmed they are present, in seat order
+    pub captains: Vec<String>, // Empty unless captains draft squads before the item draft
+    pub squads: Vec<Squad>, // One per captain, in captain order
+    pub version: u64, // Matches `stateVersion` in the result of the operation that produced it
+}
+
+/// How many more times a player may extend their own turn
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerTimeRequests {
+    pub player: String, // Owner address as string
+    pub remaining: u8,
+}
+
+/// The players a captain has drafted so far
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Squad {
+    pub captain: String, // Owner address as string
+    pub players: Vec<String>, // In the order the captain drafted them
+}
+
+/// A player's remaining budget in a salary-cap room
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerBudget {
+    pub player: String, // Owner address as string
+    pub remaining: u32,
 }
 
 /// Player picks for GraphQL response
@@ -56,11 +171,222 @@ pub struct PlayerPicks {
     pub items: Vec<DraftItem>,
 }
 
+/// One pick from a room's history, for paging through large drafts
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PickEntry {
+    pub pick_number: u32, // 1-based position in the whole draft, hidden picks included
+    pub player: String, // Owner address as string
+    pub round: u8,
+    pub item: DraftItem,
+}
+
+/// A player's picks as bare item ids, for clients that cache the pool
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerPickIds {
+    pub player: String, // Owner address as string
+    pub item_ids: Vec<u8>,
+}
+
+/// A player's running score for the scoreboard
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerScore {
+    pub player: String, // Owner address as string
+    pub total_power: u64,
+    pub pick_count: u32,
+}
+
+/// Two players' power side by side, for head-to-head views
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerComparison {
+    pub a: PlayerScore,
+    pub b: PlayerScore,
+    pub delta: i64, // `a`'s total power minus `b`'s
+    pub rounds: Vec<RoundComparison>, // Every round of the draft, first to last
+}
+
+/// Two players' power picked in a single round
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RoundComparison {
+    pub round: u8,
+    pub a_power: u64,
+    pub b_power: u64,
+    pub delta: i64, // `a_power` minus `b_power`
+}
+
+/// A player's place in the standings
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct StandingData {
+    pub player: String, // Owner address as string
+    pub raw_power: u64,
+    pub weighted_power: u64, // Power scaled by the room's round weights
+}
+
+/// A player's total picked power in the final results
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerTotal {
+    pub player: String, // Owner address as string
+    pub total_power: u64,
+}
+
+/// A finalized room's outcome as the Lobby recorded it, even once the room is delisted
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RoomResult {
+    pub chain_id: String, // ChainId as string for GraphQL
+    pub room_name: String,
+    pub winner: Option<String>, // Owner address as string
+    pub finalized_at: String, // RFC 3339
+}
+
+/// Per-player totals and the winner recorded by `FinalizeDraft`
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct DraftResults {
+    pub totals: Vec<PlayerTotal>, // In seating order
+    pub winner: Option<String>, // None until the draft is finalized
+    pub conceded_by: Option<String>, // Set when the draft ended early by concession
+}
+
+/// What is still needed before a draft can be started
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct StartRequirements {
+    pub min_players: u8,
+    pub current_players: u8,
+    pub can_start: bool,
+    pub is_creator: bool,
+}
+
+/// Whose turn it is in a room, relative to the requesting player
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct CurrentTurn {
+    pub player: Option<String>, // Owner address; None unless drafting
+    pub is_my_turn: bool, // False for anonymous requests
+}
+
+/// Room state and picks returned when resuming from a resume token
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ResumeData {
+    pub player_id: String,
+    pub room: DraftRoomState,
+    pub picks: Vec<DraftItem>,
+}
+
+/// The caller's service identity
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerInfo {
+    pub player_id: String,
+    pub owner: Option<String>, // Owner address as string; None for anonymous sessions
+}
+
+/// Who a mutation from this session is signed by, next to the owner it is meant to act as
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct SigningInfo {
+    pub player_owner: Option<String>, // Owner derived from the player ID; None for anonymous sessions
+    pub signer: Option<String>, // Owner of the wallet key operations are submitted with, if known
+    pub signs_as_player: bool, // Whether the contract will see the derived owner as the signer
+}
+
+/// Everything a fresh client needs to render the lobby, in one response
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct Bootstrap {
+    pub player: PlayerInfo,
+    pub rooms: Vec<RoomData>,
+    pub server_time: String, // RFC 3339, UTC
+}
+
+/// Result of checking a prospective custom pool
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PoolValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+}
+
+/// Whether an operation would currently be accepted, and why not if it wouldn't
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ExecutionCheck {
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+/// Whether a pick would currently be accepted, and why not if it wouldn't
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct PickValidation {
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+/// The randomness a room's pool was drawn with, for independent verification
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct RandomnessInfo {
+    pub seed: Option<String>, // Decimal u64; null until recorded or revealed
+    pub commitment: Option<String>, // Hex SHA-256 of the seed's big-endian bytes, if the creator committed
+}
+
+/// Operations that can be checked with `canExecute`
+#[derive(Debug, Clone, Serialize, Deserialize, Enum, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    CreateRoom,
+    JoinRoom,
+    StartDraft,
+    PickItem,
+    FinalizeDraft,
+}
+
 /// Operation inputs for mutations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, InputObject)]
 pub struct CreateRoomInput {
     pub room_name: String,
     pub max_players: u8, // Match contract u8 type
+    pub reveal_after_round: Option<bool>, // Defaults to open picks
+    pub final_round_min_power: Option<u32>, // No threshold by default
+    pub round_weights: Option<Vec<u32>>, // Equal weights by default
+    pub turn_duration_secs: Option<u64>, // No turn clock by default
+    pub min_pick_interval_secs: Option<u64>, // Picks may be instant by default
+    pub auto_close_after_secs: Option<u64>, // Finished rooms stay listed by default
+    pub budget: Option<u32>, // No salary cap by default
+    pub max_spectators: Option<u8>, // Contract default when omitted
+    pub preview: Option<bool>, // Real draft by default
+    pub ban_rounds: Option<u8>, // No ban phase by default
+    pub pool_size: Option<u8>, // Use the whole candidate pool by default
+    pub pool_seed: Option<u64>, // Derived from the block time when omitted
+    pub seed_commitment: Option<String>, // Commit to a secret seed instead, revealed before the draft starts
+    pub custom_pool: Option<Vec<DraftItemInput>>, // Wave-5 cards when omitted or empty
+    pub handicap_first_pick: Option<bool>, // No first-pick handicap by default
+    pub password: Option<String>, // Makes the room private; only its hash leaves the service
+    pub captains: Option<Vec<String>>, // Two owners who draft the other players into squads first
+}
+
+/// A custom pool item submitted by the room creator
+#[derive(Debug, Clone, Serialize, Deserialize, InputObject)]
+pub struct DraftItemInput {
+    pub id: u8,
+    pub name: String,
+    pub power: u32,
+    pub quantity: Option<u8>, // Defaults to a single copy
+    pub tags: Option<Vec<String>>, // Defaults to no tags
+    pub cost: Option<u32>, // Free by default
+    pub weight: Option<u32>, // Defaults to 1
+}
+
+/// An operation to check with `canExecute`
+#[derive(Debug, Clone, Serialize, Deserialize, InputObject)]
+pub struct OperationInput {
+    pub kind: OperationKind,
+    pub item_id: Option<u8>, // Required for PickItem
+    pub password: Option<String>, // For JoinRoom on a private room
+}
+
+/// Progress of an operation queued with `enqueueOperation`
+#[derive(Debug, Clone, Serialize, Deserialize, Enum, Copy, PartialEq, Eq)]
+pub enum JobState {
+    Pending,
+    Done,
+    Failed,
+}
+
+/// Status of a queued operation, with its result once submitted
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct JobStatus {
+    pub state: JobState,
+    pub result: Option<OperationResult>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,4 +400,15 @@ pub struct OperationResult {
     pub success: bool,
     pub message: String,
     pub transaction_hash: Option<String>,
+    pub request_id: String, // Correlation ID, also sent as the X-Request-Id header
+    pub server_time: String, // RFC 3339, for client clock-skew correction
+    pub state_version: Option<u64>, // Room version after the operation, when it could be read
+}
+
+/// What `finalizePending` did for one room
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct FinalizeOutcome {
+    pub chain_id: String,
+    pub result: Option<OperationResult>, // FinalizeDraft outcome; null when the room was skipped
+    pub skipped: Option<String>, // Why nothing was submitted for this room
 }
\ No newline at end of file